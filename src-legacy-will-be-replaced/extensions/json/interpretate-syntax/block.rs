@@ -0,0 +1,22 @@
+use crate::extensions::json::interpretate_types::{to_json, JsonError};
+use crate::extensions::output::{OutputBackend, OutputError};
+use crate::interpretator::prototypes::types::object::object::ObjectRef;
+
+/// Записывает значение в JSON с отступами, по элементу на строку.
+pub fn write(object: &ObjectRef) -> Result<String, JsonError> {
+    let value = to_json(object)?;
+    Ok(serde_json::to_string_pretty(&value).expect("serde_json::Value всегда сериализуется"))
+}
+
+/// `[Marker:Output<...> as JSON]`
+pub struct JsonBackend;
+
+impl OutputBackend for JsonBackend {
+    fn name(&self) -> &'static str {
+        "JSON"
+    }
+
+    fn write(&self, root: &ObjectRef) -> Result<String, OutputError> {
+        Ok(write(root)?)
+    }
+}
@@ -0,0 +1,7 @@
+use crate::extensions::json::interpretate_types::{to_json, JsonError};
+use crate::interpretator::prototypes::types::object::object::ObjectRef;
+
+/// Записывает значение в JSON одной строкой: `{"key":"value"}`.
+pub fn write(object: &ObjectRef) -> Result<String, JsonError> {
+    Ok(to_json(object)?.to_string())
+}
@@ -0,0 +1,205 @@
+use crate::interpretator::prototypes::types::object::array::ArrayValue;
+use crate::interpretator::prototypes::types::object::dictionary::DictionaryValue;
+use crate::interpretator::prototypes::types::object::object::{Object, ObjectRef};
+use crate::interpretator::prototypes::types::object::routine::routine::RoutineValue;
+use crate::interpretator::prototypes::types::primitive::boolean::BooleanValue;
+use crate::interpretator::prototypes::types::primitive::number::big_integer::BigInt;
+use crate::interpretator::prototypes::types::primitive::number::hexadecimal::Radix;
+use crate::interpretator::prototypes::types::primitive::undefined::UndefinedValue;
+use crate::interpretator::stdlib::core::prelude::Prelude;
+use crate::interpretator::stdlib::core::primitives::{
+    as_number, as_text, is_empty_value, type_name, Number,
+};
+use indexmap::IndexMap;
+use serde_json::{Map, Number as JsonNumber, Value};
+use thiserror::Error;
+
+/// Значение, которое нельзя записать в JSON.
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum JsonError {
+    #[error("значение типа {type_name} нельзя записать в JSON{}", path_suffix(.path))]
+    Unrepresentable { type_name: String, path: String },
+    #[error("число {value} нельзя записать в JSON{}", path_suffix(.path))]
+    NonFiniteNumber { value: f64, path: String },
+}
+
+pub(crate) fn path_suffix(path: &str) -> String {
+    if path.is_empty() {
+        String::new()
+    } else {
+        format!(" (по пути «{}»)", path)
+    }
+}
+
+/// Переводит дерево значений LaCoN в `serde_json::Value`.
+///
+/// Соответствие типов:
+/// - `Dictionary` → объект, `Array` → массив;
+/// - `Int`, `Float` и `Fraction` → число; `Infinity` и `NaN` в JSON не существуют и дают ошибку;
+/// - `BigInteger` → строка с десятичной записью, чтобы не потерять цифры у читателей
+///   с 64-битными числами; это запись в одну сторону: `from_json` прочтёт её как `String`;
+/// - `String` и `MultilineString` → строка, `Boolean` → `true`/`false`;
+/// - `nil`, `none` и `auto` → `null`; `undefined` — ошибка, такого значения нет;
+/// - `Function` и `Procedure` — ошибка: код в данные не переносится;
+/// - всё остальное, у чего нет пары в JSON, но есть текстовая запись
+///   (операторы `"+"`, величины с единицами `"15kW"`, цвета `"#ff8800"`), → строка в синтаксисе LaCoN.
+///
+/// Ключи словарей идут в порядке объявления.
+pub fn to_json(object: &ObjectRef) -> Result<Value, JsonError> {
+    convert(&object.borrow(), &mut Vec::new())
+}
+
+fn convert(object: &Object, path: &mut Vec<String>) -> Result<Value, JsonError> {
+    if let Some(dictionary) = object.downcast::<DictionaryValue>() {
+        let mut map = Map::new();
+        for (key, value) in &dictionary.entries {
+            path.push(key.clone());
+            let value = convert(&value.borrow(), path)?;
+            path.pop();
+            map.insert(key.clone(), value);
+        }
+        return Ok(Value::Object(map));
+    }
+    if let Some(array) = object.downcast::<ArrayValue>() {
+        let mut values = Vec::with_capacity(array.elements.len());
+        for (index, element) in array.elements.iter().enumerate() {
+            path.push((index + 1).to_string());
+            values.push(convert(&element.borrow(), path)?);
+            path.pop();
+        }
+        return Ok(Value::Array(values));
+    }
+    if let Some(number) = as_number(object) {
+        return match number {
+            Number::Integer(value) => Ok(Value::from(value)),
+            Number::Big(value) => Ok(Value::String(value.to_string())),
+            Number::Fraction(value) => float_number(value.to_f64(), path),
+            Number::Float(value) => float_number(value, path),
+        };
+    }
+    if let Some(text) = as_text(object) {
+        return Ok(Value::String(text.to_string()));
+    }
+    if let Some(BooleanValue(value)) = object.downcast::<BooleanValue>() {
+        return Ok(Value::Bool(*value));
+    }
+    let unrepresentable = object.downcast::<UndefinedValue>().is_some()
+        || object.downcast::<RoutineValue>().is_some();
+    if unrepresentable {
+        return Err(JsonError::Unrepresentable {
+            type_name: type_name(object),
+            path: path.join("."),
+        });
+    }
+    if is_empty_value(object) {
+        return Ok(Value::Null);
+    }
+    Ok(Value::String(object.to_string()))
+}
+
+fn float_number(value: f64, path: &[String]) -> Result<Value, JsonError> {
+    JsonNumber::from_f64(value)
+        .map(Value::Number)
+        .ok_or_else(|| JsonError::NonFiniteNumber {
+            value,
+            path: path.join("."),
+        })
+}
+
+/// Чтение JSON: `serde_json::Value` → дерево значений LaCoN.
+///
+/// - объект → `Dictionary`, массив → `Array`;
+/// - целое число → `Int`, а если оно не помещается в `Int` — `BigInteger` со всеми цифрами;
+///   любое другое число → `Float`;
+/// - строка → `String`, `true`/`false` → `Boolean`, `null` → `nil`.
+///
+/// Для любого JSON без целых за пределами `Int` верно `to_json(from_json(value)) == value`;
+/// такие целые возвращаются строкой, как любой `BigInteger`. В другую сторону тождество
+/// не держится: `none` и `auto` возвращаются как `nil`, а записанные строкой операторы,
+/// величины, цвета и `BigInteger` остаются строками.
+pub fn from_json(prelude: &Prelude, value: &Value) -> ObjectRef {
+    match value {
+        Value::Null => prelude.nil_value(),
+        Value::Bool(value) => prelude.boolean_value(*value),
+        // С `arbitrary_precision` число хранит запись из текста, поэтому цифры длинных целых
+        // доступны целиком
+        Value::Number(number) => match number.as_i64() {
+            Some(value) => prelude.integer_value(value),
+            None => match BigInt::parse_radix(&number.to_string(), 10) {
+                Some(value) => prelude.big_integer_value(value, Radix::Decimal),
+                None => prelude.float_value(number.as_f64().unwrap_or(f64::NAN)),
+            },
+        },
+        Value::String(text) => prelude.string_value(text.clone()),
+        Value::Array(values) => prelude.array_value(
+            values
+                .iter()
+                .map(|value| from_json(prelude, value))
+                .collect(),
+        ),
+        Value::Object(map) => {
+            let entries: IndexMap<String, ObjectRef> = map
+                .iter()
+                .map(|(key, value)| (key.clone(), from_json(prelude, value)))
+                .collect();
+            prelude.dictionary_value(entries)
+        }
+    }
+}
+
+/// Читает JSON-текст в дерево значений LaCoN по правилам `from_json`.
+pub fn parse_json(prelude: &Prelude, text: &str) -> Result<ObjectRef, serde_json::Error> {
+    let value: Value = serde_json::from_str(text)?;
+    Ok(from_json(prelude, &value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interpretator::engine::engine::Engine;
+    use serde_json::json;
+
+    #[test]
+    fn test_json_round_trip() {
+        let engine = Engine::new();
+        let source =
+            "name \"lamp\"\npower 15kW\nops [+, *]\nlevels [1, 2.5, nil]\nnested { on true }\n";
+        let root = engine.evaluate_document(source).unwrap();
+        let value = to_json(&root).unwrap();
+        assert_eq!(
+            value,
+            json!({
+                "name": "lamp",
+                "power": "15kW",
+                "ops": ["+", "*"],
+                "levels": [1, 2.5, null],
+                "nested": { "on": true }
+            })
+        );
+        assert_eq!(
+            to_json(&from_json(engine.prelude(), &value)).unwrap(),
+            value
+        );
+
+        let value: Value =
+            serde_json::from_str("[9223372036854775808, -12345678901234567890123, 1e3]").unwrap();
+        let imported = from_json(engine.prelude(), &value);
+        assert_eq!(
+            imported.borrow().to_string(),
+            "[9223372036854775808, -12345678901234567890123, 1000.0]"
+        );
+        assert_eq!(
+            to_json(&imported).unwrap(),
+            json!(["9223372036854775808", "-12345678901234567890123", 1000.0])
+        );
+
+        let root = engine
+            .evaluate_document("inner { bad undefined }\n")
+            .unwrap();
+        let error = to_json(&root).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "значение типа Undefined нельзя записать в JSON (по пути «inner.bad»)"
+        );
+    }
+}
@@ -0,0 +1,4 @@
+#[path = "interpretate-syntax/mod.rs"]
+pub mod interpretate_syntax;
+#[path = "interpretate-types.rs"]
+pub mod interpretate_types;
@@ -0,0 +1,4 @@
+pub mod json;
+pub mod output;
+pub mod toml;
+pub mod yaml;
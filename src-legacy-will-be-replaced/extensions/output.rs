@@ -0,0 +1,150 @@
+use crate::extensions::json::interpretate_syntax::block::JsonBackend;
+use crate::extensions::json::interpretate_types::{path_suffix, JsonError};
+use crate::extensions::toml::TomlBackend;
+use crate::extensions::yaml::YamlBackend;
+use crate::interpretator::prototypes::types::object::array::ArrayValue;
+use crate::interpretator::prototypes::types::object::dictionary::DictionaryValue;
+use crate::interpretator::prototypes::types::object::object::{Object, ObjectRef};
+use crate::interpretator::prototypes::types::object::routine::routine::RoutineValue;
+use crate::interpretator::prototypes::types::primitive::boolean::BooleanValue;
+use crate::interpretator::prototypes::types::primitive::undefined::UndefinedValue;
+use crate::interpretator::stdlib::core::primitives::{
+    as_number, as_text, is_empty_value, type_name, Number,
+};
+use indexmap::IndexMap;
+use thiserror::Error;
+
+/// Ошибка записи документа в формат вывода.
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum OutputError {
+    #[error(transparent)]
+    Json(#[from] JsonError),
+    #[error("значение типа {type_name} нельзя записать в {format}{}", path_suffix(.path))]
+    Unrepresentable {
+        format: &'static str,
+        type_name: String,
+        path: String,
+    },
+    #[error("в {format} нет пустых значений, {type_name} записать нельзя{}", path_suffix(.path))]
+    Empty {
+        format: &'static str,
+        type_name: String,
+        path: String,
+    },
+    #[error("элементы массива TOML должны быть одного вида, а здесь {first} и {second}{}", path_suffix(.path))]
+    MixedArray {
+        first: &'static str,
+        second: &'static str,
+        path: String,
+    },
+    #[error("корнем документа {format} может быть только словарь")]
+    RootNotTable { format: &'static str },
+}
+
+/// Формат вывода для `[Marker:Output<...> as FORMAT]`.
+pub trait OutputBackend {
+    /// Имя формата для сообщений: `YAML`
+    fn name(&self) -> &'static str;
+
+    fn write(&self, root: &ObjectRef) -> Result<String, OutputError>;
+}
+
+/// Формат по имени из маркера, без учёта регистра.
+pub fn backend_for(format: &str) -> Option<Box<dyn OutputBackend>> {
+    match format.to_ascii_uppercase().as_str() {
+        "JSON" => Some(Box::new(JsonBackend)),
+        "YAML" | "YML" => Some(Box::new(YamlBackend)),
+        "TOML" => Some(Box::new(TomlBackend)),
+        _ => None,
+    }
+}
+
+/// Дерево значений, подготовленное к записи: типы сведены к общим для форматов,
+/// ключи идут в порядке объявления, так что один документ всегда даёт один и тот же текст.
+#[derive(Debug, Clone, PartialEq)]
+pub enum OutputValue {
+    /// `nil`, `none` и `auto`; что с ними делать, решает формат
+    Empty {
+        type_name: String,
+    },
+    Boolean(bool),
+    Integer(i64),
+    Float(f64),
+    String(String),
+    Array(Vec<OutputValue>),
+    Table(IndexMap<String, OutputValue>),
+}
+
+impl OutputValue {
+    /// Переводит значение LaCoN в дерево вывода по тем же правилам, что и экспорт в JSON:
+    /// `undefined` и функции записать нельзя, а значения без собственного вида
+    /// (операторы, величины, цвета) записываются строкой в синтаксисе LaCoN.
+    pub fn from_object(object: &ObjectRef, format: &'static str) -> Result<Self, OutputError> {
+        lower(&object.borrow(), format, &mut Vec::new())
+    }
+}
+
+fn lower(
+    object: &Object,
+    format: &'static str,
+    path: &mut Vec<String>,
+) -> Result<OutputValue, OutputError> {
+    if let Some(dictionary) = object.downcast::<DictionaryValue>() {
+        let mut table = IndexMap::with_capacity(dictionary.entries.len());
+        for (key, value) in &dictionary.entries {
+            path.push(key.clone());
+            let value = lower(&value.borrow(), format, path)?;
+            path.pop();
+            table.insert(key.clone(), value);
+        }
+        return Ok(OutputValue::Table(table));
+    }
+    if let Some(array) = object.downcast::<ArrayValue>() {
+        let mut values = Vec::with_capacity(array.elements.len());
+        for (index, element) in array.elements.iter().enumerate() {
+            path.push((index + 1).to_string());
+            values.push(lower(&element.borrow(), format, path)?);
+            path.pop();
+        }
+        return Ok(OutputValue::Array(values));
+    }
+    if let Some(number) = as_number(object) {
+        return Ok(match number {
+            Number::Integer(value) => OutputValue::Integer(value),
+            // Целые длиннее `i64` форматы вывода не читают без потери точности
+            Number::Big(value) => OutputValue::String(value.to_string()),
+            Number::Fraction(value) => OutputValue::Float(value.to_f64()),
+            Number::Float(value) => OutputValue::Float(value),
+        });
+    }
+    if let Some(text) = as_text(object) {
+        return Ok(OutputValue::String(text.to_string()));
+    }
+    if let Some(BooleanValue(value)) = object.downcast::<BooleanValue>() {
+        return Ok(OutputValue::Boolean(*value));
+    }
+    let unrepresentable = object.downcast::<UndefinedValue>().is_some()
+        || object.downcast::<RoutineValue>().is_some();
+    if unrepresentable {
+        return Err(OutputError::Unrepresentable {
+            format,
+            type_name: type_name(object),
+            path: path.join("."),
+        });
+    }
+    if is_empty_value(object) {
+        return Ok(OutputValue::Empty {
+            type_name: type_name(object),
+        });
+    }
+    Ok(OutputValue::String(object.to_string()))
+}
+
+/// Запись дробного числа, в которой видно, что оно дробное: `10.0`, а не `10`.
+pub(crate) fn float_literal(value: f64) -> String {
+    if value.fract() == 0.0 && value.abs() < 1e16 {
+        format!("{:.1}", value)
+    } else {
+        value.to_string()
+    }
+}
@@ -0,0 +1,245 @@
+use crate::extensions::output::{float_literal, OutputBackend, OutputError, OutputValue};
+use crate::interpretator::prototypes::types::object::object::ObjectRef;
+use indexmap::IndexMap;
+
+const FORMAT: &str = "TOML";
+
+/// `[Marker:Output<Dictionary> as TOML]`.
+///
+/// Корнем может быть только словарь. Вложенные словари становятся таблицами `[a.b]`,
+/// массивы словарей — `[[a.b]]`, остальное пишется в строку. В TOML нет пустых значений,
+/// поэтому `nil`, `none` и `auto` дают ошибку, как и массивы с элементами разного вида.
+pub struct TomlBackend;
+
+impl OutputBackend for TomlBackend {
+    fn name(&self) -> &'static str {
+        FORMAT
+    }
+
+    fn write(&self, root: &ObjectRef) -> Result<String, OutputError> {
+        let table = match OutputValue::from_object(root, FORMAT)? {
+            OutputValue::Table(table) => table,
+            _ => return Err(OutputError::RootNotTable { format: FORMAT }),
+        };
+        let mut sections = Vec::new();
+        write_table(&table, &[], false, &mut sections)?;
+        Ok(sections.join("\n\n"))
+    }
+}
+
+/// Записывает таблицу и все вложенные в неё таблицы; каждая таблица — отдельная секция.
+fn write_table(
+    table: &IndexMap<String, OutputValue>,
+    path: &[String],
+    array_item: bool,
+    sections: &mut Vec<String>,
+) -> Result<(), OutputError> {
+    let mut lines = Vec::new();
+    if !path.is_empty() {
+        let header = path
+            .iter()
+            .map(|key| key_literal(key))
+            .collect::<Vec<_>>()
+            .join(".");
+        lines.push(if array_item {
+            format!("[[{}]]", header)
+        } else {
+            format!("[{}]", header)
+        });
+    }
+    // Пары ключ–значение идут до вложенных таблиц, иначе они попали бы в последнюю из них
+    for (key, value) in table {
+        if section_kind(value).is_none() {
+            let key_path = join_path(path, key);
+            lines.push(format!(
+                "{} = {}",
+                key_literal(key),
+                inline(value, &key_path)?
+            ));
+        }
+    }
+    if !path.is_empty() || !lines.is_empty() {
+        sections.push(lines.join("\n"));
+    }
+
+    for (key, value) in table {
+        let mut nested = path.to_vec();
+        nested.push(key.clone());
+        match (section_kind(value), value) {
+            (Some(Section::Table), OutputValue::Table(table)) => {
+                write_table(table, &nested, false, sections)?
+            }
+            (Some(Section::ArrayOfTables), OutputValue::Array(items)) => {
+                for item in items {
+                    if let OutputValue::Table(table) = item {
+                        write_table(table, &nested, true, sections)?;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+enum Section {
+    Table,
+    ArrayOfTables,
+}
+
+/// Пишется ли значение отдельной секцией, а не в строку.
+fn section_kind(value: &OutputValue) -> Option<Section> {
+    match value {
+        OutputValue::Table(_) => Some(Section::Table),
+        OutputValue::Array(items)
+            if !items.is_empty()
+                && items
+                    .iter()
+                    .all(|item| matches!(item, OutputValue::Table(_))) =>
+        {
+            Some(Section::ArrayOfTables)
+        }
+        _ => None,
+    }
+}
+
+fn inline(value: &OutputValue, path: &str) -> Result<String, OutputError> {
+    Ok(match value {
+        OutputValue::Empty { type_name } => {
+            return Err(OutputError::Empty {
+                format: FORMAT,
+                type_name: type_name.clone(),
+                path: path.to_string(),
+            })
+        }
+        OutputValue::Boolean(value) => value.to_string(),
+        OutputValue::Integer(value) => value.to_string(),
+        OutputValue::Float(value) if value.is_nan() => "nan".to_string(),
+        OutputValue::Float(value) if value.is_infinite() => {
+            if *value > 0.0 { "inf" } else { "-inf" }.to_string()
+        }
+        OutputValue::Float(value) => float_literal(*value),
+        OutputValue::String(text) => string_literal(text),
+        OutputValue::Array(items) => {
+            check_homogeneous(items, path)?;
+            let mut parts = Vec::with_capacity(items.len());
+            for (index, item) in items.iter().enumerate() {
+                parts.push(inline(item, &join_path_index(path, index))?);
+            }
+            format!("[{}]", parts.join(", "))
+        }
+        OutputValue::Table(table) => {
+            let mut parts = Vec::with_capacity(table.len());
+            for (key, value) in table {
+                let key_path = format!("{}.{}", path, key);
+                parts.push(format!(
+                    "{} = {}",
+                    key_literal(key),
+                    inline(value, &key_path)?
+                ));
+            }
+            if parts.is_empty() {
+                "{}".to_string()
+            } else {
+                format!("{{ {} }}", parts.join(", "))
+            }
+        }
+    })
+}
+
+/// Вид значения для проверки однородности массива.
+fn kind(value: &OutputValue) -> &'static str {
+    match value {
+        OutputValue::Empty { .. } => "пустое значение",
+        OutputValue::Boolean(_) => "логическое значение",
+        OutputValue::Integer(_) => "целое число",
+        OutputValue::Float(_) => "дробное число",
+        OutputValue::String(_) => "строка",
+        OutputValue::Array(_) => "массив",
+        OutputValue::Table(_) => "таблица",
+    }
+}
+
+fn check_homogeneous(items: &[OutputValue], path: &str) -> Result<(), OutputError> {
+    let first = match items.first() {
+        Some(first) => kind(first),
+        None => return Ok(()),
+    };
+    match items.iter().map(kind).find(|other| *other != first) {
+        Some(second) => Err(OutputError::MixedArray {
+            first,
+            second,
+            path: path.to_string(),
+        }),
+        None => Ok(()),
+    }
+}
+
+fn join_path(path: &[String], key: &str) -> String {
+    let mut parts = path.to_vec();
+    parts.push(key.to_string());
+    parts.join(".")
+}
+
+fn join_path_index(path: &str, index: usize) -> String {
+    format!("{}.{}", path, index + 1)
+}
+
+/// Ключ без кавычек, если он состоит только из латиницы, цифр, `_` и `-`.
+fn key_literal(key: &str) -> String {
+    let bare = !key.is_empty()
+        && key
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || ch == '_' || ch == '-');
+    if bare {
+        key.to_string()
+    } else {
+        string_literal(key)
+    }
+}
+
+fn string_literal(text: &str) -> String {
+    let mut quoted = String::with_capacity(text.len() + 2);
+    quoted.push('"');
+    for ch in text.chars() {
+        match ch {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\t' => quoted.push_str("\\t"),
+            '\r' => quoted.push_str("\\r"),
+            ch if ch.is_control() => quoted.push_str(&format!("\\u{:04X}", ch as u32)),
+            ch => quoted.push(ch),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interpretator::engine::engine::Engine;
+
+    fn write(source: &str) -> Result<String, OutputError> {
+        let root = Engine::new().evaluate_document(source).unwrap();
+        TomlBackend.write(&root)
+    }
+
+    #[test]
+    fn test_toml_output() {
+        let source = "name lamp\nsizes [1, 2]\nlight {\n\tcolor \"тёплый белый\"\n\tmodes [{ level 1 }, { level 2 }]\n}\n";
+        assert_eq!(
+            write(source).unwrap(),
+            "name = \"lamp\"\nsizes = [1, 2]\n\n[light]\ncolor = \"тёплый белый\"\n\n[[light.modes]]\nlevel = 1\n\n[[light.modes]]\nlevel = 2"
+        );
+        assert_eq!(
+            write("list [1, \"два\"]\n").unwrap_err().to_string(),
+            "элементы массива TOML должны быть одного вида, а здесь целое число и строка (по пути «list»)"
+        );
+        assert_eq!(
+            write("options { timeout nil }\n").unwrap_err().to_string(),
+            "в TOML нет пустых значений, Nil записать нельзя (по пути «options.timeout»)"
+        );
+    }
+}
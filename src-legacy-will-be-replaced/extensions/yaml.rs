@@ -0,0 +1,146 @@
+use crate::extensions::output::{float_literal, OutputBackend, OutputError, OutputValue};
+use crate::interpretator::prototypes::types::object::object::ObjectRef;
+
+/// `[Marker:Output<...> as YAML]` — блочный YAML с отступом в два пробела.
+///
+/// Пустые значения записываются как `null`, бесконечности и `NaN` — как `.inf` и `.nan`.
+/// Строки берутся в кавычки, только если без них их прочитали бы иначе.
+pub struct YamlBackend;
+
+impl OutputBackend for YamlBackend {
+    fn name(&self) -> &'static str {
+        "YAML"
+    }
+
+    fn write(&self, root: &ObjectRef) -> Result<String, OutputError> {
+        let value = OutputValue::from_object(root, self.name())?;
+        if !is_block(&value) {
+            return Ok(scalar(&value));
+        }
+        Ok(block(&value, 0).join("\n"))
+    }
+}
+
+/// Непустые словари и массивы пишутся блоком, остальное — в одну строку.
+fn is_block(value: &OutputValue) -> bool {
+    match value {
+        OutputValue::Table(table) => !table.is_empty(),
+        OutputValue::Array(values) => !values.is_empty(),
+        _ => false,
+    }
+}
+
+fn block(value: &OutputValue, indent: usize) -> Vec<String> {
+    let pad = " ".repeat(indent);
+    let mut lines = Vec::new();
+    match value {
+        OutputValue::Table(table) => {
+            for (key, value) in table {
+                if is_block(value) {
+                    lines.push(format!("{}{}:", pad, string(key)));
+                    lines.extend(block(value, indent + 2));
+                } else {
+                    lines.push(format!("{}{}: {}", pad, string(key), scalar(value)));
+                }
+            }
+        }
+        OutputValue::Array(values) => {
+            for value in values {
+                if is_block(value) {
+                    // Первая строка вложенного блока встаёт на одну строку с `- `
+                    let mut nested = block(value, indent + 2);
+                    nested[0] = format!("{}- {}", pad, &nested[0][indent + 2..]);
+                    lines.extend(nested);
+                } else {
+                    lines.push(format!("{}- {}", pad, scalar(value)));
+                }
+            }
+        }
+        _ => lines.push(format!("{}{}", pad, scalar(value))),
+    }
+    lines
+}
+
+fn scalar(value: &OutputValue) -> String {
+    match value {
+        OutputValue::Empty { .. } => "null".to_string(),
+        OutputValue::Boolean(value) => value.to_string(),
+        OutputValue::Integer(value) => value.to_string(),
+        OutputValue::Float(value) if value.is_nan() => ".nan".to_string(),
+        OutputValue::Float(value) if value.is_infinite() => {
+            if *value > 0.0 { ".inf" } else { "-.inf" }.to_string()
+        }
+        OutputValue::Float(value) => float_literal(*value),
+        OutputValue::String(text) => string(text),
+        OutputValue::Array(_) => "[]".to_string(),
+        OutputValue::Table(_) => "{}".to_string(),
+    }
+}
+
+fn string(text: &str) -> String {
+    if is_plain(text) {
+        return text.to_string();
+    }
+    let mut quoted = String::with_capacity(text.len() + 2);
+    quoted.push('"');
+    for ch in text.chars() {
+        match ch {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\t' => quoted.push_str("\\t"),
+            '\r' => quoted.push_str("\\r"),
+            ch if ch.is_control() => quoted.push_str(&format!("\\u{:04X}", ch as u32)),
+            ch => quoted.push(ch),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Можно ли записать строку без кавычек, не изменив её смысла.
+fn is_plain(text: &str) -> bool {
+    const RESERVED: [&str; 11] = [
+        "null", "~", "true", "false", "yes", "no", "on", "off", "y", "n", "",
+    ];
+    if RESERVED.contains(&text.to_ascii_lowercase().as_str()) {
+        return false;
+    }
+    if text.starts_with(|ch: char| ch.is_whitespace() || "-?:,[]{}#&*!|>'\"%@`.".contains(ch))
+        || text.ends_with(char::is_whitespace)
+    {
+        return false;
+    }
+    if text.contains(": ") || text.contains(" #") || text.ends_with(':') {
+        return false;
+    }
+    if text.chars().any(|ch| ch.is_control()) {
+        return false;
+    }
+    // Строка «10» без кавычек прочиталась бы числом
+    let looks_numeric = text.parse::<f64>().is_ok()
+        || text.starts_with("0x")
+        || text.starts_with("0o")
+        || text.starts_with(|ch: char| ch.is_ascii_digit())
+            && text
+                .chars()
+                .all(|ch| ch.is_ascii_digit() || "_:.-+eE".contains(ch));
+    !looks_numeric
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interpretator::engine::engine::Engine;
+
+    #[test]
+    fn test_yaml_output() {
+        let source = "title \"Лампа: основная\"\nzeta 1\nalpha {\n\tlevels [1, 2.0, nil]\n\tcode \"10\"\n}\nitems [{ name a }, [x, y], []]\n";
+        let root = Engine::new().evaluate_document(source).unwrap();
+        let text = YamlBackend.write(&root).unwrap();
+        assert_eq!(
+            text,
+            "title: \"Лампа: основная\"\nzeta: 1\nalpha:\n  levels:\n    - 1\n    - 2.0\n    - null\n  code: \"10\"\nitems:\n  - name: a\n  - - x\n    - \"y\"\n  - []"
+        );
+    }
+}
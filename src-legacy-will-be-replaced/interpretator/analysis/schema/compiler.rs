@@ -0,0 +1,299 @@
+use crate::interpretator::analysis::schema::dimension::expected_dimension;
+use crate::interpretator::frontend::ast::expression::{Expr, ExprKind, StringSegment};
+use crate::interpretator::frontend::ast::node::TypeExpr;
+use crate::interpretator::frontend::ast::statement::{Entry, EntryKey, StmtKind};
+use crate::interpretator::frontend::diagnostics::error::{Diagnostic, Diagnostics};
+use crate::interpretator::frontend::diagnostics::reporter::Reporter;
+use crate::interpretator::frontend::diagnostics::span::Span;
+use crate::interpretator::frontend::parser::parser::parse_source;
+use crate::interpretator::prototypes::types::formal::formal::{kind_dimension, Dimension, Unit};
+
+/// Схема SLaCoN: поля, которые должны быть в документе, их типы и правила.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Schema {
+    pub fields: Vec<Field>,
+    /// Текст схемы: по нему в сообщениях показываются типы и правила
+    source: String,
+}
+
+/// Поле схемы: `fuel@[value in ["solid", "gas"]]: <String>`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Field {
+    pub key: String,
+    /// Место объявления поля в схеме
+    pub span: Span,
+    pub ty: FieldType,
+    /// `name?: <Type>` — поле можно не задавать
+    pub optional: bool,
+    /// Правила из `@[...]`; проверяемое значение в них называется `value`
+    pub rules: Vec<Expr>,
+    /// `@[unit: g/min]` — единица, в которую переводится значение поля
+    pub unit: Option<Unit>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum FieldType {
+    /// `<String>`, `<Power>`, `<String[]>`
+    Type(TypeExpr),
+    /// `name<Expr>: <Mass> / delta <Time>` — выражение над типами, задающее размерность
+    Expr(Expr),
+}
+
+impl Schema {
+    /// Разбирает и компилирует текст `.slacon`.
+    pub fn compile(source: &str) -> Result<Schema, Diagnostics> {
+        let mut reporter = Reporter::new();
+        let schema = compile_schema(source, &mut reporter);
+        if reporter.has_errors() {
+            return Err(reporter.into_diagnostics());
+        }
+        Ok(schema)
+    }
+
+    pub fn field(&self, key: &str) -> Option<&Field> {
+        self.fields.iter().find(|field| field.key == key)
+    }
+
+    /// Участок текста схемы: `<String>`, `value in ["solid", "gas"]`.
+    pub fn text(&self, span: Span) -> &str {
+        self.source
+            .get(span.start.offset..span.end.offset)
+            .unwrap_or_default()
+    }
+}
+
+/// Разбирает `.slacon` и собирает схему. SLaCoN ничего не исполняет: кроме заголовков
+/// и полей с типами в нём не бывает инструкций, а правила не вызывают функций.
+pub fn compile_schema(source: &str, reporter: &mut Reporter) -> Schema {
+    let program = parse_source(source, reporter);
+    let mut fields: Vec<Field> = Vec::new();
+    for statement in &program.statements {
+        let entry = match &statement.kind {
+            StmtKind::Header { .. } | StmtKind::Error => continue,
+            StmtKind::Entry(entry) => entry,
+            _ => {
+                reporter.report(executable("инструкции", statement.span));
+                continue;
+            }
+        };
+        let Some(field) = compile_field(source, entry, reporter) else {
+            continue;
+        };
+        if let Some(previous) = fields.iter().find(|previous| previous.key == field.key) {
+            reporter.report(
+                Diagnostic::error(format!("поле «{}» уже описано", field.key), field.span)
+                    .with_code("E0217")
+                    .with_label(previous.span, "первое описание"),
+            );
+            continue;
+        }
+        fields.push(field);
+    }
+    Schema {
+        fields,
+        source: source.to_string(),
+    }
+}
+
+impl Field {
+    /// Размерность значения поля: у `<Expr>` — описанная выражением, у `<Power>` — вида величины.
+    pub fn dimension(&self) -> Option<Dimension> {
+        match &self.ty {
+            FieldType::Type(ty) => kind_dimension(ty.kind.simple_name()?),
+            FieldType::Expr(expr) => expected_dimension(expr).ok(),
+        }
+    }
+}
+
+fn compile_field(source: &str, entry: &Entry, reporter: &mut Reporter) -> Option<Field> {
+    let key = match &entry.key {
+        EntryKey::Name(name) => name.clone(),
+        EntryKey::String(expr) => match &expr.kind {
+            ExprKind::String { segments, .. } => match segments.as_slice() {
+                [] => String::new(),
+                [StringSegment::Text(text)] => text.clone(),
+                _ => {
+                    reporter.report(executable("интерполяция", expr.span));
+                    return None;
+                }
+            },
+            _ => unreachable!("строковый ключ — строка"),
+        },
+        EntryKey::Multiple(_) => {
+            reporter.report(
+                Diagnostic::error("в схеме каждое поле описывается отдельно", entry.key_span)
+                    .with_code("E0217"),
+            );
+            return None;
+        }
+    };
+    let expression = entry
+        .ty
+        .as_ref()
+        .is_some_and(|ty| ty.kind.simple_name() == Some("Expr"));
+    let ty = match &entry.value.kind {
+        ExprKind::Type(ty) if !expression => FieldType::Type(ty.clone()),
+        _ if expression && is_type_expression(&entry.value) => FieldType::Expr(entry.value.clone()),
+        _ => {
+            let help = match expression {
+                true => "«<Expr>» ждёт выражение над типами: «<Mass> / delta <Time>»",
+                false => "значение поля — тип: «<String>», «<Power>»",
+            };
+            reporter.report(
+                Diagnostic::error("схема описывает типы, а не значения", entry.value.span)
+                    .with_code("E0217")
+                    .with_help(help),
+            );
+            return None;
+        }
+    };
+    if let FieldType::Expr(expr) = &ty {
+        if let Err(error) = expected_dimension(expr) {
+            reporter.report(Diagnostic::error(error.message, error.span).with_code("E0217"));
+            return None;
+        }
+    }
+    let mut field = Field {
+        key,
+        span: entry.key_span,
+        ty,
+        optional: entry.optional,
+        rules: Vec::new(),
+        unit: None,
+    };
+    for attribute in &entry.attributes {
+        match attribute.name.as_deref() {
+            Some("unit") => {
+                let text = source
+                    .get(attribute.value.span.start.offset..attribute.value.span.end.offset)
+                    .unwrap_or_default();
+                match compile_unit(&field, text) {
+                    Ok(unit) => field.unit = Some(unit),
+                    Err(message) => reporter.report(
+                        Diagnostic::error(message, attribute.value.span).with_code("E0217"),
+                    ),
+                }
+            }
+            Some(name) => reporter.report(
+                Diagnostic::error(
+                    format!("неизвестный параметр поля «{}»", name),
+                    attribute.span,
+                )
+                .with_code("E0217")
+                .with_help("у поля бывает только параметр «unit»"),
+            ),
+            None => match find_executable(&attribute.value) {
+                Some(span) => reporter.report(executable("вызовы и функции", span)),
+                None => field.rules.push(attribute.value.clone()),
+            },
+        }
+    }
+    Some(field)
+}
+
+/// Единица `@[unit: ...]`: её размерность должна совпадать с размерностью поля.
+fn compile_unit(field: &Field, text: &str) -> Result<Unit, String> {
+    let unit = Unit::parse(text).map_err(|error| error.to_string())?;
+    match field.dimension() {
+        Some(dimension) if dimension == unit.dimension() => Ok(unit),
+        Some(dimension) => Err(format!(
+            "единица «{}» не подходит полю размерности {}",
+            unit,
+            dimension.describe()
+        )),
+        None => Err(format!(
+            "поле «{}» не величина, единица ему не задаётся",
+            field.key
+        )),
+    }
+}
+
+/// Ошибка SLaCoN: то, что пришлось бы исполнять.
+fn executable(what: &str, span: Span) -> Diagnostic {
+    Diagnostic::error(format!("{} недоступны в схеме SLaCoN", what), span)
+        .with_code("E0217")
+        .with_help("схема содержит только поля с типами и правила «@[...]»")
+}
+
+/// Выражение из типов, чисел и операторов: `<Mass> / delta <Time>`, `<LuminousFlux> / <Time ** 3>`.
+fn is_type_expression(expr: &Expr) -> bool {
+    match &expr.kind {
+        ExprKind::Type(_) | ExprKind::Number { .. } => true,
+        ExprKind::Unary { operand, .. } => is_type_expression(operand),
+        ExprKind::Binary { left, right, .. } => {
+            is_type_expression(left) && is_type_expression(right)
+        }
+        _ => false,
+    }
+}
+
+/// Первая часть правила, которую пришлось бы исполнять: вызов, функция или создание объекта.
+fn find_executable(expr: &Expr) -> Option<Span> {
+    match &expr.kind {
+        ExprKind::Call { .. }
+        | ExprKind::Lambda { .. }
+        | ExprKind::Routine(_)
+        | ExprKind::New { .. }
+        | ExprKind::Dictionary(_)
+        | ExprKind::Expansion(_) => Some(expr.span),
+        ExprKind::String { segments, .. } => segments.iter().find_map(|segment| match segment {
+            StringSegment::Interpolation(expr) | StringSegment::CodePoint(expr) => {
+                find_executable(expr)
+            }
+            StringSegment::Text(_) => None,
+        }),
+        ExprKind::Array(items) | ExprKind::Tuple(items) => items.iter().find_map(find_executable),
+        ExprKind::Unary { operand: inner, .. }
+        | ExprKind::Spread(inner)
+        | ExprKind::Floor(inner)
+        | ExprKind::Ceil(inner)
+        | ExprKind::Member { object: inner, .. } => find_executable(inner),
+        ExprKind::Binary { left, right, .. }
+        | ExprKind::Index {
+            object: left,
+            index: right,
+            ..
+        } => find_executable(left).or_else(|| find_executable(right)),
+        ExprKind::Ternary {
+            condition,
+            then_branch,
+            else_branch,
+        } => find_executable(condition)
+            .or_else(|| find_executable(then_branch))
+            .or_else(|| find_executable(else_branch)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compile_schema() {
+        let schema = Schema::compile(
+            "entity_type: <String>\nfuel@[value in [\"solid\", \"gas\"]]: <String>\nflow<Expr>: <Mass> / delta <Time>\n",
+        )
+        .unwrap();
+        let keys: Vec<_> = schema
+            .fields
+            .iter()
+            .map(|field| field.key.as_str())
+            .collect();
+        assert_eq!(keys, ["entity_type", "fuel", "flow"]);
+        assert_eq!(
+            schema.text(schema.fields[1].rules[0].span),
+            "value in [\"solid\", \"gas\"]"
+        );
+        assert!(matches!(schema.fields[2].ty, FieldType::Expr(_)));
+
+        let errors =
+            Schema::compile("const limit 5\nname \"text\"\nfuel@[check(value)]: <String>\n")
+                .unwrap_err();
+        let codes: Vec<_> = errors
+            .iter()
+            .filter_map(|error| error.code.as_deref())
+            .collect();
+        assert_eq!(codes, ["E0217", "E0217", "E0217"]);
+    }
+}
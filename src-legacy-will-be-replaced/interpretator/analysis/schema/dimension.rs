@@ -0,0 +1,207 @@
+use crate::interpretator::frontend::ast::expression::{BinaryOp, Expr, ExprKind, UnaryOp};
+use crate::interpretator::frontend::ast::node::{TypeExpr, TypeExprKind};
+use crate::interpretator::frontend::diagnostics::span::Span;
+use crate::interpretator::prototypes::types::formal::formal::{kind_dimension, Dimension};
+use crate::interpretator::prototypes::types::object::object::{Object, ObjectRef};
+use crate::interpretator::runtime::execution::actions::evaluation::Evaluator;
+use crate::interpretator::stdlib::core::primitives::{
+    as_duration, as_number, as_quantity, type_name,
+};
+
+/// Выражение над типами не задаёт размерность.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DimensionError {
+    pub message: String,
+    pub span: Span,
+}
+
+/// Значение поля `<Expr>` не подошло под выражение схемы.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Mismatch {
+    pub message: String,
+    /// Место в документе: операнд, если выражения совпали по форме, иначе всё значение
+    pub span: Span,
+}
+
+/// Размерность, которую описывает выражение схемы: `<Mass> / delta <Time>` — `kg/s`,
+/// `<LuminousFlux> / <Time ** 3>` — `cd⋅rad2/s3`. Числа безразмерны, `delta` размерности
+/// не меняет.
+pub fn expected_dimension(expr: &Expr) -> Result<Dimension, DimensionError> {
+    let unsupported = || DimensionError {
+        message: "в выражении размерности допустимы типы, числа, «*», «/», «**» и «delta»"
+            .to_string(),
+        span: expr.span,
+    };
+    match &expr.kind {
+        ExprKind::Type(ty) => type_dimension(ty),
+        ExprKind::Number { .. } => Ok(Dimension::NONE),
+        ExprKind::Unary {
+            op: UnaryOp::Delta | UnaryOp::Plus | UnaryOp::Negate,
+            operand,
+        } => expected_dimension(operand),
+        ExprKind::Binary { op, left, right } => {
+            let left_dimension = expected_dimension(left)?;
+            match op {
+                BinaryOp::Mul => Ok(left_dimension.mul(expected_dimension(right)?)),
+                BinaryOp::Div => Ok(left_dimension.div(expected_dimension(right)?)),
+                BinaryOp::Pow => Ok(left_dimension.powi(exponent(right).ok_or_else(unsupported)?)),
+                _ => Err(unsupported()),
+            }
+        }
+        _ => Err(unsupported()),
+    }
+}
+
+fn type_dimension(ty: &TypeExpr) -> Result<Dimension, DimensionError> {
+    let error = |message: String| DimensionError {
+        message,
+        span: ty.span,
+    };
+    match &ty.kind {
+        TypeExprKind::Named { .. } => {
+            let name = ty.kind.simple_name().unwrap_or_default();
+            kind_dimension(name).ok_or_else(|| error(format!("«{}» — не вид величины", name)))
+        }
+        TypeExprKind::Power(inner, power) => {
+            let power = i8::try_from(*power)
+                .map_err(|_| error(format!("степень {} слишком велика", power)))?;
+            Ok(type_dimension(inner)?.powi(power))
+        }
+        _ => Err(error(
+            "ожидался вид величины: «<Mass>», «<Time ** 2>»".to_string(),
+        )),
+    }
+}
+
+/// Целая степень `** 3`.
+fn exponent(expr: &Expr) -> Option<i8> {
+    match &expr.kind {
+        ExprKind::Number {
+            literal,
+            unit: None,
+        } => literal.parse().ok(),
+        _ => None,
+    }
+}
+
+/// Размерность значения: величины, длительности и простого числа.
+pub fn value_dimension(value: &Object) -> Option<Dimension> {
+    if let Some(quantity) = as_quantity(value) {
+        return Some(quantity.dimension());
+    }
+    if as_duration(value).is_some() {
+        return Some(Dimension::TIME);
+    }
+    as_number(value).map(|_| Dimension::NONE)
+}
+
+/// Проверяет значение документа по выражению схемы. Значение не меняется: привести его
+/// к нужной единице может только `@[unit: ...]` поля.
+///
+/// `value` — уже вычисленное значение документа — проверяется по размерности целиком.
+/// Если оно не подошло, а выражение документа повторяет форму схемы, ошибка указывает
+/// на первый неподходящий операнд: для `<LuminousFlux> / <Time ** 3>` запись `40lm / 3sec`
+/// отклоняется на `3sec` — степень времени документ должен указать сам, `3sec ** 3`.
+pub fn match_expression(
+    evaluator: &mut Evaluator,
+    schema: &Expr,
+    document: Option<&Expr>,
+    value: &ObjectRef,
+    span: Span,
+) -> Result<(), Mismatch> {
+    let expected = expected_dimension(schema).map_err(|error| Mismatch {
+        message: error.message,
+        span,
+    })?;
+    let whole = check_dimension(&value.borrow(), expected, span);
+    if whole.is_ok() {
+        return whole;
+    }
+    if let Some(document) = document.filter(|document| same_shape(schema, document)) {
+        check_operands(evaluator, schema, document)?;
+    }
+    whole
+}
+
+/// Формы выражений совпадают: на месте каждого бинарного оператора схемы в документе
+/// тот же оператор. Типы схемы сопоставляются с любыми выражениями.
+fn same_shape(schema: &Expr, document: &Expr) -> bool {
+    match (&schema.kind, &document.kind) {
+        (ExprKind::Unary { operand, .. }, _) => same_shape(operand, document),
+        (
+            ExprKind::Binary { op, left, right },
+            ExprKind::Binary {
+                op: other,
+                left: l,
+                right: r,
+            },
+        ) => op == other && same_shape(left, l) && same_shape(right, r),
+        (ExprKind::Binary { .. }, _) => false,
+        _ => true,
+    }
+}
+
+/// Сверяет каждый операнд документа с типом на том же месте схемы, степень типа
+/// включительно. Показатель `** n` в схеме задаёт размерность, а не операнд.
+fn check_operands(
+    evaluator: &mut Evaluator,
+    schema: &Expr,
+    document: &Expr,
+) -> Result<(), Mismatch> {
+    match &schema.kind {
+        ExprKind::Unary { operand, .. } => check_operands(evaluator, operand, document),
+        ExprKind::Binary { op, left, right } => {
+            let ExprKind::Binary {
+                left: document_left,
+                right: document_right,
+                ..
+            } = &document.kind
+            else {
+                unreachable!("формы выражений сверены заранее")
+            };
+            check_operands(evaluator, left, document_left)?;
+            match op {
+                BinaryOp::Pow => Ok(()),
+                _ => check_operands(evaluator, right, document_right),
+            }
+        }
+        ExprKind::Type(ty) => {
+            let value = evaluator.evaluate(document).map_err(|error| Mismatch {
+                message: error.to_string(),
+                span: document.span,
+            })?;
+            let expected = type_dimension(ty).map_err(|error| Mismatch {
+                message: error.message,
+                span: document.span,
+            })?;
+            let value = value.borrow();
+            check_dimension(&value, expected, document.span)
+        }
+        _ => Ok(()),
+    }
+}
+
+fn check_dimension(value: &Object, expected: Dimension, span: Span) -> Result<(), Mismatch> {
+    let found = match value_dimension(value) {
+        Some(found) if found == expected => return Ok(()),
+        Some(found) => describe(found),
+        None => type_name(value),
+    };
+    Err(Mismatch {
+        message: format!(
+            "«{}» должно иметь размерность {}, а не {}",
+            value,
+            describe(expected),
+            found
+        ),
+        span,
+    })
+}
+
+/// Имя размерности для сообщений; безразмерное значение — просто число.
+fn describe(dimension: Dimension) -> String {
+    match dimension.is_dimensionless() {
+        true => "Number".to_string(),
+        false => dimension.describe(),
+    }
+}
@@ -0,0 +1,327 @@
+use crate::interpretator::analysis::schema::compiler::{Field, FieldType, Schema};
+use crate::interpretator::frontend::ast::expression::{
+    BinaryOp, Expr, ExprKind, StringSegment, UnaryOp,
+};
+use crate::interpretator::frontend::ast::node::{TypeExpr, TypeExprKind};
+use crate::interpretator::prototypes::types::formal::formal::{kind_dimension, Dimension, Unit};
+use serde_json::{json, Map, Value};
+
+/// Адрес черновика JSON Schema, по которому пишется схема.
+pub const DRAFT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// Число в записи величины при выводе документа: `15`, `-1.5`.
+const NUMBER_PATTERN: &str = "-?[0-9]+(\\.[0-9]+)?";
+
+/// Переводит схему SLaCoN в JSON Schema 2020-12 для документов, выведенных через
+/// `[Marker:Output<Dictionary> as JSON]`.
+///
+/// Величины выводятся в JSON строкой вроде `"15kW"`, поэтому и в схеме становятся строками
+/// с шаблоном; у поля с `@[unit: ...]` шаблон требует именно эту единицу. Правила, у которых
+/// нет аналога в JSON Schema, попадают в `$comment` и проверяются только в LaCoN.
+pub fn json_schema(schema: &Schema) -> Value {
+    let mut properties = Map::new();
+    let mut required = Vec::new();
+    for field in &schema.fields {
+        properties.insert(
+            field.key.clone(),
+            Value::Object(field_schema(schema, field)),
+        );
+        if !field.optional {
+            required.push(Value::String(field.key.clone()));
+        }
+    }
+    json!({
+        "$schema": DRAFT,
+        "type": "object",
+        "properties": properties,
+        "required": required,
+        "additionalProperties": false,
+    })
+}
+
+fn field_schema(schema: &Schema, field: &Field) -> Map<String, Value> {
+    let mut output = match (&field.ty, &field.unit) {
+        (FieldType::Type(ty), None) => type_schema(ty),
+        (ty, unit) => {
+            let kind = match ty {
+                FieldType::Type(ty) => schema.text(ty.span),
+                FieldType::Expr(expr) => schema.text(expr.span),
+            };
+            field.dimension().map_or_else(Map::new, |dimension| {
+                quantity_schema(kind, dimension, unit.as_ref())
+            })
+        }
+    };
+    let mut skipped = Vec::new();
+    for rule in field.rules.iter().flat_map(conjuncts) {
+        if !apply_rule(&mut output, rule) {
+            skipped.push(format!("«{}»", schema.text(rule.span)));
+        }
+    }
+    if !skipped.is_empty() {
+        let comment = format!("проверяется только в LaCoN: {}", skipped.join(", "));
+        output.insert("$comment".to_string(), Value::String(comment));
+    }
+    output
+}
+
+/// Схема значения типа: `<String>`, `<Int[]>`, `<Array<Power>>`, `<String{}>`.
+fn type_schema(ty: &TypeExpr) -> Map<String, Value> {
+    match &ty.kind {
+        TypeExprKind::Named { arguments, .. } => {
+            let name = ty.kind.simple_name().unwrap_or_default();
+            named_schema(name, arguments.first())
+        }
+        TypeExprKind::Array(item) => object(json!({ "type": "array", "items": type_schema(item) })),
+        TypeExprKind::Dictionary(item) => object(json!({
+            "type": "object",
+            "additionalProperties": type_schema(item),
+        })),
+        TypeExprKind::Tuple(items) => {
+            let items: Vec<_> = items.iter().map(|(_, item)| type_schema(item)).collect();
+            object(json!({
+                "type": "array",
+                "prefixItems": items,
+                "minItems": items.len(),
+                "items": false,
+            }))
+        }
+        TypeExprKind::Power(..) => match type_dimension(ty) {
+            Some(dimension) => quantity_schema(&dimension.describe(), dimension, None),
+            None => Map::new(),
+        },
+    }
+}
+
+fn named_schema(name: &str, argument: Option<&TypeExpr>) -> Map<String, Value> {
+    let item = || argument.map_or_else(Map::new, type_schema);
+    let value = match name.to_ascii_lowercase().as_str() {
+        "string" | "multilinestring" => json!({ "type": "string" }),
+        "int" | "integer" | "binary" | "octal" | "hex" | "hexadecimal" | "base32" => {
+            json!({ "type": "integer" })
+        }
+        // Целые длиннее `i64` выводятся строкой
+        "biginteger" => json!({ "type": ["integer", "string"], "pattern": "^-?[0-9]+$" }),
+        "number" | "float" | "fraction" => json!({ "type": "number" }),
+        "bool" | "boolean" => json!({ "type": "boolean" }),
+        "nil" | "none" => json!({ "type": "null" }),
+        "array" => json!({ "type": "array", "items": item() }),
+        "dictionary" => json!({ "type": "object", "additionalProperties": item() }),
+        "duration" => json!({ "type": "string", "format": "duration" }),
+        "datetime" => json!({
+            "type": "string",
+            "description": "дата или дата и время ISO 8601",
+        }),
+        "color" => json!({
+            "type": "string",
+            "description": "цвет CSS: «#fa8246», «rgb(...)» или «hsl(...)»",
+        }),
+        _ => match kind_dimension(name) {
+            Some(dimension) => return quantity_schema(name, dimension, None),
+            None => json!({ "$comment": format!("тип «{}» не проверяется", name) }),
+        },
+    };
+    object(value)
+}
+
+fn type_dimension(ty: &TypeExpr) -> Option<Dimension> {
+    match &ty.kind {
+        TypeExprKind::Named { .. } => kind_dimension(ty.kind.simple_name()?),
+        TypeExprKind::Power(inner, power) => {
+            Some(type_dimension(inner)?.powi(i8::try_from(*power).ok()?))
+        }
+        _ => None,
+    }
+}
+
+/// Величина: безразмерная — число, остальные — строка с числом и единицей.
+/// `kind` — как величина названа в схеме: `Power`, `<Mass> / delta <Time>`.
+fn quantity_schema(kind: &str, dimension: Dimension, unit: Option<&Unit>) -> Map<String, Value> {
+    if dimension.is_dimensionless() && unit.is_none() {
+        return object(json!({ "type": "number" }));
+    }
+    let (pattern, description) = match unit {
+        Some(unit) => (
+            format!("^{}{}$", NUMBER_PATTERN, escape(&unit.to_string())),
+            format!("{} в {}: число с единицей, «1.5{}»", kind, unit, unit),
+        ),
+        None => (
+            format!("^{}[^\\s0-9.-]\\S*$", NUMBER_PATTERN),
+            format!("{}: число с единицей измерения", kind),
+        ),
+    };
+    object(json!({ "type": "string", "pattern": pattern, "description": description }))
+}
+
+fn object(value: Value) -> Map<String, Value> {
+    match value {
+        Value::Object(map) => map,
+        _ => unreachable!("схема значения — объект"),
+    }
+}
+
+/// Экранирует единицу для регулярного выражения ECMA-262: `m/s2` — `m\/s2`.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if "^$\\.*+?()[]{}|/".contains(c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Части правила, соединённые `&&`: каждую можно перевести отдельно.
+fn conjuncts(rule: &Expr) -> Vec<&Expr> {
+    match &rule.kind {
+        ExprKind::Binary {
+            op: BinaryOp::And,
+            left,
+            right,
+        } => {
+            let mut parts = conjuncts(left);
+            parts.extend(conjuncts(right));
+            parts
+        }
+        _ => vec![rule],
+    }
+}
+
+/// Переводит правило в ключевые слова JSON Schema: `value in [...]` — `enum`,
+/// `value > 0` — `exclusiveMinimum`, `value <= 10` — `maximum`.
+/// Возвращает `false`, если у правила нет аналога.
+fn apply_rule(output: &mut Map<String, Value>, rule: &Expr) -> bool {
+    let ExprKind::Binary { op, left, right } = &rule.kind else {
+        return false;
+    };
+    let numeric = matches!(
+        output.get("type").and_then(Value::as_str),
+        Some("integer" | "number")
+    );
+    // `0 < value` читается как `value > 0`
+    let (op, bound) = match (is_value(left), is_value(right)) {
+        (true, false) => (*op, right.as_ref()),
+        (false, true) => match op {
+            BinaryOp::Less => (BinaryOp::Greater, left.as_ref()),
+            BinaryOp::LessEq => (BinaryOp::GreaterEq, left.as_ref()),
+            BinaryOp::Greater => (BinaryOp::Less, left.as_ref()),
+            BinaryOp::GreaterEq => (BinaryOp::LessEq, left.as_ref()),
+            _ => return false,
+        },
+        _ => return false,
+    };
+    let keyword = match op {
+        BinaryOp::In => {
+            let ExprKind::Array(items) = &bound.kind else {
+                return false;
+            };
+            let Some(values) = items.iter().map(literal).collect::<Option<Vec<_>>>() else {
+                return false;
+            };
+            output.insert("enum".to_string(), Value::Array(values));
+            return true;
+        }
+        BinaryOp::Equal | BinaryOp::StrictEqual => "const",
+        BinaryOp::Greater if numeric => "exclusiveMinimum",
+        BinaryOp::GreaterEq if numeric => "minimum",
+        BinaryOp::Less if numeric => "exclusiveMaximum",
+        BinaryOp::LessEq if numeric => "maximum",
+        _ => return false,
+    };
+    match literal(bound) {
+        Some(value) if keyword == "const" || value.is_number() => {
+            output.insert(keyword.to_string(), value);
+            true
+        }
+        _ => false,
+    }
+}
+
+fn is_value(expr: &Expr) -> bool {
+    matches!(&expr.kind, ExprKind::Identifier(name) if name == "value")
+}
+
+/// Литерал правила в виде значения JSON; величины и выражения не переводятся.
+fn literal(expr: &Expr) -> Option<Value> {
+    match &expr.kind {
+        ExprKind::Number {
+            literal,
+            unit: None,
+        } => number(literal),
+        ExprKind::Unary {
+            op: UnaryOp::Negate,
+            operand,
+        } => match literal(operand)? {
+            Value::Number(number) => match number.as_i64() {
+                Some(value) => Some(json!(-value)),
+                None => Some(json!(-number.as_f64()?)),
+            },
+            _ => None,
+        },
+        ExprKind::String { segments, .. } => match segments.as_slice() {
+            [] => Some(json!("")),
+            [StringSegment::Text(text)] => Some(json!(text)),
+            _ => None,
+        },
+        ExprKind::Boolean(value) => Some(json!(value)),
+        ExprKind::Nil | ExprKind::None => Some(Value::Null),
+        _ => None,
+    }
+}
+
+/// Числовой литерал: `10`, `20_000`, `0x1E`, `10.5`, `1e5`.
+fn number(literal: &str) -> Option<Value> {
+    let digits = literal.replace('_', "");
+    let radix = match digits.get(..2) {
+        Some("0b") => 2,
+        Some("0o") => 8,
+        Some("0x") => 16,
+        _ if digits.contains(['.', 'e', 'E']) => {
+            return digits.parse::<f64>().ok().map(|value| json!(value))
+        }
+        _ => return digits.parse::<i64>().ok().map(|value| json!(value)),
+    };
+    i64::from_str_radix(&digits[2..], radix)
+        .ok()
+        .map(|value| json!(value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_json_schema() {
+        let schema = Schema::compile(
+            "name: <String>\ncount@[value > 0 && value <= 0x10]: <Int>\nfuel@[value in [\"solid\", \"gas\"]]: <String>\ntags?: <Array<String>>\nlimits: <Float{}>\npower@[unit: kW]: <Power>\nflow<Expr>: <Mass> / delta <Time>\n",
+        )
+        .unwrap();
+        let output = json_schema(&schema);
+        assert_eq!(output["$schema"], DRAFT);
+        assert_eq!(
+            output["required"],
+            json!(["name", "count", "fuel", "limits", "power", "flow"])
+        );
+        let properties = &output["properties"];
+        assert_eq!(properties["name"], json!({ "type": "string" }));
+        assert_eq!(
+            properties["count"],
+            json!({ "type": "integer", "exclusiveMinimum": 0, "maximum": 16 })
+        );
+        assert_eq!(
+            properties["fuel"],
+            json!({ "type": "string", "enum": ["solid", "gas"] })
+        );
+        assert_eq!(
+            properties["tags"],
+            json!({ "type": "array", "items": { "type": "string" } })
+        );
+        assert_eq!(
+            properties["limits"],
+            json!({ "type": "object", "additionalProperties": { "type": "number" } })
+        );
+        assert_eq!(properties["power"]["pattern"], "^-?[0-9]+(\\.[0-9]+)?kW$");
+        assert_eq!(properties["flow"]["type"], "string");
+    }
+}
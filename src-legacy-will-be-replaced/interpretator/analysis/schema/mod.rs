@@ -0,0 +1,4 @@
+pub mod compiler;
+pub mod dimension;
+pub mod export;
+pub mod validator;
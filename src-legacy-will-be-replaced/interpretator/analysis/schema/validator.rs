@@ -0,0 +1,137 @@
+use crate::interpretator::analysis::schema::compiler::{Field, FieldType, Schema};
+use crate::interpretator::analysis::schema::dimension::match_expression;
+use crate::interpretator::frontend::ast::expression::Expr;
+use crate::interpretator::frontend::diagnostics::error::Diagnostic;
+use crate::interpretator::frontend::diagnostics::span::Span;
+use crate::interpretator::prototypes::types::object::object::ObjectRef;
+use crate::interpretator::runtime::execution::actions::evaluation::Evaluator;
+use crate::interpretator::stdlib::core::primitives::type_name;
+use crate::interpretator::stdlib::core::quantities;
+use indexmap::IndexMap;
+use std::collections::HashMap;
+
+/// Схема, подключённая документом через `use schema "..."`.
+#[derive(Debug, Clone)]
+pub struct SchemaUse {
+    pub schema: Schema,
+    /// Путь как он записан в документе
+    pub path: String,
+    /// Файл схемы, каким его открыл загрузчик: им подписаны метки в схеме
+    pub file: String,
+    /// Место `use schema` в документе
+    pub span: Span,
+}
+
+/// Элемент, записанный в документе явно: место ключа и выражение значения.
+#[derive(Debug, Clone, Copy)]
+pub struct Written<'e> {
+    pub span: Span,
+    pub value: &'e Expr,
+}
+
+/// Проверяет элементы документа по схеме и возвращает все нарушения.
+///
+/// Ошибка указывает на элемент документа (или на `use schema`, если элемента нет),
+/// а вспомогательная метка в файле схемы — на описание поля. Значения полей `<Expr>` заменяются вычисленными
+/// по схеме, а поля с `@[unit: ...]` переводятся в эту единицу.
+pub fn validate(
+    evaluator: &mut Evaluator,
+    usage: &SchemaUse,
+    entries: &mut IndexMap<String, ObjectRef>,
+    written: &HashMap<String, Written>,
+) -> Vec<Diagnostic> {
+    let schema = &usage.schema;
+    let span_of = |key: &str| written.get(key).map_or(usage.span, |entry| entry.span);
+    let violation = |message: String, span: Span, field: &Field| {
+        Diagnostic::error(message, span)
+            .with_code("E0218")
+            .with_label_in(&usage.file, field.span, "поле описано здесь")
+    };
+
+    let mut violations = Vec::new();
+    for field in &schema.fields {
+        let Some(value) = entries.get(&field.key).cloned() else {
+            if !field.optional {
+                violations.push(violation(
+                    format!("в документе нет поля «{}» из схемы", field.key),
+                    usage.span,
+                    field,
+                ));
+            }
+            continue;
+        };
+        let span = span_of(&field.key);
+        let value = match &field.ty {
+            FieldType::Type(ty) if !evaluator.matches_type(&value, ty) => {
+                violations.push(violation(
+                    format!(
+                        "поле «{}» должно быть типа {}, а не {}",
+                        field.key,
+                        schema.text(ty.span),
+                        type_name(&value.borrow())
+                    ),
+                    span,
+                    field,
+                ));
+                continue;
+            }
+            FieldType::Type(_) => value,
+            FieldType::Expr(expr) => {
+                let document = written.get(&field.key).map(|entry| entry.value);
+                match match_expression(evaluator, expr, document, &value, span) {
+                    Ok(()) => value,
+                    Err(mismatch) => {
+                        let message = format!("поле «{}»: {}", field.key, mismatch.message);
+                        violations.push(violation(message, mismatch.span, field));
+                        continue;
+                    }
+                }
+            }
+        };
+        let value = match &field.unit {
+            Some(unit) => match quantities::convert(evaluator.prelude(), &value, unit) {
+                Ok(value) => value,
+                Err(error) => {
+                    let message = format!("поле «{}»: {}", field.key, error);
+                    violations.push(violation(message, span, field));
+                    continue;
+                }
+            },
+            None => value,
+        };
+        entries.insert(field.key.clone(), value.clone());
+        for rule in &field.rules {
+            let message = match evaluator.rule_holds(rule, &value) {
+                Ok(true) => continue,
+                Ok(false) => format!(
+                    "значение «{}» поля «{}» нарушает правило «{}»",
+                    value.borrow(),
+                    field.key,
+                    schema.text(rule.span)
+                ),
+                Err(error) => format!(
+                    "правило «{}» поля «{}» не вычисляется: {}",
+                    schema.text(rule.span),
+                    field.key,
+                    error
+                ),
+            };
+            violations.push(violation(message, span, field));
+        }
+    }
+    for key in entries.keys() {
+        if schema.field(key).is_none() {
+            let diagnostic = Diagnostic::error(format!("поля «{}» нет в схеме", key), span_of(key))
+                .with_code("E0218");
+            violations.push(match (schema.fields.first(), schema.fields.last()) {
+                (Some(first), Some(last)) => diagnostic.with_label_in(
+                    &usage.file,
+                    first.span.to(last.span),
+                    format!("поля схемы «{}»", usage.path),
+                ),
+                _ => diagnostic.with_note(format!("схема «{}» не описывает полей", usage.path)),
+            });
+        }
+    }
+    violations
+}
@@ -0,0 +1,328 @@
+use crate::interpretator::frontend::ast::expression::{Expr, ExprKind, StringSegment};
+use crate::interpretator::frontend::ast::node::Program;
+use crate::interpretator::frontend::ast::statement::{DeclKind, Stmt, StmtKind};
+use crate::interpretator::frontend::diagnostics::error::Diagnostic;
+use crate::interpretator::frontend::diagnostics::reporter::Reporter;
+use crate::interpretator::frontend::diagnostics::span::Span;
+use crate::interpretator::stdlib::core::builtins;
+use std::path::Path;
+
+/// Набор конструкций, доступных документу.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Dialect {
+    /// Полный LaCoN
+    #[default]
+    Full,
+    /// List LaCoN — файлы `.llacon` и документы с `[Marker:Extension<...>]`: данные,
+    /// константы, секции и управляющие конструкции без функций, классов и побочных эффектов
+    List,
+}
+
+impl Dialect {
+    /// Диалект по расширению файла: `.llacon` — List LaCoN.
+    pub fn from_path(path: &Path) -> Self {
+        let list = path
+            .extension()
+            .is_some_and(|extension| extension.eq_ignore_ascii_case("llacon"));
+        match list {
+            true => Dialect::List,
+            false => Dialect::Full,
+        }
+    }
+
+    /// Диалект, объявленный в самом документе маркером `[Marker:Extension<...>]`.
+    pub fn declared(program: &Program) -> Self {
+        let marked = program.statements.iter().any(|statement| {
+            matches!(&statement.kind, StmtKind::Header { name, body }
+                if name == "Marker" && body.trim().starts_with("Extension"))
+        });
+        match marked {
+            true => Dialect::List,
+            false => Dialect::Full,
+        }
+    }
+}
+
+/// Проверяет документ List LaCoN до выполнения и сообщает о каждой запрещённой конструкции.
+///
+/// Разрешены `const`, `let` внутри других конструкций, `if`/`elif`/`else`, циклы `for`, `loop`,
+/// `while` с `break` и `cancel` (он заканчивает текущий блок), `include`, секции,
+/// указатели типов, встроенные функции вроде `RGBA` и `format` и методы встроенных типов.
+/// Константы и секции объявляются до первого элемента данных и до `[Marker:Output]`.
+pub fn check_list(program: &Program, reporter: &mut Reporter) {
+    let mut data_start: Option<Span> = None;
+    for statement in &program.statements {
+        let declaration = match &statement.kind {
+            StmtKind::VarDecl(decl) if decl.kind == DeclKind::Const => true,
+            StmtKind::Section { .. } => true,
+            StmtKind::Header { name, body } => {
+                if name == "Marker" && body.trim().starts_with("Output") {
+                    data_start.get_or_insert(statement.span);
+                }
+                continue;
+            }
+            StmtKind::VarDecl(_)
+            | StmtKind::UseSchema { .. }
+            | StmtKind::Provide { .. }
+            | StmtKind::Error => false,
+            _ => {
+                data_start.get_or_insert(statement.span);
+                false
+            }
+        };
+        if let (true, Some(start)) = (declaration, data_start) {
+            reporter.report(
+                Diagnostic::error(
+                    "в List LaCoN константы и секции объявляются до первого элемента данных",
+                    statement.span,
+                )
+                .with_code("E0215")
+                .with_label(start, "данные начинаются здесь"),
+            );
+        }
+        check_statement(statement, false, reporter);
+    }
+}
+
+fn check_statement(statement: &Stmt, nested: bool, reporter: &mut Reporter) {
+    let forbidden = |what: &str, reporter: &mut Reporter| {
+        reporter.report(
+            Diagnostic::error(format!("{} недоступны в List LaCoN", what), statement.span)
+                .with_code("E0214")
+                .with_help("List LaCoN хранит данные; логику вынесите в файл «.lacon»"),
+        )
+    };
+    match &statement.kind {
+        StmtKind::Header { .. } | StmtKind::Error => {}
+        StmtKind::VarDecl(decl) => {
+            if decl.kind == DeclKind::Let && !nested {
+                reporter.report(
+                    Diagnostic::error(
+                        "в List LaCoN «let» объявляется только внутри других конструкций",
+                        statement.span,
+                    )
+                    .with_code("E0214")
+                    .with_help("для значения верхнего уровня используйте «const»"),
+                );
+            }
+            check_optional(decl.value.as_ref(), reporter);
+        }
+        StmtKind::Entry(entry) => check_expr(&entry.value, reporter),
+        StmtKind::Assign { target, value, .. } => {
+            if !nested {
+                forbidden("присваивания верхнего уровня", reporter);
+            }
+            check_expr(target, reporter);
+            check_optional(value.as_ref(), reporter);
+        }
+        StmtKind::Routine(_) => forbidden("функции и процедуры", reporter),
+        StmtKind::Class(_) => forbidden("классы и структуры", reporter),
+        StmtKind::Section { body, .. } | StmtKind::Block(body) => check_block(&body.kind, reporter),
+        StmtKind::Include { .. } | StmtKind::UseSchema { .. } | StmtKind::Provide { .. } => {}
+        StmtKind::If {
+            branches,
+            otherwise,
+        } => {
+            for (condition, block) in branches {
+                check_expr(condition, reporter);
+                check_block(&block.kind, reporter);
+            }
+            if let Some(block) = otherwise {
+                check_block(&block.kind, reporter);
+            }
+        }
+        StmtKind::For { iterable, body, .. } => {
+            check_expr(iterable, reporter);
+            check_block(&body.kind, reporter);
+        }
+        StmtKind::Loop { count, body } => {
+            check_optional(count.as_ref(), reporter);
+            check_block(&body.kind, reporter);
+        }
+        StmtKind::While {
+            condition, body, ..
+        } => {
+            check_expr(condition, reporter);
+            check_block(&body.kind, reporter);
+        }
+        StmtKind::Yield { key, value } => {
+            check_optional(key.as_ref(), reporter);
+            check_expr(value, reporter);
+        }
+        StmtKind::Break(value) | StmtKind::Cancel(value) => {
+            check_optional(value.as_ref(), reporter)
+        }
+        StmtKind::Match { .. } => forbidden("«match»", reporter),
+        StmtKind::Try { .. } | StmtKind::Throw(_) => forbidden("исключения", reporter),
+        StmtKind::Return(_) => forbidden("«return»", reporter),
+        StmtKind::Continue(_) => forbidden("«continue»", reporter),
+        // Вызов отдельной инструкцией нужен только ради побочных эффектов
+        StmtKind::Expression(expr) if matches!(expr.kind, ExprKind::Call { .. }) => {
+            forbidden("вызовы отдельной инструкцией", reporter)
+        }
+        StmtKind::Expression(expr) => check_expr(expr, reporter),
+    }
+}
+
+fn check_block(statements: &[Stmt], reporter: &mut Reporter) {
+    for statement in statements {
+        check_statement(statement, true, reporter);
+    }
+}
+
+fn check_optional(expr: Option<&Expr>, reporter: &mut Reporter) {
+    if let Some(expr) = expr {
+        check_expr(expr, reporter);
+    }
+}
+
+/// Ищет в выражении функции, создание объектов и вызовы не встроенных функций.
+fn check_expr(expr: &Expr, reporter: &mut Reporter) {
+    let forbidden = |what: &str, reporter: &mut Reporter| {
+        reporter.report(
+            Diagnostic::error(format!("{} недоступны в List LaCoN", what), expr.span)
+                .with_code("E0214"),
+        )
+    };
+    match &expr.kind {
+        ExprKind::Lambda { .. } | ExprKind::Routine(_) => forbidden("функции", reporter),
+        ExprKind::New { .. } => forbidden("объекты классов", reporter),
+        ExprKind::String { segments, .. } => {
+            for segment in segments {
+                if let StringSegment::Interpolation(expr) | StringSegment::CodePoint(expr) = segment
+                {
+                    check_expr(expr, reporter);
+                }
+            }
+        }
+        ExprKind::Array(items) | ExprKind::Tuple(items) => {
+            for item in items {
+                check_expr(item, reporter);
+            }
+        }
+        ExprKind::Dictionary(statements) => check_block(statements, reporter),
+        ExprKind::Expansion(expansion) => {
+            check_expr(&expansion.source, reporter);
+            check_block(&expansion.body.kind, reporter);
+        }
+        ExprKind::Unary { operand: inner, .. }
+        | ExprKind::Spread(inner)
+        | ExprKind::Floor(inner)
+        | ExprKind::Ceil(inner)
+        | ExprKind::Member { object: inner, .. } => check_expr(inner, reporter),
+        ExprKind::Binary { left, right, .. } => {
+            check_expr(left, reporter);
+            check_expr(right, reporter);
+        }
+        ExprKind::Ternary {
+            condition,
+            then_branch,
+            else_branch,
+        } => {
+            check_expr(condition, reporter);
+            check_expr(then_branch, reporter);
+            check_expr(else_branch, reporter);
+        }
+        ExprKind::Call {
+            callee, arguments, ..
+        } => {
+            let allowed = match &callee.kind {
+                ExprKind::Identifier(name) => builtins::is_function(name),
+                ExprKind::Member { property, .. } => builtins::is_method(property),
+                ExprKind::Operator(_) => true,
+                _ => false,
+            };
+            if !allowed {
+                let message = "вызовы пользовательских функций недоступны в List LaCoN";
+                let help = "вызываются встроенные функции вроде «RGBA» и «format» \
+                            и методы встроенных типов";
+                reporter.report(
+                    Diagnostic::error(message, callee.span)
+                        .with_code("E0214")
+                        .with_help(help),
+                );
+            }
+            check_expr(callee, reporter);
+            for argument in arguments {
+                check_expr(argument, reporter);
+            }
+        }
+        ExprKind::Index { object, index, .. } => {
+            check_expr(object, reporter);
+            check_expr(index, reporter);
+        }
+        ExprKind::Number { .. }
+        | ExprKind::Boolean(_)
+        | ExprKind::None
+        | ExprKind::Nil
+        | ExprKind::Undefined
+        | ExprKind::Auto
+        | ExprKind::Identifier(_)
+        | ExprKind::Section(_)
+        | ExprKind::DateTime(_)
+        | ExprKind::Color(_)
+        | ExprKind::Operator(_)
+        | ExprKind::Type(_)
+        | ExprKind::Error => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interpretator::engine::engine::tests::{entry, TempDir};
+    use crate::interpretator::engine::engine::Engine;
+
+    #[test]
+    fn test_list_dialect() {
+        let source = "const base 10\n[Marker:Output<Dictionary>]\nlimit base * 2\nfor item in [1, 2] {\n    let twice item * 2\n}\n";
+        let engine = Engine::new().with_dialect(Dialect::List);
+        let root = engine.evaluate_document(source).unwrap();
+        assert_eq!(entry(&root, "limit"), "20");
+
+        let source = "[Marker:Extension<Storage(Static)>]\nconst base 10\nfunction twice(x) { return x * 2 }\nvalue base\nconst late 1\n";
+        let errors = Engine::new().evaluate_document(source).unwrap_err();
+        let codes: Vec<_> = errors
+            .iter()
+            .filter_map(|error| error.code.as_deref())
+            .collect();
+        assert_eq!(codes, ["E0214", "E0215"]);
+
+        // Вызывать можно только встроенные функции и методы встроенных типов
+        let source = concat!(
+            "const brand #fa8246\n",
+            "light brand.lighten(10%)\n",
+            "label format(\"{:03}\", 7)\n",
+            "save(brand)\n",
+            "total compute(2)\n",
+        );
+        let errors = engine.evaluate_document(source).unwrap_err();
+        let messages: Vec<_> = errors.iter().map(|error| error.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            [
+                "вызовы отдельной инструкцией недоступны в List LaCoN",
+                "вызовы пользовательских функций недоступны в List LaCoN",
+            ]
+        );
+        let root = engine
+            .evaluate_document(&source[..source.find("save").unwrap()])
+            .unwrap();
+        assert_eq!(entry(&root, "label"), "007");
+
+        // `cancel` заканчивает текущий блок, а цикл идёт дальше
+        let source = "[Marker:Output<Array>]\nfor item in [1, 2, 3] {\n    if item == 2 {\n        cancel\n        item * 10\n    }\n    item\n}\n";
+        let root = engine.evaluate_document(source).unwrap();
+        assert_eq!(root.borrow().to_string(), "[1, 2, 3]");
+        let source = "[Marker:Output<Array>]\nloop 3 {\n    index\n    cancel\n    index * 10\n}\n";
+        let root = engine.evaluate_document(source).unwrap();
+        assert_eq!(root.borrow().to_string(), "[1, 2, 3]");
+
+        let dir = TempDir::new("list-dialect");
+        std::fs::write(dir.path().join("data.llacon"), "value 1\nlet counter 0\n").unwrap();
+        let errors = Engine::new()
+            .evaluate_file(&dir.path().join("data.llacon"))
+            .unwrap_err();
+        assert_eq!(errors[0].code.as_deref(), Some("E0214"));
+    }
+}
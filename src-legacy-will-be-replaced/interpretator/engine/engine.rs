@@ -0,0 +1,566 @@
+use crate::extensions::output::backend_for;
+use crate::interpretator::analysis::schema::compiler::Schema;
+use crate::interpretator::analysis::schema::export::json_schema;
+use crate::interpretator::engine::dialect::{check_list, Dialect};
+use crate::interpretator::frontend::ast::node::Program;
+use crate::interpretator::frontend::ast::statement::StmtKind;
+use crate::interpretator::frontend::diagnostics::error::{Diagnostic, Diagnostics};
+use crate::interpretator::frontend::diagnostics::reporter::Reporter;
+use crate::interpretator::frontend::diagnostics::span::Span;
+use crate::interpretator::frontend::parser::parser::parse_source;
+use crate::interpretator::modules::loader::file_loader::FileLoader;
+use crate::interpretator::prototypes::environment::time::Clock;
+use crate::interpretator::prototypes::types::formal::formal::UnitError;
+use crate::interpretator::prototypes::types::formal::length::UnitContext;
+use crate::interpretator::prototypes::types::object::array::Array;
+use crate::interpretator::prototypes::types::object::dictionary::Dictionary;
+use crate::interpretator::prototypes::types::object::object::ObjectRef;
+use crate::interpretator::runtime::execution::actions::evaluation::{Evaluator, Target};
+use crate::interpretator::stdlib::core::prelude::Prelude;
+use indexmap::IndexMap;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+/// Форма корня документа, заданная заголовком `[Document:...]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DocumentKind {
+    Dictionary,
+    Array,
+}
+
+/// Точка входа интерпретатора: превращает исходный текст документа в дерево значений.
+pub struct Engine {
+    prelude: Prelude,
+    base_dir: PathBuf,
+    dialect: Dialect,
+}
+
+impl Engine {
+    pub fn new() -> Self {
+        Engine {
+            prelude: Prelude::new(),
+            base_dir: PathBuf::from("."),
+            dialect: Dialect::Full,
+        }
+    }
+
+    /// Каталог документа: от него отсчитываются пути в `include`.
+    pub fn with_base_dir(mut self, base_dir: impl Into<PathBuf>) -> Self {
+        self.base_dir = base_dir.into();
+        self
+    }
+
+    /// Набор конструкций, доступных документам движка; `[Marker:Extension<...>]`
+    /// включает List LaCoN и без этой настройки.
+    pub fn with_dialect(mut self, dialect: Dialect) -> Self {
+        self.dialect = dialect;
+        self
+    }
+
+    /// Размеры шрифта, от которых отсчитываются `em` и `rem` в документе.
+    /// Нулевой, отрицательный или бесконечный размер — ошибка.
+    pub fn with_unit_context(mut self, units: UnitContext) -> Result<Self, UnitError> {
+        units.validate()?;
+        self.prelude.units = units;
+        Ok(self)
+    }
+
+    /// Часы, по которым документ узнаёт текущее время в `now()` и `today()`.
+    pub fn with_clock(mut self, clock: impl Clock + 'static) -> Self {
+        self.prelude.clock = Rc::new(clock);
+        self
+    }
+
+    pub fn prelude(&self) -> &Prelude {
+        &self.prelude
+    }
+
+    /// Вычисляет документ в режиме данных (`[Mode:Data]`, режим по умолчанию):
+    /// элементы верхнего уровня вроде `key-1 value` становятся элементами корневого словаря,
+    /// а под `[Document:Array]` значения без ключей — элементами корневого массива.
+    ///
+    /// Возвращает все найденные ошибки, если документ не удалось вычислить целиком.
+    pub fn evaluate_document(&self, source: &str) -> Result<ObjectRef, Diagnostics> {
+        let mut reporter = Reporter::new();
+        let (root, _) = self.run(source, None, &mut reporter);
+        if reporter.has_errors() {
+            return Err(reporter.into_diagnostics());
+        }
+        Ok(root)
+    }
+
+    /// Читает и вычисляет файл документа: пути в `include` отсчитываются от его каталога,
+    /// а файлы `.llacon` проверяются как List LaCoN.
+    pub fn evaluate_file(&self, path: &Path) -> Result<ObjectRef, Diagnostics> {
+        let source = std::fs::read_to_string(path).map_err(|error| {
+            vec![Diagnostic::error(
+                format!("не удалось прочитать «{}»: {}", path.display(), error),
+                Span::default(),
+            )
+            .with_code("E0212")]
+        })?;
+        let mut reporter = Reporter::new();
+        let (root, _) = self.run(&source, Some(path), &mut reporter);
+        if reporter.has_errors() {
+            return Err(reporter.into_diagnostics());
+        }
+        Ok(root)
+    }
+
+    /// Вычисляет документ и записывает результат в каждом формате,
+    /// заказанном маркерами `[Marker:Output<Dictionary> as JSON]`, в порядке маркеров.
+    pub fn export_document(&self, source: &str) -> Result<Vec<Export>, Diagnostics> {
+        let mut reporter = Reporter::new();
+        let (root, headers) = self.run(source, None, &mut reporter);
+        if reporter.has_errors() {
+            return Err(reporter.into_diagnostics());
+        }
+
+        let mut exports = Vec::new();
+        for marker in headers.outputs {
+            match write_output(&root, &marker.format) {
+                Ok(text) => exports.push(Export {
+                    format: marker.format,
+                    text,
+                }),
+                Err(message) => {
+                    reporter.report(Diagnostic::error(message, marker.span).with_code("E0211"))
+                }
+            }
+        }
+        if reporter.has_errors() {
+            return Err(reporter.into_diagnostics());
+        }
+        Ok(exports)
+    }
+
+    /// Компилирует схему `.slacon` и записывает её как JSON Schema 2020-12, чтобы документы,
+    /// выведенные через `[Marker:Output ... as JSON]`, проверялись и вне LaCoN.
+    pub fn export_schema(&self, source: &str) -> Result<String, Diagnostics> {
+        let schema = Schema::compile(source)?;
+        Ok(serde_json::to_string_pretty(&json_schema(&schema))
+            .expect("JSON Schema всегда сериализуема"))
+    }
+
+    /// `path` — файл, из которого прочитан документ: он задаёт каталог и диалект.
+    fn run(
+        &self,
+        source: &str,
+        path: Option<&Path>,
+        reporter: &mut Reporter,
+    ) -> (ObjectRef, Headers) {
+        let program = parse_source(source, reporter);
+        let headers = read_headers(&program, reporter);
+        let base_dir = path.and_then(Path::parent).unwrap_or(&self.base_dir);
+        let list = [
+            self.dialect,
+            Dialect::declared(&program),
+            path.map(Dialect::from_path).unwrap_or_default(),
+        ]
+        .contains(&Dialect::List);
+        if list {
+            check_list(&program, reporter);
+            if reporter.has_errors() {
+                return (
+                    Array::new_instance(self.prelude.array.clone(), Vec::new()),
+                    headers,
+                );
+            }
+        }
+
+        let mut target = match headers.kind {
+            DocumentKind::Dictionary => Target::Dictionary(IndexMap::new()),
+            DocumentKind::Array => Target::Array(Vec::new()),
+        };
+        Evaluator::new(&self.prelude, reporter)
+            .with_base_dir(base_dir)
+            .with_loader(
+                path.map(|path| FileLoader::new().nested(path))
+                    .unwrap_or_default(),
+            )
+            .execute_block(&program.statements, &mut target);
+
+        let root = match target {
+            Target::Dictionary(entries) => {
+                Dictionary::new_instance(self.prelude.dictionary.clone(), entries)
+            }
+            Target::Array(elements) => Array::new_instance(self.prelude.array.clone(), elements),
+        };
+        (root, headers)
+    }
+}
+
+impl Default for Engine {
+    fn default() -> Self {
+        Engine::new()
+    }
+}
+
+/// Документ, записанный в одном из форматов вывода.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Export {
+    /// Формат как он указан в маркере: `JSON`
+    pub format: String,
+    pub text: String,
+}
+
+/// Маркер `[Marker:Output<...> as FORMAT]`.
+#[derive(Debug, Clone)]
+struct OutputMarker {
+    format: String,
+    span: Span,
+}
+
+/// Заголовки, от которых зависит вычисление и вывод документа.
+#[derive(Debug)]
+struct Headers {
+    kind: DocumentKind,
+    outputs: Vec<OutputMarker>,
+}
+
+/// Читает заголовки документа; неизвестные заголовки оставлены другим стадиям.
+fn read_headers(program: &Program, reporter: &mut Reporter) -> Headers {
+    let mut headers = Headers {
+        kind: DocumentKind::Dictionary,
+        outputs: Vec::new(),
+    };
+    for statement in &program.statements {
+        let (name, body) = match &statement.kind {
+            StmtKind::Header { name, body } => (name.as_str(), body.trim()),
+            _ => continue,
+        };
+        let span = statement.span;
+        let error = match (name, body) {
+            ("Mode", "Data") => continue,
+            ("Document", kind) => match document_kind(kind) {
+                Some(kind) => {
+                    headers.kind = kind;
+                    continue;
+                }
+                None => unknown_kind(kind, span),
+            },
+            ("Mode", other) => {
+                Diagnostic::error(format!("режим «{}» не поддерживается", other), span)
+                    .with_help("движок пока умеет только «[Mode:Data]»")
+            }
+            ("Marker", body) if body.starts_with("Output") => match parse_output_marker(body) {
+                Some((kind, format)) => {
+                    match kind.map(|kind| (kind, document_kind(kind))) {
+                        Some((_, Some(kind))) => headers.kind = kind,
+                        Some((kind, None)) => {
+                            reporter.report(unknown_kind(kind, span).with_code("E0210"));
+                            continue;
+                        }
+                        None => {}
+                    }
+                    if let Some(format) = format {
+                        headers.outputs.push(OutputMarker {
+                            format: format.to_string(),
+                            span,
+                        });
+                    }
+                    continue;
+                }
+                None => Diagnostic::error(format!("некорректный маркер вывода «{}»", body), span)
+                    .with_help("ожидалось «[Marker:Output<Dictionary> as JSON]»"),
+            },
+            _ => continue,
+        };
+        reporter.report(error.with_code("E0210"));
+    }
+    headers
+}
+
+fn unknown_kind(kind: &str, span: Span) -> Diagnostic {
+    Diagnostic::error(format!("неизвестный вид документа «{}»", kind), span)
+        .with_help("ожидалось «Dictionary» или «Array»")
+}
+
+fn document_kind(name: &str) -> Option<DocumentKind> {
+    match name {
+        "Dictionary" => Some(DocumentKind::Dictionary),
+        "Array" => Some(DocumentKind::Array),
+        _ => None,
+    }
+}
+
+/// Разбирает `Output<Dictionary> as JSON` на вид документа и формат; обе части необязательны.
+fn parse_output_marker(body: &str) -> Option<(Option<&str>, Option<&str>)> {
+    let rest = body.strip_prefix("Output")?.trim_start();
+    let (kind, rest) = match rest.strip_prefix('<') {
+        Some(rest) => {
+            let end = rest.find('>')?;
+            (Some(rest[..end].trim()), rest[end + 1..].trim())
+        }
+        None => (None, rest),
+    };
+    if rest.is_empty() {
+        return Some((kind, None));
+    }
+    let format = rest.strip_prefix("as")?.trim();
+    if format.is_empty() || format.contains(char::is_whitespace) {
+        return None;
+    }
+    Some((kind, Some(format)))
+}
+
+/// Записывает корень документа в формате вывода; `Err` содержит текст ошибки.
+fn write_output(root: &ObjectRef, format: &str) -> Result<String, String> {
+    match backend_for(format) {
+        Some(backend) => backend.write(root).map_err(|error| error.to_string()),
+        None => Err(format!(
+            "формат вывода «{}» не поддерживается, доступны JSON, YAML и TOML",
+            format
+        )),
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::interpretator::frontend::diagnostics::render::Renderer;
+    use crate::interpretator::prototypes::types::object::dictionary::DictionaryValue;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    pub(crate) fn entry(root: &ObjectRef, key: &str) -> String {
+        let root = root.borrow();
+        let dictionary = root
+            .downcast::<DictionaryValue>()
+            .expect("корень — словарь");
+        let text = dictionary.entries[key].borrow().to_string();
+        text
+    }
+
+    /// Временный каталог теста: у каждого вызова свой путь, удаляется вместе со значением.
+    pub(crate) struct TempDir {
+        path: PathBuf,
+    }
+
+    impl TempDir {
+        pub(crate) fn new(name: &str) -> Self {
+            static COUNTER: AtomicUsize = AtomicUsize::new(0);
+            let index = COUNTER.fetch_add(1, Ordering::Relaxed);
+            let path = std::env::temp_dir().join(format!(
+                "lacon-{}-{}-{}",
+                name,
+                std::process::id(),
+                index
+            ));
+            std::fs::create_dir_all(&path).unwrap();
+            TempDir { path }
+        }
+
+        pub(crate) fn path(&self) -> &Path {
+            &self.path
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.path);
+        }
+    }
+
+    #[test]
+    fn test_include_json() {
+        let dir = TempDir::new("include-json");
+        std::fs::write(
+            dir.path().join("data.json"),
+            r#"{"port": 8080, "hosts": ["a", "b"], "tls": null}"#,
+        )
+        .unwrap();
+
+        let engine = Engine::new().with_base_dir(dir.path());
+        let root = engine
+            .evaluate_document("name server\ninclude \"./data.json\"\n")
+            .unwrap();
+        assert_eq!(entry(&root, "port"), "8080");
+        assert_eq!(entry(&root, "hosts"), "[a, b]");
+        assert_eq!(entry(&root, "tls"), "nil");
+
+        let errors = engine
+            .evaluate_document("include \"./missing.json\"\n")
+            .unwrap_err();
+        assert_eq!(errors[0].code.as_deref(), Some("E0212"));
+    }
+
+    #[test]
+    fn test_include_provided_keys() {
+        let dir = TempDir::new("include-provide");
+        std::fs::create_dir_all(dir.path().join("shared")).unwrap();
+        std::fs::write(
+            dir.path().join("shared/keys.llacon"),
+            "key_1 1\nkey_2 2\nkey_3 3\nprovide {\n    key_1,\n    key_2\n}\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("main.lacon"),
+            "name main\ninclude \"./shared/keys.llacon\"\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("clash.lacon"),
+            "key_1 0\ninclude \"./shared/keys.llacon\"\nkey_2 0\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("merge.lacon"),
+            concat!(
+                "include \"./shared/keys.llacon\"\n",
+                "const extra {key_1: 0}\n",
+                "...extra\n",
+                "generate ([1]) as let (i) { yield {key_2: i} }\n",
+                "key_1 += 1\n",
+            ),
+        )
+        .unwrap();
+
+        let root = Engine::new()
+            .evaluate_file(&dir.path().join("main.lacon"))
+            .unwrap();
+        assert_eq!(
+            root.borrow().to_string(),
+            r#"{"name": main, "key_1": 1, "key_2": 2}"#
+        );
+
+        let errors = Engine::new()
+            .evaluate_file(&dir.path().join("clash.lacon"))
+            .unwrap_err();
+        let codes: Vec<_> = errors
+            .iter()
+            .filter_map(|error| error.code.as_deref())
+            .collect();
+        assert_eq!(codes, ["E0216", "E0216"]);
+
+        let errors = Engine::new()
+            .evaluate_file(&dir.path().join("merge.lacon"))
+            .unwrap_err();
+        let messages: Vec<_> = errors.iter().map(|error| error.message.as_str()).collect();
+        assert_eq!(messages.len(), 3, "{:?}", messages);
+        assert!(errors
+            .iter()
+            .all(|error| error.code.as_deref() == Some("E0216")));
+    }
+
+    #[test]
+    fn test_use_schema() {
+        let dir = TempDir::new("use-schema");
+        std::fs::write(
+            dir.path().join("schema.slacon"),
+            "entity_type: <String>\npower: <Power>\nfuel@[value in [\"solid\", \"gas\"]]: <String>\nnote?: <String>\n",
+        )
+        .unwrap();
+        let document = |body: &str| format!("use schema \"./schema.slacon\"\n{}", body);
+
+        let root = Engine::new()
+            .with_base_dir(dir.path())
+            .evaluate_document(&document(
+                "entity_type \"engine\"\npower 15kW\nfuel \"solid\"\n",
+            ))
+            .unwrap();
+        assert_eq!(entry(&root, "power"), "15kW");
+
+        let source = document("entity_type 5\nfuel \"plasma\"\nextra 1\n");
+        let errors = Engine::new()
+            .with_base_dir(dir.path())
+            .evaluate_document(&source)
+            .unwrap_err();
+        let messages: Vec<_> = errors.iter().map(|error| error.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            [
+                "в документе нет поля «power» из схемы",
+                "поле «entity_type» должно быть типа String, а не Int",
+                "значение «plasma» поля «fuel» нарушает правило «value in [\"solid\", \"gas\"]»",
+                "поля «extra» нет в схеме",
+            ]
+        );
+        assert!(errors
+            .iter()
+            .all(|error| error.code.as_deref() == Some("E0218")));
+        assert_eq!(errors[1].span().start.line, 2);
+        let schema_file = dir.path().join("schema.slacon").display().to_string();
+        let label = &errors[1].secondary[0];
+        assert_eq!(label.source.as_deref(), Some(schema_file.as_str()));
+        assert_eq!(label.span.start.line, 1);
+        let label = &errors[3].secondary[0];
+        assert_eq!(
+            label.message.as_deref(),
+            Some("поля схемы «./schema.slacon»")
+        );
+
+        let schema = std::fs::read_to_string(dir.path().join("schema.slacon")).unwrap();
+        let rendered = Renderer::new(&source, "doc.lacon")
+            .with_source(&schema, &schema_file)
+            .render(&errors[1]);
+        assert!(rendered.ends_with(&format!(
+            "::: {}:1:1\n  |\n1 | entity_type: <String>\n  | ----------- поле описано здесь\n",
+            schema_file
+        )));
+    }
+
+    #[test]
+    fn test_schema_expression_dimensions() {
+        let dir = TempDir::new("schema-dimensions");
+        std::fs::write(
+            dir.path().join("flux.slacon"),
+            "flux<Expr>: <LuminousFlux> / <Time ** 3>\nfuel_consumption<Expr>@[unit: g/s]: <Mass> / delta <Time>\npower@[unit: kW]: <Power>\n",
+        )
+        .unwrap();
+        let document = |body: &str| format!("use schema \"./flux.slacon\"\n{}", body);
+
+        let root = Engine::new()
+            .with_base_dir(dir.path())
+            .evaluate_document(&document(
+                "flux 54lm / 3sec ** 3\nfuel_consumption 120g / 1min\npower 1500W\n",
+            ))
+            .unwrap();
+        assert_eq!(entry(&root, "flux"), "2lm/sec3");
+        assert_eq!(entry(&root, "fuel_consumption"), "2g/s");
+        assert_eq!(entry(&root, "power"), "1.5kW");
+
+        let errors = Engine::new()
+            .with_base_dir(dir.path())
+            .evaluate_document(&document(
+                "flux 40lx / 5g\nfuel_consumption 10g\npower 1500W\n",
+            ))
+            .unwrap_err();
+        let messages: Vec<_> = errors.iter().map(|error| error.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            [
+                "поле «flux»: «40lx» должно иметь размерность LuminousFlux, а не Illuminance",
+                "поле «fuel_consumption»: «10g» должно иметь размерность kg/s, а не Mass",
+            ]
+        );
+
+        let errors = Engine::new()
+            .with_base_dir(dir.path())
+            .evaluate_document(&document(
+                "flux 40lm / 3sec\nfuel_consumption 2g/s\npower 1500W\n",
+            ))
+            .unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(
+            errors[0].message,
+            "поле «flux»: «3sec» должно иметь размерность s3, а не Time"
+        );
+    }
+
+    #[test]
+    fn test_export_by_output_marker() {
+        let source = "[Marker:Output<Array> as JSON]\n1\n\"два\"\n";
+        let exports = Engine::new().export_document(source).unwrap();
+        assert_eq!(exports.len(), 1);
+        assert_eq!(exports[0].format, "JSON");
+        assert_eq!(exports[0].text, "[\n  1,\n  \"два\"\n]");
+
+        let errors = Engine::new()
+            .export_document("[Marker:Output<Dictionary> as XML]\nkey value\n")
+            .unwrap_err();
+        assert_eq!(
+            errors[0].message,
+            "формат вывода «XML» не поддерживается, доступны JSON, YAML и TOML"
+        );
+    }
+}
@@ -0,0 +1,3 @@
+pub mod dialect;
+#[allow(clippy::module_inception, reason = "движок LaCoN живёт в «engine/engine.rs» по раскладке каталогов")]
+pub mod engine;
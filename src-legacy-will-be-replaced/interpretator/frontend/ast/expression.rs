@@ -0,0 +1,168 @@
+use crate::interpretator::frontend::ast::node::{Node, TypeExpr};
+use crate::interpretator::frontend::ast::statement::{Body, Expansion, Param, RoutineDecl, Stmt};
+use crate::interpretator::frontend::lexer::token::QuoteStyle;
+
+pub type Expr = Node<ExprKind>;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExprKind {
+    /// Число в исходном виде; разбор значения — задача интерпретатора.
+    Number {
+        literal: String,
+        unit: Option<String>,
+    },
+    String {
+        quote: QuoteStyle,
+        segments: Vec<StringSegment>,
+    },
+    Boolean(bool),
+    None,
+    Nil,
+    Undefined,
+    Auto,
+    /// Имя: `value`, `$value`, `root`, `global`
+    Identifier(String),
+    /// Ссылка на секцию: `§config`
+    Section(String),
+    /// Дата и время ISO 8601: `2024-03-15T10:30:00+03:00`
+    DateTime(String),
+    /// Цвет `#fa8246`; хранятся цифры без `#`
+    Color(String),
+    /// Оператор как значение: `[+, -, *]`, `c<operator> +`
+    Operator(BinaryOp),
+    Array(Vec<Expr>),
+    /// Тело словаря — те же инструкции, что и в корне документа.
+    Dictionary(Vec<Stmt>),
+    Tuple(Vec<Expr>),
+    /// Тип как значение: `<LuminousFlux> / <Time ** 3>`
+    Type(TypeExpr),
+    Unary {
+        op: UnaryOp,
+        operand: Box<Expr>,
+    },
+    Binary {
+        op: BinaryOp,
+        left: Box<Expr>,
+        right: Box<Expr>,
+    },
+    Ternary {
+        condition: Box<Expr>,
+        then_branch: Box<Expr>,
+        else_branch: Box<Expr>,
+    },
+    Call {
+        callee: Box<Expr>,
+        type_arguments: Vec<TypeExpr>,
+        arguments: Vec<Expr>,
+    },
+    Member {
+        object: Box<Expr>,
+        property: String,
+        optional: bool,
+    },
+    Index {
+        object: Box<Expr>,
+        index: Box<Expr>,
+        optional: bool,
+    },
+    Lambda {
+        params: Vec<Param>,
+        body: Box<Body>,
+    },
+    /// Анонимная функция или процедура: `function(a, b) { ... }`
+    Routine(Box<RoutineDecl>),
+    New {
+        class: Box<Expr>,
+        arguments: Vec<Expr>,
+    },
+    /// Деконструкция: `...value`
+    Spread(Box<Expr>),
+    /// `generate`, `spread` или `filter`: значения из `yield` раскрываются
+    /// в объемлющий массив или словарь
+    Expansion(Box<Expansion>),
+    /// `⌊ value ⌋`
+    Floor(Box<Expr>),
+    /// `⌈ value ⌉`
+    Ceil(Box<Expr>),
+    /// Выражение, которое не удалось разобрать; ошибка уже сообщена.
+    Error,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum StringSegment {
+    Text(String),
+    Interpolation(Expr),
+    /// `\u{${code}}` — символ по коду, вычисленному выражением
+    CodePoint(Expr),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnaryOp {
+    Negate, // -
+    Plus,   // +
+    Not,    // ! not
+    Delta,  // Δ — разность (например, температур)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinaryOp {
+    Add,            // +
+    Sub,            // -
+    Mul,            // *
+    Div,            // /
+    IDiv,           // //
+    Mod,            // %
+    Pow,            // **
+    Less,           // <
+    Greater,        // >
+    LessEq,         // <=
+    GreaterEq,      // >=
+    Equal,          // ==
+    StrictEqual,    // ===
+    NotEqual,       // !=
+    StrictNotEqual, // !==
+    Identical,      // ≣
+    Matches,        // ~=
+    And,            // && and
+    Or,             // || or
+    Is,             // is
+    In,             // in
+    Range,          // ..
+    Convert,        // -> →
+    Monus,          // ∸ .-
+    CartesianSum,   // ∔
+    Pipeline,       // |>
+}
+
+impl BinaryOp {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BinaryOp::Add => "+",
+            BinaryOp::Sub => "-",
+            BinaryOp::Mul => "*",
+            BinaryOp::Div => "/",
+            BinaryOp::IDiv => "//",
+            BinaryOp::Mod => "%",
+            BinaryOp::Pow => "**",
+            BinaryOp::Less => "<",
+            BinaryOp::Greater => ">",
+            BinaryOp::LessEq => "<=",
+            BinaryOp::GreaterEq => ">=",
+            BinaryOp::Equal => "==",
+            BinaryOp::StrictEqual => "===",
+            BinaryOp::NotEqual => "!=",
+            BinaryOp::StrictNotEqual => "!==",
+            BinaryOp::Identical => "≣",
+            BinaryOp::Matches => "~=",
+            BinaryOp::And => "&&",
+            BinaryOp::Or => "||",
+            BinaryOp::Is => "is",
+            BinaryOp::In => "in",
+            BinaryOp::Range => "..",
+            BinaryOp::Convert => "->",
+            BinaryOp::Monus => "∸",
+            BinaryOp::CartesianSum => "∔",
+            BinaryOp::Pipeline => "|>",
+        }
+    }
+}
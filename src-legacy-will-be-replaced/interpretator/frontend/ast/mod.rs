@@ -0,0 +1,3 @@
+pub mod expression;
+pub mod node;
+pub mod statement;
@@ -0,0 +1,52 @@
+use crate::interpretator::frontend::ast::statement::Stmt;
+use crate::interpretator::frontend::diagnostics::span::Span;
+
+/// Узел AST: полезные данные плюс участок исходного текста, из которого они получены.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Node<T> {
+    pub kind: T,
+    pub span: Span,
+}
+
+impl<T> Node<T> {
+    pub fn new(kind: T, span: Span) -> Self {
+        Node { kind, span }
+    }
+}
+
+/// Корень дерева — разобранный файл целиком.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Program {
+    pub statements: Vec<Stmt>,
+    pub span: Span,
+}
+
+/// Указатель типа `<...>`.
+pub type TypeExpr = Node<TypeExprKind>;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TypeExprKind {
+    /// `Int`, `Unit::Length`, `Array<Operator>`
+    Named {
+        path: Vec<String>,
+        arguments: Vec<TypeExpr>,
+    },
+    /// `String[]` — массив значений типа
+    Array(Box<TypeExpr>),
+    /// `String{}` — словарь значений типа
+    Dictionary(Box<TypeExpr>),
+    /// `(quotient<int>, remainder<int>)` — кортеж с необязательными именами полей
+    Tuple(Vec<(Option<String>, TypeExpr)>),
+    /// `Time ** 3` — степень размерности
+    Power(Box<TypeExpr>, i32),
+}
+
+impl TypeExprKind {
+    /// Имя типа без пространства имён, если это простой именованный тип.
+    pub fn simple_name(&self) -> Option<&str> {
+        match self {
+            TypeExprKind::Named { path, .. } => path.last().map(|s| s.as_str()),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,282 @@
+use crate::interpretator::frontend::ast::expression::Expr;
+use crate::interpretator::frontend::ast::node::{Node, TypeExpr};
+use crate::interpretator::frontend::diagnostics::span::Span;
+
+pub type Stmt = Node<StmtKind>;
+
+/// Блок инструкций: `{ ... }` или строки с бо́льшим отступом.
+pub type Block = Node<Vec<Stmt>>;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum StmtKind {
+    /// `[Mode:Data]`, `[Document:Array]`, `[Marker:Output<Dictionary> as JSON]`
+    Header {
+        name: String,
+        body: String,
+    },
+    VarDecl(VarDecl),
+    /// «Контейнер» — элемент выходных данных: `key value`, `key: value`, `key = value`
+    Entry(Entry),
+    Assign {
+        target: Expr,
+        op: AssignOp,
+        /// Отсутствует у `++` и `--`
+        value: Option<Expr>,
+    },
+    Routine(RoutineDecl),
+    Class(ClassDecl),
+    /// `§name { ... }`, `static §config { ... }`, анонимная `§ { ... }`
+    Section {
+        name: String,
+        modifiers: Vec<Modifier>,
+        body: Block,
+    },
+    /// `include "./data.json"` — вставляет данные файла на место директивы
+    Include {
+        path: String,
+    },
+    /// `use schema "./schema.slacon"` — документ проверяется по схеме SLaCoN
+    UseSchema {
+        path: String,
+    },
+    /// `provide { key_1, key_2 }` — ключи, которые документ отдаёт включающему его файлу
+    Provide {
+        keys: Vec<String>,
+    },
+    If {
+        branches: Vec<(Expr, Block)>,
+        otherwise: Option<Block>,
+    },
+    For {
+        pattern: Pattern,
+        iterable: Expr,
+        body: Block,
+    },
+    Loop {
+        count: Option<Expr>,
+        body: Block,
+    },
+    While {
+        condition: Expr,
+        body: Block,
+        /// `do { } while (...)`
+        do_while: bool,
+    },
+    Match {
+        subject: Expr,
+        arms: Vec<MatchArm>,
+    },
+    Try {
+        body: Block,
+        catch: Option<CatchClause>,
+        finally: Option<Block>,
+    },
+    /// `yield value` или пара `yield key, value` внутри `generate`, `spread` и `filter`
+    Yield {
+        /// Ключ пары: имя записывается строкой, `[expr]` — вычисляемый ключ
+        key: Option<Expr>,
+        value: Expr,
+    },
+    Return(Option<Expr>),
+    Break(Option<Expr>),
+    Continue(Option<Expr>),
+    Cancel(Option<Expr>),
+    Throw(Option<Expr>),
+    Block(Block),
+    Expression(Expr),
+    /// Инструкция, которую не удалось разобрать; ошибка уже сообщена.
+    Error,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Modifier {
+    Local,
+    Global,
+    Static,
+    Public,
+    Private,
+    Strict,
+    Async,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeclKind {
+    Const,
+    Let,
+}
+
+/// Что связывается объявлением: одно имя или кортеж `(a, b)`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Pattern {
+    Name(String),
+    Tuple(Vec<String>),
+}
+
+/// Элемент списка атрибутов `@[unit: nm, value > 0m, clamp]`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Attribute {
+    /// Имя для пар `name: value`; у правил-выражений отсутствует.
+    pub name: Option<String>,
+    pub value: Expr,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct VarDecl {
+    pub kind: DeclKind,
+    pub modifiers: Vec<Modifier>,
+    pub pattern: Pattern,
+    /// `&name` — ссылка
+    pub by_ref: bool,
+    pub ty: Option<TypeExpr>,
+    pub attributes: Vec<Attribute>,
+    /// `name<Type>?` — допускает nil, none и auto
+    pub optional: bool,
+    pub value: Option<Expr>,
+    /// `name => value` — вычисляется при каждом обращении
+    pub lazy: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum EntryKey {
+    Name(String),
+    /// Ключ-строка: `"1 + 7" value`
+    String(Expr),
+    /// Массив ключей: `[key, key2] value`
+    Multiple(Vec<EntryKey>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entry {
+    pub key: EntryKey,
+    pub key_span: Span,
+    pub ty: Option<TypeExpr>,
+    pub attributes: Vec<Attribute>,
+    pub optional: bool,
+    pub value: Expr,
+    pub lazy: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AssignOp {
+    Set,       // =
+    Add,       // +=
+    Sub,       // -=
+    Mul,       // *=
+    Div,       // /=
+    Mod,       // %=
+    Concat,    // .=
+    And,       // &=
+    Increment, // ++
+    Decrement, // --
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RoutineKind {
+    Function,
+    Procedure,
+    Iterator,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GenericParam {
+    pub name: String,
+    pub bound: Option<TypeExpr>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Param {
+    pub name: String,
+    /// `&argument`
+    pub by_ref: bool,
+    /// `args...` / `...args`
+    pub variadic: bool,
+    pub ty: Option<TypeExpr>,
+    /// `argument?`
+    pub optional: bool,
+    pub default: Option<Expr>,
+    pub span: Span,
+}
+
+/// Тело функции, лямбды или ветки match: блок или одно выражение после `=>`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Body {
+    Block(Block),
+    Expression(Expr),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RoutineDecl {
+    pub kind: RoutineKind,
+    pub modifiers: Vec<Modifier>,
+    /// Отсутствует у анонимных функций
+    pub name: Option<String>,
+    pub generics: Vec<GenericParam>,
+    pub params: Vec<Param>,
+    pub return_type: Option<TypeExpr>,
+    pub body: Body,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClassKind {
+    Class,
+    Structure,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClassDecl {
+    pub kind: ClassKind,
+    pub modifiers: Vec<Modifier>,
+    pub name: String,
+    pub extends: Option<TypeExpr>,
+    pub body: Block,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MatchArm {
+    pub pattern: Expr,
+    pub guard: Option<Expr>,
+    pub body: Body,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExpansionKind {
+    Generate,
+    Spread,
+    Filter,
+}
+
+impl ExpansionKind {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "generate" => Some(ExpansionKind::Generate),
+            "spread" => Some(ExpansionKind::Spread),
+            "filter" => Some(ExpansionKind::Filter),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ExpansionKind::Generate => "generate",
+            ExpansionKind::Spread => "spread",
+            ExpansionKind::Filter => "filter",
+        }
+    }
+}
+
+/// `spread (letters, suffixes) as let (letter, suffix) { yield ... }`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Expansion {
+    pub kind: ExpansionKind,
+    /// Источник; кортеж `(a, b)` — несколько источников, которые идут параллельно
+    pub source: Expr,
+    pub pattern: Pattern,
+    pub body: Block,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CatchClause {
+    pub binding: Option<String>,
+    pub body: Block,
+}
@@ -0,0 +1,176 @@
+use crate::interpretator::frontend::diagnostics::span::Span;
+use serde::Serialize;
+use std::fmt;
+
+/// Насколько серьёзна диагностика.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Error,
+    Warning,
+    Note,
+}
+
+impl Severity {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Severity::Error => "ошибка",
+            Severity::Warning => "предупреждение",
+            Severity::Note => "примечание",
+        }
+    }
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Подписанный участок исходного текста.
+/// Основная метка указывает на саму ошибку, вспомогательные — на связанные места.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Label {
+    /// Файл, в котором лежит участок; `None` — тот же, что у диагностики.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    pub span: Span,
+    pub message: Option<String>,
+}
+
+impl Label {
+    pub fn new(span: Span, message: impl Into<String>) -> Self {
+        Label {
+            source: None,
+            span,
+            message: Some(message.into()),
+        }
+    }
+
+    /// Метка без подписи: только подчёркивание.
+    pub fn bare(span: Span) -> Self {
+        Label {
+            source: None,
+            span,
+            message: None,
+        }
+    }
+
+    /// Метка в другом файле: описание поля в схеме, подключённый документ.
+    pub fn in_source(source: impl Into<String>, span: Span, message: impl Into<String>) -> Self {
+        Label {
+            source: Some(source.into()),
+            ..Label::new(span, message)
+        }
+    }
+}
+
+/// Все диагностики одного прогона в порядке появления в исходном тексте.
+pub type Diagnostics = Vec<Diagnostic>;
+
+/// Сообщение об ошибке или предупреждение, привязанное к участку исходного текста.
+///
+/// ```text
+/// ошибка[E0100]: ожидалось выражение, найдено «)»
+///  --> config.lacon:5:7
+///   |
+/// 5 | inner (1, 2 +)
+///   |              ^ ожидалось выражение
+///   |
+///   = помощь: ...
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    /// Код вида `E0100`, по которому ошибку можно найти в документации.
+    pub code: Option<String>,
+    pub message: String,
+    pub primary: Label,
+    pub secondary: Vec<Label>,
+    pub notes: Vec<String>,
+    pub help: Option<String>,
+}
+
+impl Diagnostic {
+    pub fn new(severity: Severity, message: impl Into<String>, span: Span) -> Self {
+        Diagnostic {
+            severity,
+            code: None,
+            message: message.into(),
+            primary: Label::bare(span),
+            secondary: Vec::new(),
+            notes: Vec::new(),
+            help: None,
+        }
+    }
+
+    pub fn error(message: impl Into<String>, span: Span) -> Self {
+        Diagnostic::new(Severity::Error, message, span)
+    }
+
+    pub fn warning(message: impl Into<String>, span: Span) -> Self {
+        Diagnostic::new(Severity::Warning, message, span)
+    }
+
+    /// Участок, к которому относится диагностика.
+    pub fn span(&self) -> Span {
+        self.primary.span
+    }
+
+    pub fn with_code(mut self, code: impl Into<String>) -> Self {
+        self.code = Some(code.into());
+        self
+    }
+
+    /// Подпись под основным участком.
+    pub fn with_primary_message(mut self, message: impl Into<String>) -> Self {
+        self.primary.message = Some(message.into());
+        self
+    }
+
+    pub fn with_label(mut self, span: Span, message: impl Into<String>) -> Self {
+        self.secondary.push(Label::new(span, message));
+        self
+    }
+
+    /// Вспомогательная метка в другом файле.
+    pub fn with_label_in(
+        mut self,
+        source: impl Into<String>,
+        span: Span,
+        message: impl Into<String>,
+    ) -> Self {
+        self.secondary.push(Label::in_source(source, span, message));
+        self
+    }
+
+    pub fn with_note(mut self, note: impl Into<String>) -> Self {
+        self.notes.push(note.into());
+        self
+    }
+
+    pub fn with_help(mut self, help: impl Into<String>) -> Self {
+        self.help = Some(help.into());
+        self
+    }
+
+    pub fn is_error(&self) -> bool {
+        self.severity == Severity::Error
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.code {
+            Some(code) => write!(
+                f,
+                "{}: {}[{}]: {}",
+                self.span(),
+                self.severity,
+                code,
+                self.message
+            ),
+            None => write!(f, "{}: {}: {}", self.span(), self.severity, self.message),
+        }
+    }
+}
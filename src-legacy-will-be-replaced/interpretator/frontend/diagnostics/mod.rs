@@ -0,0 +1,4 @@
+pub mod error;
+pub mod render;
+pub mod reporter;
+pub mod span;
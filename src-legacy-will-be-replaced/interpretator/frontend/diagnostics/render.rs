@@ -0,0 +1,289 @@
+use crate::interpretator::frontend::diagnostics::error::Diagnostic;
+use crate::interpretator::frontend::diagnostics::span::Span;
+use serde::Serialize;
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Ширина табуляции при выводе строк исходника.
+const TAB_WIDTH: usize = 4;
+
+/// Печатает диагностики в стиле rustc: заголовок, место в файле и строки исходника
+/// с подчёркнутыми участками. Столбцы считаются в графемах, поэтому кириллица,
+/// `°C`, `⋅` и составные символы подчёркиваются ровно под собой.
+pub struct Renderer<'a> {
+    file_name: &'a str,
+    /// Начало каждой строки в байтах и её текст без перевода строки.
+    lines: Vec<(usize, &'a str)>,
+    /// Другие файлы, на которые указывают метки: схема, подключённый документ.
+    others: Vec<Renderer<'a>>,
+}
+
+/// Подчёркивание внутри одной строки исходника.
+struct Underline {
+    line: usize,
+    /// Столбцы на экране, с нуля
+    from: usize,
+    to: usize,
+    marker: char,
+    message: Option<String>,
+}
+
+#[derive(Serialize)]
+struct JsonReport<'d> {
+    file: &'d str,
+    diagnostics: Vec<JsonDiagnostic<'d>>,
+}
+
+/// Диагностика для редактора: исходные данные и готовый текст. Столбцы в участках
+/// уже считаются в графемах.
+#[derive(Serialize)]
+struct JsonDiagnostic<'d> {
+    #[serde(flatten)]
+    diagnostic: &'d Diagnostic,
+    rendered: String,
+}
+
+impl<'a> Renderer<'a> {
+    pub fn new(source: &'a str, file_name: &'a str) -> Self {
+        let mut offset = 0;
+        let lines = source
+            .split('\n')
+            .map(|line| {
+                let start = offset;
+                offset += line.len() + 1;
+                (start, line.strip_suffix('\r').unwrap_or(line))
+            })
+            .collect();
+        Renderer {
+            file_name,
+            lines,
+            others: Vec::new(),
+        }
+    }
+
+    /// Текст другого файла для меток с `source`: без него такая метка выводится
+    /// только местом, `::: schema.slacon:3:1`.
+    pub fn with_source(mut self, source: &'a str, file_name: &'a str) -> Self {
+        self.others.push(Renderer::new(source, file_name));
+        self
+    }
+
+    /// Все диагностики подряд, через пустую строку.
+    pub fn render_all(&self, diagnostics: &[Diagnostic]) -> String {
+        diagnostics
+            .iter()
+            .map(|diagnostic| self.render(diagnostic))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    pub fn render(&self, diagnostic: &Diagnostic) -> String {
+        let mut out = String::new();
+        match &diagnostic.code {
+            Some(code) => out.push_str(&format!(
+                "{}[{}]: {}\n",
+                diagnostic.severity, code, diagnostic.message
+            )),
+            None => out.push_str(&format!(
+                "{}: {}\n",
+                diagnostic.severity, diagnostic.message
+            )),
+        }
+
+        let (local, foreign): (Vec<_>, Vec<_>) = diagnostic.secondary.iter().partition(|label| {
+            label
+                .source
+                .as_deref()
+                .is_none_or(|source| source == self.file_name)
+        });
+        let mut underlines =
+            vec![self.underline(diagnostic.primary.span, '^', &diagnostic.primary.message)];
+        for label in local {
+            underlines.push(self.underline(label.span, '-', &label.message));
+        }
+        // Метки в других файлах: файл, если его текст известен, и подчёркивание в нём
+        let foreign: Vec<_> = foreign
+            .into_iter()
+            .map(|label| {
+                let source = label.source.as_deref().unwrap_or_default();
+                let other = self.others.iter().find(|other| other.file_name == source);
+                let underline = other.map(|other| other.underline(label.span, '-', &label.message));
+                (label, other, underline)
+            })
+            .collect();
+
+        let widest = underlines
+            .iter()
+            .chain(
+                foreign
+                    .iter()
+                    .filter_map(|(_, _, underline)| underline.as_ref()),
+            )
+            .map(|underline| underline.line)
+            .max();
+        let gutter = widest.map_or(1, |line| line.to_string().len());
+        let pad = " ".repeat(gutter);
+        out.push_str(&format!(
+            "{}--> {}:{}\n",
+            pad,
+            self.file_name,
+            diagnostic.span()
+        ));
+        out.push_str(&format!("{} |\n", pad));
+        self.write_lines(&mut out, &underlines, gutter);
+
+        for (label, other, underline) in foreign {
+            let source = label.source.as_deref().unwrap_or_default();
+            match (other, underline) {
+                (Some(other), Some(underline)) => {
+                    out.push_str(&format!("{}::: {}:{}\n", pad, source, label.span));
+                    out.push_str(&format!("{} |\n", pad));
+                    other.write_lines(&mut out, &[underline], gutter);
+                }
+                _ => {
+                    let location = format!("{}::: {}:{}", pad, source, label.span);
+                    match &label.message {
+                        Some(message) => out.push_str(&format!("{} {}\n", location, message)),
+                        None => out.push_str(&format!("{}\n", location)),
+                    }
+                }
+            }
+        }
+
+        if !diagnostic.notes.is_empty() || diagnostic.help.is_some() {
+            out.push_str(&format!("{} |\n", pad));
+        }
+        for note in &diagnostic.notes {
+            out.push_str(&format!("{} = примечание: {}\n", pad, note));
+        }
+        if let Some(help) = &diagnostic.help {
+            out.push_str(&format!("{} = помощь: {}\n", pad, help));
+        }
+        out
+    }
+
+    /// Строки исходника с подчёркиваниями под ними; между несмежными строками — `...`.
+    fn write_lines(&self, out: &mut String, underlines: &[Underline], gutter: usize) {
+        let pad = " ".repeat(gutter);
+        let mut shown_lines: Vec<usize> = underlines.iter().map(|u| u.line).collect();
+        shown_lines.sort_unstable();
+        shown_lines.dedup();
+
+        let mut previous: Option<usize> = None;
+        for line in shown_lines {
+            if previous.is_some_and(|previous| line > previous + 1) {
+                out.push_str("...\n");
+            }
+            previous = Some(line);
+            out.push_str(&format!(
+                "{:>gutter$} | {}\n",
+                line,
+                self.display_line(line)
+            ));
+            for underline in underlines.iter().filter(|u| u.line == line) {
+                let marks = underline
+                    .marker
+                    .to_string()
+                    .repeat(underline.to - underline.from);
+                let text = match &underline.message {
+                    Some(message) => format!("{}{} {}", " ".repeat(underline.from), marks, message),
+                    None => format!("{}{}", " ".repeat(underline.from), marks),
+                };
+                out.push_str(&format!("{} | {}\n", pad, text));
+            }
+        }
+    }
+
+    /// Те же данные в JSON для интеграции с редакторами.
+    pub fn render_json(&self, diagnostics: &[Diagnostic]) -> String {
+        let report = JsonReport {
+            file: self.file_name,
+            diagnostics: diagnostics
+                .iter()
+                .map(|diagnostic| JsonDiagnostic {
+                    diagnostic,
+                    rendered: self.render(diagnostic),
+                })
+                .collect(),
+        };
+        serde_json::to_string_pretty(&report).expect("диагностики всегда сериализуемы")
+    }
+
+    // --- Геометрия строк ---
+
+    /// Номер строки (с 1) и её начало для байтового смещения.
+    fn locate(&self, offset: usize) -> (usize, usize) {
+        let index = self
+            .lines
+            .partition_point(|(start, _)| *start <= offset)
+            .saturating_sub(1);
+        (index + 1, self.lines[index].0)
+    }
+
+    fn line_text(&self, line: usize) -> &'a str {
+        self.lines.get(line - 1).map_or("", |(_, text)| text)
+    }
+
+    /// Экранный столбец (с нуля) для байта внутри строки: графема — один столбец,
+    /// табуляция — `TAB_WIDTH`.
+    fn display_column(text: &str, byte: usize) -> usize {
+        text.grapheme_indices(true)
+            .take_while(|(index, _)| *index < byte)
+            .map(|(_, grapheme)| if grapheme == "\t" { TAB_WIDTH } else { 1 })
+            .sum()
+    }
+
+    fn display_line(&self, line: usize) -> String {
+        self.line_text(line).replace('\t', &" ".repeat(TAB_WIDTH))
+    }
+
+    /// Подчёркивание участка в строке, где он начинается. Многострочный участок
+    /// подчёркивается до конца первой строки.
+    fn underline(&self, span: Span, marker: char, message: &Option<String>) -> Underline {
+        let (line, start) = self.locate(span.start.offset);
+        let text = self.line_text(line);
+        let from_byte = (span.start.offset - start).min(text.len());
+        let to_byte = span
+            .end
+            .offset
+            .saturating_sub(start)
+            .clamp(from_byte, text.len());
+        let from = Self::display_column(text, from_byte);
+        let to = Self::display_column(text, to_byte).max(from + 1);
+        Underline {
+            line,
+            from,
+            to,
+            marker,
+            message: message.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interpretator::frontend::diagnostics::reporter::Reporter;
+    use crate::interpretator::frontend::parser::parser::parse_source;
+
+    #[test]
+    fn test_render_counts_graphemes() {
+        // «е» с комбинируемым ударением — два символа, но одна графема
+        let source = "\tключ \"е\u{301}\" ¤\n";
+        let mut reporter = Reporter::new();
+        parse_source(source, &mut reporter);
+        let diagnostic = reporter.diagnostics()[0]
+            .clone()
+            .with_help("удалите символ");
+        let rendered = Renderer::new(source, "пример.lacon").render(&diagnostic);
+        assert_eq!(
+            rendered,
+            "ошибка[E0001]: неожиданный символ «¤»\n\
+             \x20--> пример.lacon:1:11\n\
+             \x20 |\n\
+             1 |     ключ \"е\u{301}\" ¤\n\
+             \x20 |              ^ символ не входит в синтаксис LaCoN\n\
+             \x20 |\n\
+             \x20 = помощь: удалите символ\n"
+        );
+    }
+}
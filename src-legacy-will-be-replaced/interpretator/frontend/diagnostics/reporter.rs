@@ -0,0 +1,35 @@
+use crate::interpretator::frontend::diagnostics::error::Diagnostic;
+
+/// Накопитель диагностик. Лексер и парсер не останавливаются на первой ошибке,
+/// а сообщают о каждой сюда; сообщения хранятся в порядке исходного текста.
+#[derive(Debug, Clone, Default)]
+pub struct Reporter {
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Reporter {
+    pub fn new() -> Self {
+        Reporter::default()
+    }
+
+    pub fn report(&mut self, diagnostic: impl Into<Diagnostic>) {
+        let diagnostic = diagnostic.into();
+        let index = self
+            .diagnostics
+            .partition_point(|existing| existing.span().start <= diagnostic.span().start);
+        self.diagnostics.insert(index, diagnostic);
+    }
+
+    /// Есть ли среди сообщений ошибки; одни предупреждения не мешают работе.
+    pub fn has_errors(&self) -> bool {
+        self.diagnostics.iter().any(Diagnostic::is_error)
+    }
+
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+
+    pub fn into_diagnostics(self) -> Vec<Diagnostic> {
+        self.diagnostics
+    }
+}
@@ -0,0 +1,44 @@
+use crate::interpretator::frontend::lexer::position::Position;
+use serde::Serialize;
+use std::fmt;
+
+/// Участок исходного текста: от `start` включительно до `end` не включительно.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize)]
+pub struct Span {
+    pub start: Position,
+    pub end: Position,
+}
+
+impl Span {
+    pub fn new(start: Position, end: Position) -> Self {
+        Span { start, end }
+    }
+
+    /// Пустой участок в указанной точке.
+    pub fn point(at: Position) -> Self {
+        Span { start: at, end: at }
+    }
+
+    /// Объединяет два участка в один, охватывающий оба.
+    pub fn to(self, other: Span) -> Span {
+        Span {
+            start: self.start.min(other.start),
+            end: self.end.max(other.end),
+        }
+    }
+
+    /// Длина участка в байтах.
+    pub fn len(&self) -> usize {
+        self.end.offset.saturating_sub(self.start.offset)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl fmt::Display for Span {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.start)
+    }
+}
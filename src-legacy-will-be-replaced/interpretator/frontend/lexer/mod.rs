@@ -0,0 +1,3 @@
+pub mod position;
+pub mod token;
+pub mod tokenizer;
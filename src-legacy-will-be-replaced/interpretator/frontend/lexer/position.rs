@@ -0,0 +1,55 @@
+use serde::Serialize;
+use std::fmt;
+use unicode_segmentation::GraphemeCursor;
+
+/// Позиция в исходном тексте.
+/// Хранит как байтовое смещение (для срезов строки), так и строку/столбец (для человека).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+pub struct Position {
+    /// Смещение в байтах от начала источника.
+    pub offset: usize,
+    /// Номер строки, начиная с 1.
+    pub line: usize,
+    /// Номер столбца в графемах, начиная с 1: «е» с комбинируемым ударением
+    /// и флаг из двух региональных символов занимают по одному столбцу.
+    pub column: usize,
+}
+
+impl Position {
+    /// Позиция самого начала файла.
+    pub fn start() -> Self {
+        Position {
+            offset: 0,
+            line: 1,
+            column: 1,
+        }
+    }
+
+    /// Сдвигает позицию за символ `ch`, стоящий в `source` на текущем смещении.
+    /// Столбец растёт, только если символ начинает новую графему.
+    pub fn advance(&mut self, ch: char, source: &str) {
+        let at = self.offset;
+        self.offset += ch.len_utf8();
+        if ch == '\n' {
+            self.line += 1;
+            self.column = 1;
+        } else if GraphemeCursor::new(at, source.len(), true)
+            .is_boundary(source, 0)
+            .unwrap_or(true)
+        {
+            self.column += 1;
+        }
+    }
+}
+
+impl Default for Position {
+    fn default() -> Self {
+        Position::start()
+    }
+}
+
+impl fmt::Display for Position {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
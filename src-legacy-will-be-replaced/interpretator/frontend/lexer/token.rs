@@ -0,0 +1,402 @@
+use crate::interpretator::frontend::diagnostics::span::Span;
+use std::fmt;
+
+/// Вид кавычек строкового литерала: "" '' ``
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuoteStyle {
+    Double,
+    Single,
+    Backtick,
+}
+
+impl QuoteStyle {
+    pub fn from_char(ch: char) -> Option<Self> {
+        match ch {
+            '"' => Some(QuoteStyle::Double),
+            '\'' => Some(QuoteStyle::Single),
+            '`' => Some(QuoteStyle::Backtick),
+            _ => None,
+        }
+    }
+
+    pub fn as_char(&self) -> char {
+        match self {
+            QuoteStyle::Double => '"',
+            QuoteStyle::Single => '\'',
+            QuoteStyle::Backtick => '`',
+        }
+    }
+}
+
+/// Часть строкового литерала: обычный текст или интерполяция `${...}`.
+/// Интерполяция лексируется сразу, чтобы парсер получил готовые токены выражения.
+#[derive(Debug, Clone, PartialEq)]
+pub enum StringPart {
+    Text(String),
+    Interpolation(Vec<Token>),
+    /// `\u{${code}}` — символ по вычисляемому коду
+    CodePoint(Vec<Token>),
+}
+
+/// Зарезервированные слова. Остальные «ключевые» слова (index, value, spread…)
+/// на уровне синтаксиса не определены и остаются идентификаторами.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Keyword {
+    Const,
+    Let,
+    Local,
+    Global,
+    Static,
+    Public,
+    Private,
+    Strict,
+    Async,
+    Function,
+    Procedure,
+    Iterator,
+    Class,
+    Structure,
+    Extends,
+    If,
+    Elif,
+    Else,
+    For,
+    In,
+    Loop,
+    While,
+    Until,
+    Do,
+    Match,
+    Try,
+    Catch,
+    Finally,
+    As,
+    Return,
+    Break,
+    Continue,
+    Cancel,
+    Throw,
+    Yield,
+    New,
+    Is,
+    And,
+    Or,
+    Not,
+    True,
+    False,
+    None,
+    Nil,
+    Undefined,
+    Auto,
+}
+
+impl Keyword {
+    pub fn lookup(word: &str) -> Option<Keyword> {
+        let keyword = match word {
+            "const" => Keyword::Const,
+            "let" => Keyword::Let,
+            "local" => Keyword::Local,
+            "global" => Keyword::Global,
+            "static" => Keyword::Static,
+            "public" => Keyword::Public,
+            "private" => Keyword::Private,
+            "strict" => Keyword::Strict,
+            "async" => Keyword::Async,
+            "function" => Keyword::Function,
+            "procedure" => Keyword::Procedure,
+            "iterator" => Keyword::Iterator,
+            "class" => Keyword::Class,
+            "structure" => Keyword::Structure,
+            "extends" => Keyword::Extends,
+            "if" => Keyword::If,
+            "elif" => Keyword::Elif,
+            "else" => Keyword::Else,
+            "for" => Keyword::For,
+            "in" => Keyword::In,
+            "loop" => Keyword::Loop,
+            "while" => Keyword::While,
+            "until" => Keyword::Until,
+            "do" => Keyword::Do,
+            "match" => Keyword::Match,
+            "try" => Keyword::Try,
+            "catch" => Keyword::Catch,
+            "finally" => Keyword::Finally,
+            "as" => Keyword::As,
+            "return" => Keyword::Return,
+            "break" => Keyword::Break,
+            "continue" => Keyword::Continue,
+            "cancel" => Keyword::Cancel,
+            "throw" => Keyword::Throw,
+            "yield" => Keyword::Yield,
+            "new" => Keyword::New,
+            "is" => Keyword::Is,
+            "and" => Keyword::And,
+            "or" => Keyword::Or,
+            "not" => Keyword::Not,
+            "true" => Keyword::True,
+            "false" => Keyword::False,
+            "none" => Keyword::None,
+            "nil" => Keyword::Nil,
+            "undefined" => Keyword::Undefined,
+            "auto" => Keyword::Auto,
+            _ => return None,
+        };
+        Some(keyword)
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Keyword::Const => "const",
+            Keyword::Let => "let",
+            Keyword::Local => "local",
+            Keyword::Global => "global",
+            Keyword::Static => "static",
+            Keyword::Public => "public",
+            Keyword::Private => "private",
+            Keyword::Strict => "strict",
+            Keyword::Async => "async",
+            Keyword::Function => "function",
+            Keyword::Procedure => "procedure",
+            Keyword::Iterator => "iterator",
+            Keyword::Class => "class",
+            Keyword::Structure => "structure",
+            Keyword::Extends => "extends",
+            Keyword::If => "if",
+            Keyword::Elif => "elif",
+            Keyword::Else => "else",
+            Keyword::For => "for",
+            Keyword::In => "in",
+            Keyword::Loop => "loop",
+            Keyword::While => "while",
+            Keyword::Until => "until",
+            Keyword::Do => "do",
+            Keyword::Match => "match",
+            Keyword::Try => "try",
+            Keyword::Catch => "catch",
+            Keyword::Finally => "finally",
+            Keyword::As => "as",
+            Keyword::Return => "return",
+            Keyword::Break => "break",
+            Keyword::Continue => "continue",
+            Keyword::Cancel => "cancel",
+            Keyword::Throw => "throw",
+            Keyword::Yield => "yield",
+            Keyword::New => "new",
+            Keyword::Is => "is",
+            Keyword::And => "and",
+            Keyword::Or => "or",
+            Keyword::Not => "not",
+            Keyword::True => "true",
+            Keyword::False => "false",
+            Keyword::None => "none",
+            Keyword::Nil => "nil",
+            Keyword::Undefined => "undefined",
+            Keyword::Auto => "auto",
+        }
+    }
+}
+
+/// Вид токена.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TokenKind {
+    /// Имя: может содержать буквы любого языка, цифры, «_» и «-» (кроме начала).
+    Identifier(String),
+    Keyword(Keyword),
+    /// Числовой литерал с необязательной единицей измерения: `15`, `2.5m/s2`, `0x1E`.
+    Number {
+        literal: String,
+        unit: Option<String>,
+    },
+    String {
+        quote: QuoteStyle,
+        parts: Vec<StringPart>,
+    },
+    /// Заголовок документа: `[Mode:Data]`, `[Document:Array]`, `[Marker:Output<Dictionary> as JSON]`.
+    Header {
+        name: String,
+        body: String,
+    },
+    /// Имя секции `§name`; пустая строка — анонимная секция `§ { }`.
+    Section(String),
+    /// Дата и время ISO 8601: `2024-03-15`, `2024-03-15T10:30:00+03:00`.
+    DateTime(String),
+    /// Цвет `#fa8246`: 3, 4, 6 или 8 шестнадцатеричных цифр без `#`.
+    Color(String),
+    /// Начало списка атрибутов `@[`.
+    AttributeOpen,
+    At,
+    Dollar,
+    Hash,
+
+    Plus,          // +
+    Minus,         // -
+    Star,          // * ⋅
+    StarStar,      // **
+    Slash,         // /
+    SlashSlash,    // //
+    Percent,       // %
+    PlusPlus,      // ++
+    MinusMinus,    // --
+    Assign,        // =
+    PlusAssign,    // +=
+    MinusAssign,   // -=
+    StarAssign,    // *=
+    SlashAssign,   // /=
+    PercentAssign, // %=
+    DotAssign,     // .=
+    AmpAssign,     // &=
+    EqEq,          // ==
+    EqEqEq,        // ===
+    NotEq,         // !=
+    NotEqEq,       // !==
+    Less,          // <
+    Greater,       // >
+    LessEq,        // <=
+    GreaterEq,     // >=
+    AndAnd,        // &&
+    OrOr,          // ||
+    Bang,          // !
+    TildeEq,       // ~=
+    Amp,           // &
+    Pipe,          // |
+    Pipeline,      // |>
+    Arrow,         // -> →
+    FatArrow,      // =>
+    Monus,         // ∸ .-
+    CartesianSum,  // ∔
+    Identical,     // ≣
+    FloorOpen,     // ⌊
+    FloorClose,    // ⌋
+    CeilOpen,      // ⌈
+    CeilClose,     // ⌉
+    Delta,         // Δ
+    Question,      // ?
+    QuestionDot,   // ?.
+    Dot,           // .
+    DotDot,        // ..
+    Ellipsis,      // ...
+    Colon,         // :
+    ColonColon,    // ::
+    Comma,         // ,
+    Semicolon,     // ;
+    LParen,
+    RParen,
+    LBracket,
+    RBracket,
+    LBrace,
+    RBrace,
+
+    /// Перевод строки. Подряд идущие пустые строки схлопываются в один токен.
+    Newline,
+    /// Символ, который лексер не распознал; ошибка о нём уже сообщена.
+    Error,
+    Eof,
+}
+
+impl TokenKind {
+    /// Текстовое представление для сообщений об ошибках.
+    pub fn describe(&self) -> String {
+        let symbol = match self {
+            TokenKind::Identifier(name) => return format!("имя «{}»", name),
+            TokenKind::Keyword(keyword) => return format!("ключевое слово «{}»", keyword.as_str()),
+            TokenKind::Number { literal, unit } => {
+                return format!("число «{}{}»", literal, unit.as_deref().unwrap_or(""))
+            }
+            TokenKind::String { .. } => return "строка".to_string(),
+            TokenKind::Header { name, body } => return format!("заголовок «[{}:{}]»", name, body),
+            TokenKind::Section(name) => return format!("секция «§{}»", name),
+            TokenKind::DateTime(text) => return format!("дата «{}»", text),
+            TokenKind::Color(digits) => return format!("цвет «#{}»", digits),
+            TokenKind::Newline => return "перевод строки".to_string(),
+            TokenKind::Error => return "нераспознанный символ".to_string(),
+            TokenKind::Eof => return "конец файла".to_string(),
+            TokenKind::AttributeOpen => "@[",
+            TokenKind::At => "@",
+            TokenKind::Dollar => "$",
+            TokenKind::Hash => "#",
+            TokenKind::Plus => "+",
+            TokenKind::Minus => "-",
+            TokenKind::Star => "*",
+            TokenKind::StarStar => "**",
+            TokenKind::Slash => "/",
+            TokenKind::SlashSlash => "//",
+            TokenKind::Percent => "%",
+            TokenKind::PlusPlus => "++",
+            TokenKind::MinusMinus => "--",
+            TokenKind::Assign => "=",
+            TokenKind::PlusAssign => "+=",
+            TokenKind::MinusAssign => "-=",
+            TokenKind::StarAssign => "*=",
+            TokenKind::SlashAssign => "/=",
+            TokenKind::PercentAssign => "%=",
+            TokenKind::DotAssign => ".=",
+            TokenKind::AmpAssign => "&=",
+            TokenKind::EqEq => "==",
+            TokenKind::EqEqEq => "===",
+            TokenKind::NotEq => "!=",
+            TokenKind::NotEqEq => "!==",
+            TokenKind::Less => "<",
+            TokenKind::Greater => ">",
+            TokenKind::LessEq => "<=",
+            TokenKind::GreaterEq => ">=",
+            TokenKind::AndAnd => "&&",
+            TokenKind::OrOr => "||",
+            TokenKind::Bang => "!",
+            TokenKind::TildeEq => "~=",
+            TokenKind::Amp => "&",
+            TokenKind::Pipe => "|",
+            TokenKind::Pipeline => "|>",
+            TokenKind::Arrow => "->",
+            TokenKind::FatArrow => "=>",
+            TokenKind::Monus => "∸",
+            TokenKind::CartesianSum => "∔",
+            TokenKind::Identical => "≣",
+            TokenKind::FloorOpen => "⌊",
+            TokenKind::FloorClose => "⌋",
+            TokenKind::CeilOpen => "⌈",
+            TokenKind::CeilClose => "⌉",
+            TokenKind::Delta => "Δ",
+            TokenKind::Question => "?",
+            TokenKind::QuestionDot => "?.",
+            TokenKind::Dot => ".",
+            TokenKind::DotDot => "..",
+            TokenKind::Ellipsis => "...",
+            TokenKind::Colon => ":",
+            TokenKind::ColonColon => "::",
+            TokenKind::Comma => ",",
+            TokenKind::Semicolon => ";",
+            TokenKind::LParen => "(",
+            TokenKind::RParen => ")",
+            TokenKind::LBracket => "[",
+            TokenKind::RBracket => "]",
+            TokenKind::LBrace => "{",
+            TokenKind::RBrace => "}",
+        };
+        format!("«{}»", symbol)
+    }
+}
+
+/// Токен вместе с участком исходного текста, из которого он получен.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Token {
+    pub kind: TokenKind,
+    pub span: Span,
+}
+
+impl Token {
+    pub fn new(kind: TokenKind, span: Span) -> Self {
+        Token { kind, span }
+    }
+
+    /// Стоит ли токен вплотную после другого (без пробелов между ними).
+    /// Нужно парсеру, чтобы отличать `key<Type>` от `a < b` и `call(x)` от `key (x)`.
+    pub fn is_adjacent_to(&self, previous: &Token) -> bool {
+        previous.span.end.offset == self.span.start.offset
+    }
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.kind.describe())
+    }
+}
@@ -0,0 +1,936 @@
+use crate::interpretator::frontend::diagnostics::error::{Diagnostic, Label};
+use crate::interpretator::frontend::diagnostics::span::Span;
+use crate::interpretator::frontend::lexer::position::Position;
+use crate::interpretator::frontend::lexer::token::{
+    Keyword, QuoteStyle, StringPart, Token, TokenKind,
+};
+use crate::interpretator::prototypes::types::primitive::number::hexadecimal::Radix;
+use thiserror::Error;
+
+/// Ошибка лексического анализа.
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum LexError {
+    #[error("неожиданный символ «{ch}»")]
+    UnexpectedChar { ch: char, span: Span },
+    #[error("строка не закрыта кавычкой «{quote}»")]
+    UnterminatedString { quote: char, span: Span },
+    #[error("блочный комментарий не закрыт «*/»")]
+    UnterminatedComment { span: Span },
+    #[error("интерполяция «${{...}}» не закрыта «}}»")]
+    UnterminatedInterpolation { span: Span },
+    #[error("заголовок «[{name}:...]» не закрыт «]»")]
+    UnterminatedHeader { name: String, span: Span },
+    #[error("«\\u{{{text}}}» — не код символа Unicode")]
+    InvalidCodePoint { text: String, span: Span },
+}
+
+impl LexError {
+    pub fn span(&self) -> Span {
+        match self {
+            LexError::UnexpectedChar { span, .. }
+            | LexError::UnterminatedString { span, .. }
+            | LexError::UnterminatedComment { span }
+            | LexError::UnterminatedInterpolation { span }
+            | LexError::UnterminatedHeader { span, .. }
+            | LexError::InvalidCodePoint { span, .. } => *span,
+        }
+    }
+
+    /// Код ошибки для диагностик.
+    pub fn code(&self) -> &'static str {
+        match self {
+            LexError::UnexpectedChar { .. } => "E0001",
+            LexError::UnterminatedString { .. } => "E0002",
+            LexError::UnterminatedComment { .. } => "E0003",
+            LexError::UnterminatedInterpolation { .. } => "E0004",
+            LexError::UnterminatedHeader { .. } => "E0005",
+            LexError::InvalidCodePoint { .. } => "E0006",
+        }
+    }
+}
+
+impl From<LexError> for Diagnostic {
+    fn from(error: LexError) -> Self {
+        let span = error.span();
+        let diagnostic = Diagnostic::error(error.to_string(), span).with_code(error.code());
+        // У незакрытых конструкций подчёркивается начало, а не весь хвост файла
+        let opening = Span::point(span.start);
+        match error {
+            LexError::UnexpectedChar { ch: '\\', .. } => diagnostic
+                .with_primary_message("символ не входит в синтаксис LaCoN")
+                .with_help("экранирование «\\» допустимо только внутри строк"),
+            LexError::UnexpectedChar { .. } => {
+                diagnostic.with_primary_message("символ не входит в синтаксис LaCoN")
+            }
+            LexError::UnterminatedString { quote, .. } => Diagnostic {
+                primary: Label::new(opening, "строка начинается здесь"),
+                ..diagnostic
+            }
+            .with_help(format!("добавьте закрывающую кавычку «{}»", quote)),
+            LexError::UnterminatedComment { .. } => Diagnostic {
+                primary: Label::new(opening, "комментарий начинается здесь"),
+                ..diagnostic
+            }
+            .with_help("добавьте «*/» в конце комментария"),
+            LexError::UnterminatedInterpolation { .. } => Diagnostic {
+                primary: Label::new(opening, "интерполяция начинается здесь"),
+                ..diagnostic
+            },
+            LexError::UnterminatedHeader { .. } => Diagnostic {
+                primary: Label::new(opening, "заголовок начинается здесь"),
+                ..diagnostic
+            }
+            .with_note("заголовок документа должен умещаться в одной строке"),
+            LexError::InvalidCodePoint { .. } => {
+                diagnostic.with_help("код записывается в шестнадцатеричном виде: «\\u{1F031}»")
+            }
+        }
+    }
+}
+
+/// Имена заголовков документа, которые лексируются целиком: `[Mode:Data]`.
+const HEADER_NAMES: [&str; 3] = ["Mode", "Marker", "Document"];
+
+/// Лексер LaCoN: превращает исходный текст в поток токенов с позициями.
+pub struct Tokenizer<'a> {
+    source: &'a str,
+    position: Position,
+    /// Ошибки не прерывают разбор: лексер сообщает о них и продолжает с того же места.
+    errors: Vec<LexError>,
+}
+
+impl<'a> Tokenizer<'a> {
+    pub fn new(source: &'a str) -> Self {
+        Tokenizer {
+            source,
+            position: Position::start(),
+            errors: Vec::new(),
+        }
+    }
+
+    /// Разбирает весь источник, останавливаясь на первой ошибке. Последний токен всегда `Eof`.
+    pub fn tokenize(self) -> Result<Vec<Token>, LexError> {
+        let (tokens, mut errors) = self.tokenize_recovering();
+        if errors.is_empty() {
+            Ok(tokens)
+        } else {
+            Err(errors.remove(0))
+        }
+    }
+
+    /// Разбирает весь источник, собирая все ошибки.
+    /// Нераспознанные символы становятся токенами `Error`, незакрытые строки,
+    /// заголовки и интерполяции считаются закрытыми в конце строки или файла.
+    pub fn tokenize_recovering(mut self) -> (Vec<Token>, Vec<LexError>) {
+        let mut tokens: Vec<Token> = Vec::new();
+        loop {
+            let token = self.next_token();
+            let is_eof = token.kind == TokenKind::Eof;
+            // Пустые строки и строки из одних комментариев не несут смысла
+            let redundant_newline = token.kind == TokenKind::Newline
+                && tokens
+                    .last()
+                    .is_none_or(|last| last.kind == TokenKind::Newline);
+            if !redundant_newline {
+                tokens.push(token);
+            }
+            if is_eof {
+                break;
+            }
+        }
+        (tokens, self.errors)
+    }
+
+    // --- Навигация по источнику ---
+
+    fn rest(&self) -> &'a str {
+        &self.source[self.position.offset..]
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn peek_nth(&self, n: usize) -> Option<char> {
+        self.rest().chars().nth(n)
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let ch = self.peek()?;
+        self.position.advance(ch, self.source);
+        Some(ch)
+    }
+
+    fn bump_str(&mut self, text: &str) {
+        for ch in text.chars() {
+            self.position.advance(ch, self.source);
+        }
+    }
+
+    fn starts_with(&self, text: &str) -> bool {
+        self.rest().starts_with(text)
+    }
+
+    fn span_from(&self, start: Position) -> Span {
+        Span::new(start, self.position)
+    }
+
+    // --- Пропуск пробелов и комментариев ---
+
+    fn skip_trivia(&mut self) {
+        loop {
+            match self.peek() {
+                Some(' ') | Some('\t') | Some('\r') | Some('\u{feff}') => {
+                    self.bump();
+                }
+                // Строчные комментарии: /// и /|\
+                _ if self.starts_with("///") || self.starts_with("/|\\") => {
+                    while let Some(ch) = self.peek() {
+                        if ch == '\n' {
+                            break;
+                        }
+                        self.bump();
+                    }
+                }
+                _ if self.starts_with("/*") => {
+                    let start = self.position;
+                    self.bump_str("/*");
+                    loop {
+                        if self.starts_with("*/") {
+                            self.bump_str("*/");
+                            break;
+                        }
+                        if self.bump().is_none() {
+                            self.errors.push(LexError::UnterminatedComment {
+                                span: self.span_from(start),
+                            });
+                            return;
+                        }
+                    }
+                }
+                _ => return,
+            }
+        }
+    }
+
+    // --- Основной цикл ---
+
+    fn next_token(&mut self) -> Token {
+        self.skip_trivia();
+        let start = self.position;
+
+        let ch = match self.peek() {
+            Some(ch) => ch,
+            None => return Token::new(TokenKind::Eof, self.span_from(start)),
+        };
+
+        if ch == '\n' {
+            self.bump();
+            return Token::new(TokenKind::Newline, self.span_from(start));
+        }
+        if ch.is_ascii_digit() {
+            return self.lex_number(start);
+        }
+        if let Some(quote) = QuoteStyle::from_char(ch) {
+            return self.lex_string(quote, start);
+        }
+        // `\u{1F031}` вне кавычек — строка из одного символа
+        if self.rest().starts_with("\\u{") {
+            let mut parts = Vec::new();
+            let mut text = String::new();
+            self.lex_code_point(&mut parts, &mut text);
+            if !text.is_empty() || parts.is_empty() {
+                parts.push(StringPart::Text(text));
+            }
+            let kind = TokenKind::String {
+                quote: QuoteStyle::Double,
+                parts,
+            };
+            return Token::new(kind, self.span_from(start));
+        }
+        if ch == '[' {
+            if let Some(header) = self.try_lex_header(start) {
+                return header;
+            }
+        }
+        // «Δ» — алфавитный символ, но отдельно стоящий или перед числом (`Δ5K`)
+        // он обозначает разность
+        let in_name = |next: char| is_identifier_continue(next) && !next.is_ascii_digit();
+        if ch == 'Δ' && !self.peek_nth(1).is_some_and(in_name) {
+            self.bump();
+            return Token::new(TokenKind::Delta, self.span_from(start));
+        }
+        if is_identifier_start(ch) {
+            return self.lex_identifier(start);
+        }
+        if ch == '§' {
+            self.bump();
+            let name = self.read_identifier_tail();
+            return Token::new(TokenKind::Section(name), self.span_from(start));
+        }
+        if ch == '#' {
+            if let Some(color) = self.try_lex_color(start) {
+                return color;
+            }
+        }
+
+        match self.lex_operator() {
+            Some(kind) => Token::new(kind, self.span_from(start)),
+            None => {
+                self.bump();
+                let span = self.span_from(start);
+                self.errors.push(LexError::UnexpectedChar { ch, span });
+                Token::new(TokenKind::Error, span)
+            }
+        }
+    }
+
+    // --- Имена ---
+
+    /// Читает продолжение имени. «-» входит в имя, только если за ним снова идёт символ имени
+    /// или интерполяция, поэтому `key-1` и `digit-${name}` — одно имя, а `a -> b` или `x--` — нет.
+    fn read_identifier_tail(&mut self) -> String {
+        let mut name = String::new();
+        while let Some(ch) = self.peek() {
+            let hyphen_inside = ch == '-'
+                && (self.peek_nth(1).is_some_and(is_identifier_continue)
+                    || self.rest()[1..].starts_with("${"));
+            if is_identifier_continue(ch) || hyphen_inside {
+                name.push(ch);
+                self.bump();
+            } else {
+                break;
+            }
+        }
+        name
+    }
+
+    fn lex_identifier(&mut self, start: Position) -> Token {
+        let name = self.read_identifier_tail();
+        if self.rest().starts_with("${") {
+            return self.lex_interpolated_name(name, start);
+        }
+        let kind = match Keyword::lookup(&name) {
+            Some(keyword) => TokenKind::Keyword(keyword),
+            None => TokenKind::Identifier(name),
+        };
+        Token::new(kind, self.span_from(start))
+    }
+
+    /// `digit-${name}`, `hel_${letter-case}_let_a_alpha` — имя с интерполяцией. Как и `\u{...}`
+    /// вне кавычек, оно становится строкой: ключом, значением или именем поля после точки.
+    fn lex_interpolated_name(&mut self, head: String, start: Position) -> Token {
+        let mut parts = Vec::new();
+        let mut text = head;
+        while self.rest().starts_with("${") {
+            if !text.is_empty() {
+                parts.push(StringPart::Text(std::mem::take(&mut text)));
+            }
+            let interpolation_start = self.position;
+            self.bump_str("${");
+            let tokens = self.lex_interpolation(interpolation_start);
+            parts.push(StringPart::Interpolation(tokens));
+            text = self.read_identifier_tail();
+        }
+        if !text.is_empty() {
+            parts.push(StringPart::Text(text));
+        }
+        let kind = TokenKind::String {
+            quote: QuoteStyle::Double,
+            parts,
+        };
+        Token::new(kind, self.span_from(start))
+    }
+
+    // --- Числа ---
+
+    fn lex_number(&mut self, start: Position) -> Token {
+        let mut literal = String::new();
+
+        // `0b`, `0o`, `0x`, `0t`, `0c` — целое в другой системе счисления, без единицы
+        let radix = match (self.peek(), self.peek_nth(1)) {
+            (Some('0'), Some(letter)) => Radix::from_prefix(letter),
+            _ => None,
+        };
+        if let Some(radix) =
+            radix.filter(|radix| self.peek_nth(2).is_some_and(|c| radix.is_digit(c)))
+        {
+            literal.push_str(&self.rest()[..2]);
+            self.bump_str(&self.rest()[..2]);
+            while let Some(ch) = self.peek() {
+                if radix.is_digit(ch) || ch == '_' {
+                    literal.push(ch);
+                    self.bump();
+                } else {
+                    break;
+                }
+            }
+            return Token::new(
+                TokenKind::Number {
+                    literal,
+                    unit: None,
+                },
+                self.span_from(start),
+            );
+        }
+
+        if let Some(date_time) = self.try_lex_date_time(start) {
+            return date_time;
+        }
+
+        self.read_digits(&mut literal);
+        // Точка входит в число только перед цифрой: `0..7` — это диапазон
+        if self.peek() == Some('.') && self.peek_nth(1).is_some_and(|c| c.is_ascii_digit()) {
+            literal.push('.');
+            self.bump();
+            self.read_digits(&mut literal);
+        }
+        // Порядок читается раньше единицы: `1e5` — это 100000, а не `1` с единицей `e5`
+        let sign = matches!(self.peek_nth(1), Some('+' | '-')) as usize;
+        if matches!(self.peek(), Some('e' | 'E'))
+            && self.peek_nth(1 + sign).is_some_and(|c| c.is_ascii_digit())
+        {
+            literal.push_str(&self.rest()[..1 + sign]);
+            self.bump_str(&self.rest()[..1 + sign]);
+            self.read_digits(&mut literal);
+        }
+
+        let unit = self.read_unit();
+        Token::new(TokenKind::Number { literal, unit }, self.span_from(start))
+    }
+
+    /// `2024-03-15`, `2024-03-15T10:30`, `2024-03-15T10:30:00.5Z`, `2024-03-15T10:30:00+03:00`.
+    /// Дата узнаётся по форме `dddd-dd-dd`; правильность самих чисел проверяет вычислитель.
+    fn try_lex_date_time(&mut self, start: Position) -> Option<Token> {
+        let rest = self.rest();
+        let shape = |text: &str, pattern: &str| {
+            text.len() >= pattern.len()
+                && text
+                    .bytes()
+                    .zip(pattern.bytes())
+                    .all(|(ch, expected)| match expected {
+                        b'd' => ch.is_ascii_digit(),
+                        _ => ch == expected,
+                    })
+        };
+        if !shape(rest, "dddd-dd-dd") {
+            return None;
+        }
+        let mut length = "dddd-dd-dd".len();
+        if shape(&rest[length..], "Tdd:dd") {
+            length += "Tdd:dd".len();
+            if shape(&rest[length..], ":dd") {
+                length += ":dd".len();
+                if shape(&rest[length..], ".d") {
+                    length += 1 + rest[length + 1..]
+                        .bytes()
+                        .take_while(u8::is_ascii_digit)
+                        .count();
+                }
+            }
+            if rest[length..].starts_with('Z') {
+                length += 1;
+            } else if shape(&rest[length..], "+dd:dd") || shape(&rest[length..], "-dd:dd") {
+                length += "+dd:dd".len();
+            }
+        }
+        let next = rest[length..].chars().next();
+        if next.is_some_and(|ch| is_identifier_continue(ch) || ch == '.') {
+            return None;
+        }
+        let text = rest[..length].to_string();
+        self.bump_str(&text);
+        Some(Token::new(TokenKind::DateTime(text), self.span_from(start)))
+    }
+
+    fn read_digits(&mut self, literal: &mut String) {
+        while let Some(ch) = self.peek() {
+            if ch.is_ascii_digit() || ch == '_' {
+                literal.push(ch);
+                self.bump();
+            } else {
+                break;
+            }
+        }
+    }
+
+    /// Единица измерения, записанная вплотную к числу: `kW`, `m/s2`, `kg⋅m/s`, `°C`, `%`.
+    fn read_unit(&mut self) -> Option<String> {
+        if !self.peek().is_some_and(is_unit_start) {
+            return None;
+        }
+        let mut unit = String::new();
+        while let Some(ch) = self.peek() {
+            let joiner =
+                matches!(ch, '/' | '⋅' | '·' | '*') && self.peek_nth(1).is_some_and(is_unit_start);
+            if is_unit_start(ch) || ch.is_ascii_digit() || joiner {
+                unit.push(ch);
+                self.bump();
+            } else {
+                break;
+            }
+        }
+        Some(unit)
+    }
+
+    // --- Цвета ---
+
+    /// `#fa8246`, `#f84`, `#fa8246cc` — цвет. Если за `#` не 3, 4, 6 или 8 шестнадцатеричных
+    /// цифр или они продолжаются буквами (`#ff88zz`), это обычный `#`.
+    fn try_lex_color(&mut self, start: Position) -> Option<Token> {
+        let digits: String = self.rest()[1..]
+            .chars()
+            .take_while(|ch| ch.is_ascii_hexdigit())
+            .collect();
+        let next = self.peek_nth(digits.len() + 1);
+        if !matches!(digits.len(), 3 | 4 | 6 | 8) || next.is_some_and(is_identifier_continue) {
+            return None;
+        }
+        self.bump();
+        self.bump_str(&digits);
+        Some(Token::new(TokenKind::Color(digits), self.span_from(start)))
+    }
+
+    // --- Строки ---
+
+    fn lex_string(&mut self, quote: QuoteStyle, start: Position) -> Token {
+        self.bump();
+        let mut parts = Vec::new();
+        let mut text = String::new();
+
+        loop {
+            let ch = match self.peek() {
+                Some(ch) => ch,
+                None => {
+                    self.errors.push(LexError::UnterminatedString {
+                        quote: quote.as_char(),
+                        span: self.span_from(start),
+                    });
+                    break;
+                }
+            };
+
+            if ch == quote.as_char() {
+                self.bump();
+                break;
+            }
+
+            if ch == '\\' && self.peek_nth(1) == Some('u') && self.peek_nth(2) == Some('{') {
+                self.lex_code_point(&mut parts, &mut text);
+                continue;
+            }
+
+            if ch == '\\' {
+                self.bump();
+                match self.bump() {
+                    Some('n') => text.push('\n'),
+                    Some('t') => text.push('\t'),
+                    Some('r') => text.push('\r'),
+                    Some('0') => text.push('\0'),
+                    Some(escaped @ ('\\' | '"' | '\'' | '`' | '$')) => text.push(escaped),
+                    // Неизвестная последовательность сохраняется как есть
+                    Some(other) => {
+                        text.push('\\');
+                        text.push(other);
+                    }
+                    None => {
+                        text.push('\\');
+                        continue;
+                    }
+                }
+                continue;
+            }
+
+            if ch == '$' && self.peek_nth(1) == Some('{') {
+                if !text.is_empty() {
+                    parts.push(StringPart::Text(std::mem::take(&mut text)));
+                }
+                let interpolation_start = self.position;
+                self.bump_str("${");
+                let tokens = self.lex_interpolation(interpolation_start);
+                parts.push(StringPart::Interpolation(tokens));
+                continue;
+            }
+
+            text.push(ch);
+            self.bump();
+        }
+
+        if !text.is_empty() || parts.is_empty() {
+            parts.push(StringPart::Text(text));
+        }
+        Token::new(TokenKind::String { quote, parts }, self.span_from(start))
+    }
+
+    /// `\u{1F031}` — символ по шестнадцатеричному коду, `\u{${code}}` и
+    /// `\u{format(...)}` — по коду, который вычисляется. Содержимое из одних
+    /// шестнадцатеричных цифр всегда читается как код.
+    fn lex_code_point(&mut self, parts: &mut Vec<StringPart>, text: &mut String) {
+        let start = self.position;
+        self.bump_str("\\u{");
+        let digits: String = self
+            .rest()
+            .chars()
+            .take_while(|ch| ch.is_ascii_hexdigit())
+            .collect();
+        if !digits.is_empty() && self.rest()[digits.len()..].starts_with('}') {
+            self.bump_str(&digits);
+            self.bump();
+            match u32::from_str_radix(&digits, 16)
+                .ok()
+                .and_then(char::from_u32)
+            {
+                Some(ch) => text.push(ch),
+                None => self.errors.push(LexError::InvalidCodePoint {
+                    text: digits,
+                    span: self.span_from(start),
+                }),
+            }
+            return;
+        }
+        if !text.is_empty() {
+            parts.push(StringPart::Text(std::mem::take(text)));
+        }
+        let tokens = self.lex_interpolation(start);
+        parts.push(StringPart::CodePoint(tokens));
+    }
+
+    /// Лексирует выражение внутри `${...}` до парной закрывающей скобки.
+    fn lex_interpolation(&mut self, start: Position) -> Vec<Token> {
+        let mut tokens = Vec::new();
+        let mut depth = 0usize;
+        loop {
+            let token = self.next_token();
+            match token.kind {
+                TokenKind::LBrace => depth += 1,
+                TokenKind::RBrace if depth == 0 => break,
+                TokenKind::RBrace => depth -= 1,
+                TokenKind::Newline => continue,
+                TokenKind::Eof => {
+                    self.errors.push(LexError::UnterminatedInterpolation {
+                        span: self.span_from(start),
+                    });
+                    break;
+                }
+                _ => {}
+            }
+            tokens.push(token);
+        }
+        let end = tokens.last().map_or(self.position, |t| t.span.end);
+        tokens.push(Token::new(TokenKind::Eof, Span::point(end)));
+        tokens
+    }
+
+    // --- Заголовки документа ---
+
+    /// `[Mode:Data]`, `[Marker:Output<Dictionary> as JSON]` — лексируются одним токеном,
+    /// иначе `[` было бы началом массива.
+    fn try_lex_header(&mut self, start: Position) -> Option<Token> {
+        let after_bracket = &self.rest()[1..];
+        let name = match HEADER_NAMES
+            .iter()
+            .find(|name| after_bracket.starts_with(&format!("{}:", name)))
+        {
+            Some(name) => name.to_string(),
+            None => return None,
+        };
+
+        self.bump_str(&self.rest()[..name.len() + 2]);
+        let mut body = String::new();
+        loop {
+            match self.peek() {
+                Some(']') => {
+                    self.bump();
+                    break;
+                }
+                Some('\n') | None => {
+                    self.errors.push(LexError::UnterminatedHeader {
+                        name: name.clone(),
+                        span: self.span_from(start),
+                    });
+                    break;
+                }
+                Some(ch) => {
+                    body.push(ch);
+                    self.bump();
+                }
+            }
+        }
+
+        Some(Token::new(
+            TokenKind::Header {
+                name,
+                body: body.trim().to_string(),
+            },
+            self.span_from(start),
+        ))
+    }
+
+    // --- Операторы и пунктуация ---
+
+    fn lex_operator(&mut self) -> Option<TokenKind> {
+        // Самые длинные варианты проверяются первыми
+        const OPERATORS: &[(&str, TokenKind)] = &[
+            ("===", TokenKind::EqEqEq),
+            ("!==", TokenKind::NotEqEq),
+            ("...", TokenKind::Ellipsis),
+            ("@[", TokenKind::AttributeOpen),
+            ("**", TokenKind::StarStar),
+            ("//", TokenKind::SlashSlash),
+            ("++", TokenKind::PlusPlus),
+            ("--", TokenKind::MinusMinus),
+            ("+=", TokenKind::PlusAssign),
+            ("-=", TokenKind::MinusAssign),
+            ("*=", TokenKind::StarAssign),
+            ("/=", TokenKind::SlashAssign),
+            ("%=", TokenKind::PercentAssign),
+            (".=", TokenKind::DotAssign),
+            ("&=", TokenKind::AmpAssign),
+            ("==", TokenKind::EqEq),
+            ("!=", TokenKind::NotEq),
+            ("<=", TokenKind::LessEq),
+            (">=", TokenKind::GreaterEq),
+            ("&&", TokenKind::AndAnd),
+            ("||", TokenKind::OrOr),
+            ("~=", TokenKind::TildeEq),
+            ("|>", TokenKind::Pipeline),
+            ("->", TokenKind::Arrow),
+            ("=>", TokenKind::FatArrow),
+            (".-", TokenKind::Monus),
+            ("?.", TokenKind::QuestionDot),
+            ("..", TokenKind::DotDot),
+            ("::", TokenKind::ColonColon),
+            ("+", TokenKind::Plus),
+            ("-", TokenKind::Minus),
+            ("*", TokenKind::Star),
+            ("⋅", TokenKind::Star),
+            ("·", TokenKind::Star),
+            ("/", TokenKind::Slash),
+            ("%", TokenKind::Percent),
+            ("=", TokenKind::Assign),
+            ("<", TokenKind::Less),
+            (">", TokenKind::Greater),
+            ("!", TokenKind::Bang),
+            ("&", TokenKind::Amp),
+            ("|", TokenKind::Pipe),
+            ("→", TokenKind::Arrow),
+            ("∸", TokenKind::Monus),
+            ("∔", TokenKind::CartesianSum),
+            ("≣", TokenKind::Identical),
+            ("⌊", TokenKind::FloorOpen),
+            ("⌋", TokenKind::FloorClose),
+            ("⌈", TokenKind::CeilOpen),
+            ("⌉", TokenKind::CeilClose),
+            ("?", TokenKind::Question),
+            (".", TokenKind::Dot),
+            (":", TokenKind::Colon),
+            (",", TokenKind::Comma),
+            (";", TokenKind::Semicolon),
+            ("(", TokenKind::LParen),
+            (")", TokenKind::RParen),
+            ("[", TokenKind::LBracket),
+            ("]", TokenKind::RBracket),
+            ("{", TokenKind::LBrace),
+            ("}", TokenKind::RBrace),
+            ("@", TokenKind::At),
+            ("$", TokenKind::Dollar),
+            ("#", TokenKind::Hash),
+        ];
+
+        let (text, kind) = OPERATORS.iter().find(|(text, _)| self.starts_with(text))?;
+        self.bump_str(text);
+        Some(kind.clone())
+    }
+}
+
+fn is_identifier_start(ch: char) -> bool {
+    ch.is_alphabetic() || ch == '_'
+}
+
+/// `°` внутри имени нужен для целевых единиц преобразования: `25°C -> n°F`.
+fn is_identifier_continue(ch: char) -> bool {
+    ch.is_alphanumeric() || ch == '_' || ch == '°'
+}
+
+/// Символы, с которых может начинаться единица измерения после числа.
+fn is_unit_start(ch: char) -> bool {
+    ch.is_alphabetic() || matches!(ch, '°' | '%' | '‰' | '‱')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds(source: &str) -> Vec<TokenKind> {
+        Tokenizer::new(source)
+            .tokenize()
+            .unwrap()
+            .into_iter()
+            .map(|t| t.kind)
+            .collect()
+    }
+
+    #[test]
+    fn test_comments_and_names() {
+        let tokens = kinds("/|\\ комментарий\nkey-1 value /// ещё\n/* блок */ x // 7");
+        assert_eq!(
+            tokens,
+            vec![
+                TokenKind::Identifier("key-1".to_string()),
+                TokenKind::Identifier("value".to_string()),
+                TokenKind::Newline,
+                TokenKind::Identifier("x".to_string()),
+                TokenKind::SlashSlash,
+                TokenKind::Number {
+                    literal: "7".to_string(),
+                    unit: None
+                },
+                TokenKind::Eof,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_units_headers_and_unicode_operators() {
+        let tokens = kinds("[Marker:Output<Dictionary> as JSON] 25kg⋅m/s ∸ 0..7 → §config");
+        assert_eq!(
+            tokens,
+            vec![
+                TokenKind::Header {
+                    name: "Marker".to_string(),
+                    body: "Output<Dictionary> as JSON".to_string()
+                },
+                TokenKind::Number {
+                    literal: "25".to_string(),
+                    unit: Some("kg⋅m/s".to_string())
+                },
+                TokenKind::Monus,
+                TokenKind::Number {
+                    literal: "0".to_string(),
+                    unit: None
+                },
+                TokenKind::DotDot,
+                TokenKind::Number {
+                    literal: "7".to_string(),
+                    unit: None
+                },
+                TokenKind::Arrow,
+                TokenKind::Section("config".to_string()),
+                TokenKind::Eof,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_delta_before_numbers() {
+        assert_eq!(
+            kinds("Δ5K Δx Δ t"),
+            vec![
+                TokenKind::Delta,
+                TokenKind::Number {
+                    literal: "5".to_string(),
+                    unit: Some("K".to_string())
+                },
+                TokenKind::Identifier("Δx".to_string()),
+                TokenKind::Delta,
+                TokenKind::Identifier("t".to_string()),
+                TokenKind::Eof,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_radix_literals() {
+        let number = |literal: &str, unit: Option<&str>| TokenKind::Number {
+            literal: literal.to_string(),
+            unit: unit.map(str::to_string),
+        };
+        let tokens = kinds("0b1011 0o071 0x34_8F 0tL1FF 0cZYX 0cm 20_000μW");
+        assert_eq!(
+            tokens,
+            vec![
+                number("0b1011", None),
+                number("0o071", None),
+                number("0x34_8F", None),
+                number("0tL1FF", None),
+                number("0cZYX", None),
+                number("0", Some("cm")),
+                number("20_000", Some("μW")),
+                TokenKind::Eof,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_float_exponents() {
+        let number = |literal: &str, unit: Option<&str>| TokenKind::Number {
+            literal: literal.to_string(),
+            unit: unit.map(str::to_string),
+        };
+        let tokens = kinds("1e5 2.5E-3 6e+2kW 2em 0x1E");
+        assert_eq!(
+            tokens,
+            vec![
+                number("1e5", None),
+                number("2.5E-3", None),
+                number("6e+2", Some("kW")),
+                number("2", Some("em")),
+                number("0x1E", None),
+                TokenKind::Eof,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_columns_count_graphemes() {
+        // «е» с ударением и флаг — по одной графеме, хотя символов в них по два
+        let tokens = Tokenizer::new("\"е\u{301}🇷🇺\" key\r\nnext")
+            .tokenize()
+            .unwrap();
+        assert_eq!(tokens[1].kind, TokenKind::Identifier("key".to_string()));
+        assert_eq!(tokens[1].span.start.column, 6);
+        assert_eq!(tokens[1].span.to_string(), "1:6");
+        assert_eq!(tokens[3].span.start.line, 2);
+        assert_eq!(tokens[3].span.start.column, 1);
+    }
+
+    #[test]
+    fn test_string_interpolation_positions() {
+        let tokens = Tokenizer::new("ключ `U+${hex}`").tokenize().unwrap();
+        let string = &tokens[1];
+        assert_eq!(string.span.start.column, 6);
+        assert_eq!(string.span.start.offset, "ключ ".len());
+        match &string.kind {
+            TokenKind::String { quote, parts } => {
+                assert_eq!(*quote, QuoteStyle::Backtick);
+                assert_eq!(parts[0], StringPart::Text("U+".to_string()));
+                match &parts[1] {
+                    StringPart::Interpolation(inner) => {
+                        assert_eq!(inner[0].kind, TokenKind::Identifier("hex".to_string()));
+                        assert_eq!(inner[0].span.start.column, 11);
+                    }
+                    other => panic!("ожидалась интерполяция, получено {:?}", other),
+                }
+            }
+            other => panic!("ожидалась строка, получено {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_interpolated_names() {
+        let tokens = kinds("digit-${name}-text data.hel_${i}");
+        let text = |text: &str| StringPart::Text(text.to_string());
+        match &tokens[0] {
+            TokenKind::String { parts, .. } => {
+                assert_eq!(parts.len(), 3);
+                assert_eq!(parts[0], text("digit-"));
+                assert!(matches!(parts[1], StringPart::Interpolation(_)));
+                assert_eq!(parts[2], text("-text"));
+            }
+            other => panic!("ожидалось имя с интерполяцией, получено {:?}", other),
+        }
+        assert_eq!(tokens[1], TokenKind::Identifier("data".to_string()));
+        assert_eq!(tokens[2], TokenKind::Dot);
+        assert!(matches!(&tokens[3], TokenKind::String { parts, .. } if parts.len() == 2));
+    }
+}
@@ -0,0 +1,4 @@
+pub mod ast;
+pub mod diagnostics;
+pub mod lexer;
+pub mod parser;
@@ -0,0 +1,677 @@
+use crate::interpretator::frontend::ast::expression::{
+    BinaryOp, Expr, ExprKind, StringSegment, UnaryOp,
+};
+use crate::interpretator::frontend::ast::node::Node;
+use crate::interpretator::frontend::ast::statement::{Body, Param, RoutineKind};
+use crate::interpretator::frontend::diagnostics::span::Span;
+use crate::interpretator::frontend::lexer::token::{Keyword, StringPart, Token, TokenKind};
+use crate::interpretator::frontend::parser::parser::{ParseResult, Parser};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Associativity {
+    Left,
+    Right,
+}
+
+/// Уровень приоритета `**`; его же получает операнд унарного оператора,
+/// чтобы `-x ** 2` означало `-(x ** 2)`.
+const POWER_PRECEDENCE: u8 = 10;
+
+/// Уровень приоритета инфиксного применения имени-оператора `a op b`.
+const INFIX_PRECEDENCE: u8 = 8;
+
+/// Таблица приоритетов бинарных операторов (больше — связывает сильнее):
+///
+/// | 1  | `\|>`                              | левая  |
+/// | 2  | `\|\|` `or`                        | левая  |
+/// | 3  | `&&` `and`                         | левая  |
+/// | 4  | `==` `===` `!=` `!==` `≣` `~=`     | левая  |
+/// | 5  | `<` `>` `<=` `>=` `is` `in`        | левая  |
+/// | 6  | `..`                               | левая  |
+/// | 7  | `->` `→`                           | левая  |
+/// | 8  | `+` `-` `∸` `.-` `∔` `a op b`      | левая  |
+/// | 9  | `*` `⋅` `/` `//` `%`               | левая  |
+/// | 10 | `**`                               | правая |
+///
+/// Ниже всех стоит тернарный `? :`, выше — унарные `-` `+` `!` `not` `Δ` `...`
+/// и постфиксные вызов, индекс и доступ к полю.
+///
+/// `a op b`, где `op` — имя, объявленное с типом `<Operator>`, разбирается как вызов
+/// `op(a, b)` с приоритетом сложения: какой оператор окажется в имени, заранее неизвестно.
+pub fn binary_operator(kind: &TokenKind) -> Option<(BinaryOp, u8, Associativity)> {
+    use Associativity::{Left, Right};
+    let entry = match kind {
+        TokenKind::Pipeline => (BinaryOp::Pipeline, 1, Left),
+        TokenKind::OrOr | TokenKind::Keyword(Keyword::Or) => (BinaryOp::Or, 2, Left),
+        TokenKind::AndAnd | TokenKind::Keyword(Keyword::And) => (BinaryOp::And, 3, Left),
+        TokenKind::EqEq => (BinaryOp::Equal, 4, Left),
+        TokenKind::EqEqEq => (BinaryOp::StrictEqual, 4, Left),
+        TokenKind::NotEq => (BinaryOp::NotEqual, 4, Left),
+        TokenKind::NotEqEq => (BinaryOp::StrictNotEqual, 4, Left),
+        TokenKind::Identical => (BinaryOp::Identical, 4, Left),
+        TokenKind::TildeEq => (BinaryOp::Matches, 4, Left),
+        TokenKind::Less => (BinaryOp::Less, 5, Left),
+        TokenKind::Greater => (BinaryOp::Greater, 5, Left),
+        TokenKind::LessEq => (BinaryOp::LessEq, 5, Left),
+        TokenKind::GreaterEq => (BinaryOp::GreaterEq, 5, Left),
+        TokenKind::Keyword(Keyword::Is) => (BinaryOp::Is, 5, Left),
+        TokenKind::Keyword(Keyword::In) => (BinaryOp::In, 5, Left),
+        TokenKind::DotDot => (BinaryOp::Range, 6, Left),
+        TokenKind::Arrow => (BinaryOp::Convert, 7, Left),
+        TokenKind::Plus => (BinaryOp::Add, 8, Left),
+        TokenKind::Minus => (BinaryOp::Sub, 8, Left),
+        TokenKind::Monus => (BinaryOp::Monus, 8, Left),
+        TokenKind::CartesianSum => (BinaryOp::CartesianSum, 8, Left),
+        TokenKind::Star => (BinaryOp::Mul, 9, Left),
+        TokenKind::Slash => (BinaryOp::Div, 9, Left),
+        TokenKind::SlashSlash => (BinaryOp::IDiv, 9, Left),
+        TokenKind::Percent => (BinaryOp::Mod, 9, Left),
+        TokenKind::StarStar => (BinaryOp::Pow, POWER_PRECEDENCE, Right),
+        _ => return None,
+    };
+    Some(entry)
+}
+
+/// Арифметический оператор, который может стоять сам по себе как значение: `[+, -, *]`.
+fn operator_literal(kind: &TokenKind) -> Option<BinaryOp> {
+    match kind {
+        TokenKind::Plus => Some(BinaryOp::Add),
+        TokenKind::Minus => Some(BinaryOp::Sub),
+        TokenKind::Star => Some(BinaryOp::Mul),
+        TokenKind::Slash => Some(BinaryOp::Div),
+        TokenKind::SlashSlash => Some(BinaryOp::IDiv),
+        TokenKind::Percent => Some(BinaryOp::Mod),
+        TokenKind::StarStar => Some(BinaryOp::Pow),
+        _ => None,
+    }
+}
+
+impl Parser<'_> {
+    pub fn parse_expression(&mut self) -> ParseResult<Expr> {
+        let condition = self.parse_binary(1)?;
+        self.parse_ternary_rest(condition)
+    }
+
+    /// Продолжает выражение, левая часть которого уже разобрана.
+    pub(super) fn parse_expression_from(&mut self, left: Expr) -> ParseResult<Expr> {
+        let condition = self.parse_binary_rest(left, 1)?;
+        self.parse_ternary_rest(condition)
+    }
+
+    /// Выражение, в котором `(a, b) => ...` не считается лямбдой: образцы `match`,
+    /// цели присваивания, ленивые поля `key => value`.
+    pub(super) fn parse_without_lambda(
+        &mut self,
+        parse: impl FnOnce(&mut Parser) -> ParseResult<Expr>,
+    ) -> ParseResult<Expr> {
+        let previous = std::mem::replace(&mut self.allow_lambda, false);
+        let result = parse(self);
+        self.allow_lambda = previous;
+        result
+    }
+
+    fn parse_ternary_rest(&mut self, condition: Expr) -> ParseResult<Expr> {
+        // `key?` вплотную — признак необязательности, а не тернарный оператор
+        if !self.check(&TokenKind::Question) || self.is_adjacent() {
+            return Ok(condition);
+        }
+        self.advance();
+        self.skip_newlines();
+        let then_branch = self.parse_expression()?;
+        self.skip_newlines();
+        self.expect(&TokenKind::Colon)?;
+        self.skip_newlines();
+        let else_branch = self.parse_expression()?;
+        let span = condition.span.to(else_branch.span);
+        Ok(Node::new(
+            ExprKind::Ternary {
+                condition: Box::new(condition),
+                then_branch: Box::new(then_branch),
+                else_branch: Box::new(else_branch),
+            },
+            span,
+        ))
+    }
+
+    fn parse_binary(&mut self, min_precedence: u8) -> ParseResult<Expr> {
+        let left = self.parse_unary()?;
+        self.parse_binary_rest(left, min_precedence)
+    }
+
+    fn parse_binary_rest(&mut self, mut left: Expr, min_precedence: u8) -> ParseResult<Expr> {
+        loop {
+            let Some((op, precedence, associativity)) = binary_operator(self.peek_kind()) else {
+                if INFIX_PRECEDENCE >= min_precedence && self.at_infix_operator_name() {
+                    left = self.parse_infix_application(left)?;
+                    continue;
+                }
+                break;
+            };
+            if precedence < min_precedence {
+                break;
+            }
+            self.advance();
+            self.skip_newlines();
+            let next_min = match associativity {
+                Associativity::Left => precedence + 1,
+                Associativity::Right => precedence,
+            };
+            let right = self.parse_binary(next_min)?;
+            let span = left.span.to(right.span);
+            left = Node::new(
+                ExprKind::Binary {
+                    op,
+                    left: Box::new(left),
+                    right: Box::new(right),
+                },
+                span,
+            );
+        }
+        Ok(left)
+    }
+
+    /// Имя-оператор после операнда через пробел: `numbers[1] operatorSymbol numbers[2]`.
+    fn at_infix_operator_name(&self) -> bool {
+        matches!(self.peek_kind(), TokenKind::Identifier(name) if self.operator_names.contains(name))
+            && !self.is_adjacent()
+    }
+
+    fn parse_infix_application(&mut self, left: Expr) -> ParseResult<Expr> {
+        let token = self.advance();
+        let TokenKind::Identifier(name) = token.kind else {
+            unreachable!("at_infix_operator_name проверяет имя");
+        };
+        self.skip_newlines();
+        let right = self.parse_binary(INFIX_PRECEDENCE + 1)?;
+        let span = left.span.to(right.span);
+        Ok(Node::new(
+            ExprKind::Call {
+                callee: Box::new(Node::new(ExprKind::Identifier(name), token.span)),
+                type_arguments: Vec::new(),
+                arguments: vec![left, right],
+            },
+            span,
+        ))
+    }
+
+    fn parse_unary(&mut self) -> ParseResult<Expr> {
+        let start = self.peek().span;
+
+        if let Some(op) = operator_literal(self.peek_kind()) {
+            if matches!(
+                self.nth(1).kind,
+                TokenKind::Comma
+                    | TokenKind::RBracket
+                    | TokenKind::RParen
+                    | TokenKind::Newline
+                    | TokenKind::Eof
+            ) {
+                self.advance();
+                return Ok(Node::new(ExprKind::Operator(op), start));
+            }
+        }
+
+        let op = match self.peek_kind() {
+            TokenKind::Minus => UnaryOp::Negate,
+            TokenKind::Plus => UnaryOp::Plus,
+            TokenKind::Bang | TokenKind::Keyword(Keyword::Not) => UnaryOp::Not,
+            TokenKind::Delta => UnaryOp::Delta,
+            // `delta <Time>` — словесная запись `Δ <Time>`; `delta < limit` остаётся сравнением
+            TokenKind::Identifier(name) if name == "delta" && self.type_follows(1) => {
+                UnaryOp::Delta
+            }
+            TokenKind::Ellipsis => {
+                self.advance();
+                let operand = self.parse_unary()?;
+                let span = start.to(operand.span);
+                return Ok(Node::new(ExprKind::Spread(Box::new(operand)), span));
+            }
+            _ => return self.parse_postfix(),
+        };
+        self.advance();
+        let operand = self.parse_binary(POWER_PRECEDENCE)?;
+        let span = start.to(operand.span);
+        Ok(Node::new(
+            ExprKind::Unary {
+                op,
+                operand: Box::new(operand),
+            },
+            span,
+        ))
+    }
+
+    /// С токена `n` начинается тип вида `<Time>`: за `<` идёт имя с заглавной буквы.
+    fn type_follows(&self, n: usize) -> bool {
+        self.nth(n).kind == TokenKind::Less
+            && matches!(&self.nth(n + 1).kind,
+                TokenKind::Identifier(name) if name.starts_with(char::is_uppercase))
+    }
+
+    /// Первичное выражение с цепочкой вызовов, индексов и полей.
+    /// Постфиксные операторы действуют только вплотную: `call(x)`, но `key (x)` — значение.
+    pub(super) fn parse_postfix(&mut self) -> ParseResult<Expr> {
+        let mut expr = self.parse_primary()?;
+
+        while self.is_adjacent() {
+            let start = expr.span;
+            expr = match self.peek_kind() {
+                TokenKind::LParen => {
+                    let arguments = self.parse_arguments()?;
+                    Node::new(
+                        ExprKind::Call {
+                            callee: Box::new(expr),
+                            type_arguments: Vec::new(),
+                            arguments,
+                        },
+                        start.to(self.previous().span),
+                    )
+                }
+                TokenKind::LBracket | TokenKind::Question
+                    if self.check(&TokenKind::LBracket)
+                        || self.nth(1).kind == TokenKind::LBracket =>
+                {
+                    let optional = self.eat(&TokenKind::Question);
+                    self.expect(&TokenKind::LBracket)?;
+                    self.skip_newlines();
+                    let index = self.parse_expression()?;
+                    self.skip_newlines();
+                    self.expect(&TokenKind::RBracket)?;
+                    Node::new(
+                        ExprKind::Index {
+                            object: Box::new(expr),
+                            index: Box::new(index),
+                            optional,
+                        },
+                        start.to(self.previous().span),
+                    )
+                }
+                TokenKind::Dot | TokenKind::QuestionDot => {
+                    let optional = self.advance().kind == TokenKind::QuestionDot;
+                    // `data.hel_${suffix}` — имя поля вычисляется, это то же, что `data["hel_${suffix}"]`
+                    if matches!(self.peek_kind(), TokenKind::String { .. }) && self.is_adjacent() {
+                        let index = self.parse_primary()?;
+                        expr = Node::new(
+                            ExprKind::Index {
+                                object: Box::new(expr),
+                                index: Box::new(index),
+                                optional,
+                            },
+                            start.to(self.previous().span),
+                        );
+                        continue;
+                    }
+                    let property = self.expect_property_name()?;
+                    Node::new(
+                        ExprKind::Member {
+                            object: Box::new(expr),
+                            property,
+                            optional,
+                        },
+                        start.to(self.previous().span),
+                    )
+                }
+                TokenKind::Less => match self.try_parse_generic_call(expr)? {
+                    Ok(call) => call,
+                    Err(expr) => return Ok(expr),
+                },
+                _ => break,
+            };
+        }
+
+        Ok(expr)
+    }
+
+    /// `name<Float, Int>(12.543, 3)`. Если после `<...>` не идёт вызов — это не обобщённый
+    /// вызов, позиция откатывается и выражение возвращается как есть.
+    fn try_parse_generic_call(&mut self, callee: Expr) -> ParseResult<Result<Expr, Expr>> {
+        let saved = self.position;
+        self.advance();
+        let mut type_arguments = Vec::new();
+        let parsed = loop {
+            match self.parse_type_expr() {
+                Ok(ty) => type_arguments.push(ty),
+                Err(_) => break false,
+            }
+            if !self.eat(&TokenKind::Comma) {
+                break self.eat(&TokenKind::Greater)
+                    && self.check(&TokenKind::LParen)
+                    && self.is_adjacent();
+            }
+        };
+        if !parsed {
+            self.position = saved;
+            return Ok(Err(callee));
+        }
+        let arguments = self.parse_arguments()?;
+        let span = callee.span.to(self.previous().span);
+        Ok(Ok(Node::new(
+            ExprKind::Call {
+                callee: Box::new(callee),
+                type_arguments,
+                arguments,
+            },
+            span,
+        )))
+    }
+
+    /// Имя поля после точки: обычное имя, ключевое слово или секция (`fn.§config`).
+    fn expect_property_name(&mut self) -> ParseResult<String> {
+        match self.peek_kind().clone() {
+            TokenKind::Identifier(name) => {
+                self.advance();
+                Ok(name)
+            }
+            TokenKind::Keyword(keyword) => {
+                self.advance();
+                Ok(keyword.as_str().to_string())
+            }
+            TokenKind::Section(name) => {
+                self.advance();
+                Ok(format!("§{}", name))
+            }
+            _ => Err(self.expected("имя поля")),
+        }
+    }
+
+    pub(super) fn parse_arguments(&mut self) -> ParseResult<Vec<Expr>> {
+        self.expect(&TokenKind::LParen)?;
+        let mut arguments = Vec::new();
+        loop {
+            self.skip_newlines();
+            if self.eat(&TokenKind::RParen) {
+                break;
+            }
+            arguments.push(self.parse_expression()?);
+            self.skip_newlines();
+            if !self.eat(&TokenKind::Comma) {
+                self.skip_newlines();
+                self.expect(&TokenKind::RParen)?;
+                break;
+            }
+        }
+        Ok(arguments)
+    }
+
+    fn parse_primary(&mut self) -> ParseResult<Expr> {
+        let start = self.peek().span;
+
+        let kind = match self.peek_kind().clone() {
+            TokenKind::Number { literal, unit } => {
+                self.advance();
+                ExprKind::Number { literal, unit }
+            }
+            // О нераспознанном символе уже сообщил лексер
+            TokenKind::Error => {
+                self.advance();
+                ExprKind::Error
+            }
+            TokenKind::String { quote, parts } => {
+                self.advance();
+                ExprKind::String {
+                    quote,
+                    segments: self.parse_string_parts(parts),
+                }
+            }
+            TokenKind::Keyword(Keyword::True) => {
+                self.advance();
+                ExprKind::Boolean(true)
+            }
+            TokenKind::Keyword(Keyword::False) => {
+                self.advance();
+                ExprKind::Boolean(false)
+            }
+            TokenKind::Keyword(Keyword::None) => {
+                self.advance();
+                ExprKind::None
+            }
+            TokenKind::Keyword(Keyword::Nil) => {
+                self.advance();
+                ExprKind::Nil
+            }
+            TokenKind::Keyword(Keyword::Undefined) => {
+                self.advance();
+                ExprKind::Undefined
+            }
+            TokenKind::Keyword(Keyword::Auto) => {
+                self.advance();
+                ExprKind::Auto
+            }
+            // `global.index`, `local.value` — области видимости как объекты
+            TokenKind::Keyword(keyword @ (Keyword::Global | Keyword::Local)) => {
+                self.advance();
+                ExprKind::Identifier(keyword.as_str().to_string())
+            }
+            TokenKind::Keyword(Keyword::Function) => ExprKind::Routine(Box::new(
+                self.parse_routine(RoutineKind::Function, Vec::new())?,
+            )),
+            TokenKind::Keyword(Keyword::Procedure) => ExprKind::Routine(Box::new(
+                self.parse_routine(RoutineKind::Procedure, Vec::new())?,
+            )),
+            TokenKind::Keyword(Keyword::New) => {
+                self.advance();
+                let mut class = self.parse_primary()?;
+                while self.check(&TokenKind::Dot) && self.is_adjacent() {
+                    self.advance();
+                    let property = self.expect_property_name()?;
+                    let span = class.span.to(self.previous().span);
+                    class = Node::new(
+                        ExprKind::Member {
+                            object: Box::new(class),
+                            property,
+                            optional: false,
+                        },
+                        span,
+                    );
+                }
+                let arguments = if self.check(&TokenKind::LParen) && self.is_adjacent() {
+                    self.parse_arguments()?
+                } else {
+                    Vec::new()
+                };
+                ExprKind::New {
+                    class: Box::new(class),
+                    arguments,
+                }
+            }
+            TokenKind::Identifier(_) if self.expansion_follows() => return self.parse_expansion(),
+            TokenKind::Identifier(name) => {
+                if self.allow_lambda && self.nth(1).kind == TokenKind::FatArrow {
+                    return self.parse_single_param_lambda(name);
+                }
+                self.advance();
+                ExprKind::Identifier(name)
+            }
+            TokenKind::Dollar => {
+                self.advance();
+                if !self.is_adjacent() {
+                    return Err(self.expected("имя после «$»"));
+                }
+                // `${i}` и вне строки значит то же, что `i`
+                if self.eat(&TokenKind::LBrace) {
+                    let value = self.parse_expression()?;
+                    self.expect(&TokenKind::RBrace)?;
+                    return Ok(Node::new(value.kind, start.to(self.previous().span)));
+                }
+                ExprKind::Identifier(self.expect_identifier()?.0)
+            }
+            TokenKind::Section(name) => {
+                self.advance();
+                ExprKind::Section(name)
+            }
+            TokenKind::DateTime(text) => {
+                self.advance();
+                ExprKind::DateTime(text)
+            }
+            TokenKind::Color(digits) => {
+                self.advance();
+                ExprKind::Color(digits)
+            }
+            TokenKind::LParen => {
+                if self.allow_lambda && self.is_lambda_params() {
+                    return self.parse_lambda();
+                }
+                return self.parse_parenthesized();
+            }
+            TokenKind::LBracket => {
+                self.advance();
+                let mut elements = Vec::new();
+                loop {
+                    while self.eat(&TokenKind::Newline) || self.eat(&TokenKind::Comma) {}
+                    if self.eat(&TokenKind::RBracket) {
+                        break;
+                    }
+                    elements.push(self.parse_expression()?);
+                    if !matches!(
+                        self.peek_kind(),
+                        TokenKind::Comma | TokenKind::Newline | TokenKind::RBracket
+                    ) {
+                        return Err(self.expected("«,» или «]»"));
+                    }
+                }
+                ExprKind::Array(elements)
+            }
+            TokenKind::LBrace => {
+                let block = self.parse_brace_block()?;
+                return Ok(Node::new(ExprKind::Dictionary(block.kind), block.span));
+            }
+            TokenKind::Less => {
+                let ty = self.parse_type_annotation()?;
+                ExprKind::Type(ty)
+            }
+            TokenKind::FloorOpen => {
+                self.advance();
+                let value = self.parse_expression()?;
+                self.expect(&TokenKind::FloorClose)?;
+                ExprKind::Floor(Box::new(value))
+            }
+            TokenKind::CeilOpen => {
+                self.advance();
+                let value = self.parse_expression()?;
+                self.expect(&TokenKind::CeilClose)?;
+                ExprKind::Ceil(Box::new(value))
+            }
+            _ => return Err(self.expected("выражение")),
+        };
+
+        Ok(Node::new(kind, start.to(self.previous().span)))
+    }
+
+    /// `(expr)` или кортеж `(a, b)`.
+    fn parse_parenthesized(&mut self) -> ParseResult<Expr> {
+        let start = self.expect(&TokenKind::LParen)?.span;
+        let mut elements = Vec::new();
+        let mut trailing_comma = false;
+        loop {
+            self.skip_newlines();
+            if self.eat(&TokenKind::RParen) {
+                break;
+            }
+            elements.push(self.parse_expression()?);
+            self.skip_newlines();
+            trailing_comma = self.eat(&TokenKind::Comma);
+            if !trailing_comma {
+                self.skip_newlines();
+                self.expect(&TokenKind::RParen)?;
+                break;
+            }
+        }
+        let span = start.to(self.previous().span);
+        if elements.len() == 1 && !trailing_comma {
+            let mut inner = elements.pop().unwrap();
+            inner.span = span;
+            return Ok(inner);
+        }
+        Ok(Node::new(ExprKind::Tuple(elements), span))
+    }
+
+    // --- Лямбды ---
+
+    /// За скобками параметров сразу следует `=>`.
+    fn is_lambda_params(&self) -> bool {
+        self.matching_close(0)
+            .and_then(|close| self.tokens.get(close + 1))
+            .is_some_and(|token| token.kind == TokenKind::FatArrow)
+    }
+
+    fn parse_lambda(&mut self) -> ParseResult<Expr> {
+        let start = self.peek().span;
+        let params = self.parse_params()?;
+        self.expect(&TokenKind::FatArrow)?;
+        self.finish_lambda(start, params)
+    }
+
+    /// `a => b => a + b`
+    fn parse_single_param_lambda(&mut self, name: String) -> ParseResult<Expr> {
+        let start = self.advance().span;
+        self.expect(&TokenKind::FatArrow)?;
+        let param = Param {
+            name,
+            by_ref: false,
+            variadic: false,
+            ty: None,
+            optional: false,
+            default: None,
+            span: start,
+        };
+        self.finish_lambda(start, vec![param])
+    }
+
+    fn finish_lambda(&mut self, start: Span, params: Vec<Param>) -> ParseResult<Expr> {
+        let body = if self.check(&TokenKind::LBrace) {
+            Body::Block(self.parse_brace_block()?)
+        } else {
+            Body::Expression(self.parse_expression()?)
+        };
+        Ok(Node::new(
+            ExprKind::Lambda {
+                params,
+                body: Box::new(body),
+            },
+            start.to(self.previous().span),
+        ))
+    }
+
+    // --- Строки ---
+
+    /// Интерполяции уже лексированы; каждая разбирается отдельным парсером.
+    fn parse_string_parts(&mut self, parts: Vec<StringPart>) -> Vec<StringSegment> {
+        parts
+            .into_iter()
+            .map(|part| match part {
+                StringPart::Text(text) => StringSegment::Text(text),
+                StringPart::Interpolation(tokens) => {
+                    StringSegment::Interpolation(self.parse_embedded(tokens))
+                }
+                StringPart::CodePoint(tokens) => {
+                    StringSegment::CodePoint(self.parse_embedded(tokens))
+                }
+            })
+            .collect()
+    }
+
+    /// Выражение из `${...}` или `\u{...}`. Внутри строки можно записать и `if`:
+    /// `${if (done) "готово" else "в работе"}`.
+    fn parse_embedded(&mut self, tokens: Vec<Token>) -> Expr {
+        let span = match (tokens.first(), tokens.last()) {
+            (Some(first), Some(last)) => first.span.to(last.span),
+            _ => self.previous().span,
+        };
+        let mut parser = Parser::new(tokens, self.reporter);
+        let result = match parser.peek_kind() {
+            TokenKind::Keyword(Keyword::If) => parser.parse_if_expression(),
+            _ => parser.parse_expression(),
+        };
+        let result = result.and_then(|expr| {
+            if parser.is_at_end() {
+                Ok(expr)
+            } else {
+                Err(parser.expected("«}» в конце интерполяции"))
+            }
+        });
+        // Ошибка внутри `${...}` не должна терять всю строку
+        result.unwrap_or_else(|error| {
+            parser.reporter.report(error);
+            Node::new(ExprKind::Error, span)
+        })
+    }
+}
@@ -0,0 +1,4 @@
+pub mod expression;
+#[allow(clippy::module_inception, reason = "парсер живёт в «parser/parser.rs» по раскладке каталогов")]
+pub mod parser;
+pub mod statement;
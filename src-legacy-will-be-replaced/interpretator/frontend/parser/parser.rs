@@ -0,0 +1,445 @@
+use crate::interpretator::frontend::ast::node::Program;
+use crate::interpretator::frontend::diagnostics::error::Diagnostic;
+use crate::interpretator::frontend::diagnostics::reporter::Reporter;
+use crate::interpretator::frontend::diagnostics::span::Span;
+use crate::interpretator::frontend::lexer::token::{Keyword, Token, TokenKind};
+use crate::interpretator::frontend::lexer::tokenizer::Tokenizer;
+use std::collections::HashSet;
+use thiserror::Error;
+
+/// Ошибка синтаксического анализа.
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum ParseError {
+    #[error("ожидалось {expected}, найдено {found}")]
+    Expected {
+        expected: String,
+        found: String,
+        span: Span,
+    },
+    #[error("{message}")]
+    Invalid { message: String, span: Span },
+}
+
+impl ParseError {
+    pub fn span(&self) -> Span {
+        match self {
+            ParseError::Expected { span, .. } | ParseError::Invalid { span, .. } => *span,
+        }
+    }
+
+    /// Код ошибки для диагностик.
+    pub fn code(&self) -> &'static str {
+        match self {
+            ParseError::Expected { .. } => "E0100",
+            ParseError::Invalid { .. } => "E0101",
+        }
+    }
+}
+
+impl From<ParseError> for Diagnostic {
+    fn from(error: ParseError) -> Self {
+        let diagnostic = Diagnostic::error(error.to_string(), error.span()).with_code(error.code());
+        match error {
+            ParseError::Expected { expected, .. } => {
+                diagnostic.with_primary_message(format!("ожидалось {}", expected))
+            }
+            ParseError::Invalid { .. } => diagnostic,
+        }
+    }
+}
+
+pub type ParseResult<T> = Result<T, ParseError>;
+
+/// Рекурсивный спуск по потоку токенов.
+/// Разбор инструкций — в `statement.rs`, выражений и таблица приоритетов — в `expression.rs`.
+///
+/// Парсер не останавливается на первой ошибке: он сообщает о ней в `reporter`,
+/// оставляет в дереве узел `Error` и продолжает со следующей инструкции.
+pub struct Parser<'r> {
+    pub(super) tokens: Vec<Token>,
+    pub(super) position: usize,
+    /// Можно ли сейчас считать `(a) => ...` и `a => ...` лямбдой.
+    pub(super) allow_lambda: bool,
+    /// Имена, объявленные с типом `<Operator>` или значением-оператором:
+    /// между двумя операндами такое имя применяется к ним, `a operatorSymbol b`.
+    pub(super) operator_names: HashSet<String>,
+    pub(super) reporter: &'r mut Reporter,
+}
+
+/// Разбирает исходный текст целиком. Ошибки лексера и парсера собираются в `reporter`;
+/// дерево возвращается всегда, ошибочные места в нём заменены узлами `Error`.
+pub fn parse_source(source: &str, reporter: &mut Reporter) -> Program {
+    let (tokens, errors) = Tokenizer::new(source).tokenize_recovering();
+    for error in errors {
+        reporter.report(error);
+    }
+    Parser::new(tokens, reporter).parse_program()
+}
+
+impl<'r> Parser<'r> {
+    /// Поток токенов обязан заканчиваться `Eof`, как его выдаёт `Tokenizer`.
+    pub fn new(tokens: Vec<Token>, reporter: &'r mut Reporter) -> Self {
+        Parser {
+            tokens,
+            position: 0,
+            allow_lambda: true,
+            operator_names: HashSet::new(),
+            reporter,
+        }
+    }
+
+    pub fn parse_program(&mut self) -> Program {
+        let start = self.peek().span;
+        let statements = self.parse_statements(|parser| parser.is_at_end());
+        Program {
+            statements,
+            span: start.to(self.peek().span),
+        }
+    }
+
+    // --- Навигация по токенам ---
+
+    pub(super) fn peek(&self) -> &Token {
+        self.nth(0)
+    }
+
+    pub(super) fn nth(&self, n: usize) -> &Token {
+        let index = (self.position + n).min(self.tokens.len() - 1);
+        &self.tokens[index]
+    }
+
+    pub(super) fn peek_kind(&self) -> &TokenKind {
+        &self.peek().kind
+    }
+
+    pub(super) fn previous(&self) -> &Token {
+        &self.tokens[self.position.saturating_sub(1)]
+    }
+
+    pub(super) fn advance(&mut self) -> Token {
+        let token = self.peek().clone();
+        if !self.is_at_end() {
+            self.position += 1;
+        }
+        token
+    }
+
+    pub(super) fn is_at_end(&self) -> bool {
+        self.peek_kind() == &TokenKind::Eof
+    }
+
+    pub(super) fn check(&self, kind: &TokenKind) -> bool {
+        self.peek_kind() == kind
+    }
+
+    pub(super) fn eat(&mut self, kind: &TokenKind) -> bool {
+        if self.check(kind) {
+            self.advance();
+            true
+        } else {
+            false
+        }
+    }
+
+    pub(super) fn eat_keyword(&mut self, keyword: Keyword) -> bool {
+        self.eat(&TokenKind::Keyword(keyword))
+    }
+
+    pub(super) fn expect(&mut self, kind: &TokenKind) -> ParseResult<Token> {
+        if self.check(kind) {
+            Ok(self.advance())
+        } else {
+            Err(self.expected(&kind.describe()))
+        }
+    }
+
+    pub(super) fn expect_identifier(&mut self) -> ParseResult<(String, Span)> {
+        match self.peek_kind().clone() {
+            TokenKind::Identifier(name) => {
+                let span = self.advance().span;
+                Ok((name, span))
+            }
+            _ => Err(self.expected("имя")),
+        }
+    }
+
+    /// Следующий токен стоит вплотную к предыдущему, без пробелов.
+    pub(super) fn is_adjacent(&self) -> bool {
+        self.position > 0 && self.peek().is_adjacent_to(self.previous())
+    }
+
+    pub(super) fn skip_newlines(&mut self) {
+        while self.eat(&TokenKind::Newline) {}
+    }
+
+    /// Тип токена после пропуска переводов строк, без их поглощения.
+    pub(super) fn peek_past_newlines(&self) -> &TokenKind {
+        let mut n = 0;
+        while self.nth(n).kind == TokenKind::Newline {
+            n += 1;
+        }
+        &self.nth(n).kind
+    }
+
+    /// Конец инструкции на текущей строке.
+    pub(super) fn at_statement_end(&self) -> bool {
+        matches!(
+            self.peek_kind(),
+            TokenKind::Newline
+                | TokenKind::Eof
+                | TokenKind::Semicolon
+                | TokenKind::Comma
+                | TokenKind::RBrace
+                | TokenKind::RBracket
+                | TokenKind::RParen
+        )
+    }
+
+    /// Столбец первого токена строки, в которой стоит токен с индексом `index`.
+    pub(super) fn line_indent(&self, index: usize) -> usize {
+        let mut first = index.min(self.tokens.len() - 1);
+        while first > 0 && self.tokens[first - 1].kind != TokenKind::Newline {
+            first -= 1;
+        }
+        self.tokens[first].span.start.column
+    }
+
+    pub(super) fn expected(&self, expected: &str) -> ParseError {
+        ParseError::Expected {
+            expected: expected.to_string(),
+            found: self.peek().kind.describe(),
+            span: self.peek().span,
+        }
+    }
+
+    pub(super) fn invalid(&self, message: impl Into<String>, span: Span) -> ParseError {
+        ParseError::Invalid {
+            message: message.into(),
+            span,
+        }
+    }
+
+    /// Индекс парной закрывающей скобки для открывающей на позиции `self.position + offset`.
+    pub(super) fn matching_close(&self, offset: usize) -> Option<usize> {
+        let mut depth = 0usize;
+        let mut index = self.position + offset;
+        while index < self.tokens.len() {
+            match self.tokens[index].kind {
+                TokenKind::LParen | TokenKind::LBracket | TokenKind::LBrace => depth += 1,
+                TokenKind::RParen | TokenKind::RBracket | TokenKind::RBrace => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return Some(index);
+                    }
+                }
+                TokenKind::Eof => return None,
+                _ => {}
+            }
+            index += 1;
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interpretator::frontend::ast::expression::{BinaryOp, ExprKind};
+    use crate::interpretator::frontend::ast::statement::{
+        Body, DeclKind, EntryKey, Pattern, StmtKind,
+    };
+
+    fn parse_clean(source: &str) -> Program {
+        let mut reporter = Reporter::new();
+        let program = parse_source(source, &mut reporter);
+        assert!(
+            !reporter.has_errors(),
+            "неожиданные ошибки: {:?}",
+            reporter.diagnostics()
+        );
+        program
+    }
+
+    #[test]
+    fn test_entries_and_declarations() {
+        let program = parse_clean(
+            "[Mode:Data]\nconst limit<Int> = 10\nkey-1 value\nmaths {\n\t\"1 + 7\" 1 + 7\n}\n",
+        );
+        assert_eq!(program.statements.len(), 4);
+
+        match &program.statements[1].kind {
+            StmtKind::VarDecl(decl) => {
+                assert_eq!(decl.kind, DeclKind::Const);
+                assert_eq!(decl.pattern, Pattern::Name("limit".to_string()));
+                assert_eq!(decl.ty.as_ref().unwrap().kind.simple_name(), Some("Int"));
+            }
+            other => panic!("ожидалось объявление, получено {:?}", other),
+        }
+        match &program.statements[3].kind {
+            StmtKind::Entry(entry) => match &entry.value.kind {
+                ExprKind::Dictionary(items) => match &items[0].kind {
+                    StmtKind::Entry(inner) => {
+                        assert!(matches!(inner.key, EntryKey::String(_)));
+                        assert!(matches!(
+                            inner.value.kind,
+                            ExprKind::Binary {
+                                op: BinaryOp::Add,
+                                ..
+                            }
+                        ));
+                    }
+                    other => panic!("ожидался элемент, получено {:?}", other),
+                },
+                other => panic!("ожидался словарь, получено {:?}", other),
+            },
+            other => panic!("ожидался элемент, получено {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_precedence_and_pipeline() {
+        let program = parse_clean("const a = value |> name3() |> name1(1 + 2 * 3 ** 2)");
+        let value = match &program.statements[0].kind {
+            StmtKind::VarDecl(decl) => decl.value.clone().unwrap(),
+            other => panic!("ожидалось объявление, получено {:?}", other),
+        };
+        match value.kind {
+            ExprKind::Binary {
+                op: BinaryOp::Pipeline,
+                right,
+                ..
+            } => match right.kind {
+                ExprKind::Call { arguments, .. } => match &arguments[0].kind {
+                    ExprKind::Binary {
+                        op: BinaryOp::Add,
+                        right,
+                        ..
+                    } => assert!(matches!(
+                        right.kind,
+                        ExprKind::Binary {
+                            op: BinaryOp::Mul,
+                            ..
+                        }
+                    )),
+                    other => panic!("ожидалось сложение, получено {:?}", other),
+                },
+                other => panic!("ожидался вызов, получено {:?}", other),
+            },
+            other => panic!("ожидался конвейер, получено {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_routines_and_indented_blocks() {
+        let program = parse_clean(
+            "function calc(a<Float>, b<Float> = 1, c<Operator> +):<Float> {\n\treturn a\n}\n\
+             try\n\tif ready\n\t\tstart()\ncatch as e\n\tthrow e\n",
+        );
+        match &program.statements[0].kind {
+            StmtKind::Routine(routine) => {
+                assert_eq!(routine.params.len(), 3);
+                assert!(matches!(
+                    routine.params[2].default.as_ref().unwrap().kind,
+                    ExprKind::Operator(BinaryOp::Add)
+                ));
+                assert!(routine.return_type.is_some());
+                assert!(matches!(routine.body, Body::Block(_)));
+            }
+            other => panic!("ожидалась функция, получено {:?}", other),
+        }
+        match &program.statements[1].kind {
+            StmtKind::Try { body, catch, .. } => {
+                assert_eq!(body.kind.len(), 1);
+                let catch = catch.as_ref().unwrap();
+                assert_eq!(catch.binding.as_deref(), Some("e"));
+                assert!(matches!(catch.body.kind[0].kind, StmtKind::Throw(Some(_))));
+            }
+            other => panic!("ожидался try, получено {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_recovers_and_reports_every_error() {
+        let mut reporter = Reporter::new();
+        let program = parse_source(
+            "first 1\nbroken = = 2\nblock {\n\tinner (1, \n\t\t2 +)\n\tafter `${)}` ¤\n}\nlast 3\n",
+            &mut reporter,
+        );
+
+        let lines: Vec<usize> = reporter
+            .diagnostics()
+            .iter()
+            .map(|diagnostic| diagnostic.span().start.line)
+            .collect();
+        assert_eq!(lines, vec![2, 5, 6, 6]);
+
+        let kinds: Vec<&StmtKind> = program.statements.iter().map(|s| &s.kind).collect();
+        assert_eq!(kinds.len(), 4);
+        assert!(matches!(kinds[1], StmtKind::Error));
+        assert!(matches!(kinds[3], StmtKind::Entry(_)));
+        match kinds[2] {
+            StmtKind::Entry(entry) => match &entry.value.kind {
+                ExprKind::Dictionary(items) => {
+                    assert!(matches!(items[0].kind, StmtKind::Error));
+                    assert!(matches!(items[1].kind, StmtKind::Entry(_)));
+                }
+                other => panic!("ожидался словарь, получено {:?}", other),
+            },
+            other => panic!("ожидался элемент, получено {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_infix_operator_names() {
+        let program = parse_clean(
+            "function staged(numbers<Array> [], operatorSymbol<Operator> +) {\n\tconst result numbers[1] operatorSymbol numbers[2]\n}\nconst op = *\nvalue 3 op 4 + 1\nplain 3\n",
+        );
+        assert_eq!(program.statements.len(), 4);
+
+        let StmtKind::Entry(entry) = &program.statements[2].kind else {
+            panic!(
+                "ожидался элемент, получено {:?}",
+                program.statements[2].kind
+            );
+        };
+        let ExprKind::Binary { op, left, .. } = &entry.value.kind else {
+            panic!("ожидалось сложение");
+        };
+        assert_eq!(*op, BinaryOp::Add);
+        match &left.kind {
+            ExprKind::Call {
+                callee, arguments, ..
+            } => {
+                assert_eq!(callee.kind, ExprKind::Identifier("op".to_string()));
+                assert_eq!(arguments.len(), 2);
+            }
+            other => panic!("ожидался вызов оператора, получено {:?}", other),
+        }
+    }
+
+    /// Сколько ошибок дают примеры из корня репозитория. В них остаются наброски синтаксиса
+    /// (`{ ... }`, перечисления типов), поэтому ноль не ожидается — но любое изменение
+    /// числа должно быть осознанным.
+    #[test]
+    fn test_shipped_examples() {
+        let examples = [
+            (include_str!("../../../../example.lacon"), 89),
+            (include_str!("../../../../concept.lacon"), 108),
+            (include_str!("../../../../example-list.llacon"), 12),
+            (include_str!("../../../../include-example-1.llacon"), 0),
+            (include_str!("../../../../include-example-2.llacon"), 0),
+            (include_str!("../../../../schema_use.llacon"), 0),
+        ];
+        for (index, (source, expected)) in examples.into_iter().enumerate() {
+            let mut reporter = Reporter::new();
+            parse_source(source, &mut reporter);
+            assert_eq!(
+                reporter.diagnostics().len(),
+                expected,
+                "пример №{}: {:?}",
+                index + 1,
+                reporter.diagnostics().first()
+            );
+        }
+    }
+}
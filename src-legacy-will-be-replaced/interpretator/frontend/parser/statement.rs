@@ -0,0 +1,1283 @@
+use crate::interpretator::frontend::ast::expression::{Expr, ExprKind, StringSegment, UnaryOp};
+use crate::interpretator::frontend::ast::node::{Node, TypeExpr, TypeExprKind};
+use crate::interpretator::frontend::ast::statement::{
+    AssignOp, Attribute, Block, Body, CatchClause, ClassDecl, ClassKind, DeclKind, Entry, EntryKey,
+    Expansion, ExpansionKind, GenericParam, MatchArm, Modifier, Param, Pattern, RoutineDecl,
+    RoutineKind, Stmt, StmtKind, VarDecl,
+};
+use crate::interpretator::frontend::lexer::token::{Keyword, QuoteStyle, StringPart, TokenKind};
+use crate::interpretator::frontend::parser::expression::binary_operator;
+use crate::interpretator::frontend::parser::parser::{ParseResult, Parser};
+
+impl Parser<'_> {
+    /// Последовательность инструкций до условия `stop`; сам ограничитель не поглощается.
+    /// Ошибочная инструкция сообщается в `reporter` и заменяется узлом `StmtKind::Error`.
+    pub(super) fn parse_statements(&mut self, stop: impl Fn(&Parser) -> bool) -> Vec<Stmt> {
+        let mut statements = Vec::new();
+        loop {
+            self.skip_separators();
+            if self.is_at_end() || stop(self) {
+                break;
+            }
+            let start = self.position;
+            let result = self.parse_statement().and_then(|statement| {
+                // О нераспознанных символах уже сообщил лексер
+                while self.check(&TokenKind::Error) {
+                    self.advance();
+                }
+                // Заголовки можно писать подряд: `[Mode:Data][Document:Dictionary]`
+                let is_header = matches!(statement.kind, StmtKind::Header { .. });
+                if !is_header
+                    && !self.at_statement_end()
+                    && !self.ended_with_newline()
+                    && !stop(self)
+                {
+                    return Err(self.expected("конец инструкции"));
+                }
+                Ok(statement)
+            });
+            match result {
+                Ok(statement) => statements.push(statement),
+                Err(error) => {
+                    let on_lex_error =
+                        self.check(&TokenKind::Error) && self.peek().span == error.span();
+                    if !on_lex_error {
+                        self.reporter.report(error);
+                    }
+                    self.synchronize(start, &stop);
+                    let span = self.tokens[start].span.to(self.previous().span);
+                    statements.push(Node::new(StmtKind::Error, span));
+                }
+            }
+        }
+        statements
+    }
+
+    /// Пропускает остаток ошибочной инструкции, начавшейся с токена `start`:
+    /// до конца строки вне скобок, открытых в ней, либо до границы объемлющего блока.
+    fn synchronize(&mut self, start: usize, stop: &impl Fn(&Parser) -> bool) {
+        let mut depth = 0usize;
+        for token in &self.tokens[start..self.position] {
+            match token.kind {
+                TokenKind::LParen | TokenKind::LBracket | TokenKind::LBrace => depth += 1,
+                TokenKind::RParen | TokenKind::RBracket | TokenKind::RBrace => {
+                    depth = depth.saturating_sub(1)
+                }
+                _ => {}
+            }
+        }
+
+        while !self.is_at_end() {
+            match self.peek_kind() {
+                TokenKind::Newline | TokenKind::Semicolon if depth == 0 => break,
+                _ if depth == 0 && stop(self) => break,
+                TokenKind::LParen | TokenKind::LBracket | TokenKind::LBrace => depth += 1,
+                TokenKind::RParen | TokenKind::RBracket | TokenKind::RBrace => {
+                    depth = depth.saturating_sub(1)
+                }
+                _ => {}
+            }
+            self.advance();
+        }
+    }
+
+    fn skip_separators(&mut self) {
+        while matches!(
+            self.peek_kind(),
+            TokenKind::Newline | TokenKind::Semicolon | TokenKind::Comma
+        ) {
+            self.advance();
+        }
+    }
+
+    /// Инструкция уже поглотила перевод строки (так заканчиваются блоки с отступами).
+    fn ended_with_newline(&self) -> bool {
+        self.position > 0 && self.previous().kind == TokenKind::Newline
+    }
+
+    pub(super) fn parse_statement(&mut self) -> ParseResult<Stmt> {
+        let start = self.peek().span;
+
+        let kind = match self.peek_kind().clone() {
+            TokenKind::Header { name, body } => {
+                self.advance();
+                StmtKind::Header { name, body }
+            }
+            TokenKind::Keyword(Keyword::Global | Keyword::Local)
+                if self.nth(1).kind == TokenKind::Dot =>
+            {
+                self.parse_entry_or_expression()?
+            }
+            TokenKind::Keyword(
+                Keyword::Const
+                | Keyword::Let
+                | Keyword::Local
+                | Keyword::Global
+                | Keyword::Static
+                | Keyword::Public
+                | Keyword::Private
+                | Keyword::Strict
+                | Keyword::Async
+                | Keyword::Class
+                | Keyword::Structure,
+            ) => self.parse_declaration()?,
+            TokenKind::Keyword(Keyword::Function | Keyword::Procedure | Keyword::Iterator)
+                if matches!(self.nth(1).kind, TokenKind::Identifier(_)) =>
+            {
+                self.parse_declaration()?
+            }
+            TokenKind::Section(_) if self.nth(1).kind == TokenKind::LBrace => {
+                self.parse_declaration()?
+            }
+            TokenKind::Identifier(name)
+                if name == "include" && matches!(self.nth(1).kind, TokenKind::String { .. }) =>
+            {
+                self.parse_include()?
+            }
+            TokenKind::Identifier(name)
+                if name == "use"
+                    && matches!(&self.nth(1).kind, TokenKind::Identifier(word) if word == "schema")
+                    && matches!(self.nth(2).kind, TokenKind::String { .. }) =>
+            {
+                self.advance();
+                self.advance();
+                StmtKind::UseSchema {
+                    path: self.parse_static_path("use schema")?,
+                }
+            }
+            TokenKind::Identifier(name)
+                if name == "provide" && self.nth(1).kind == TokenKind::LBrace =>
+            {
+                self.parse_provide()?
+            }
+            TokenKind::Identifier(_) if self.expansion_follows() => {
+                StmtKind::Expression(self.parse_expansion()?)
+            }
+            TokenKind::Keyword(Keyword::If) => self.parse_if()?,
+            TokenKind::Keyword(Keyword::For) => self.parse_for()?,
+            TokenKind::Keyword(Keyword::Loop) => {
+                self.advance();
+                let count = if self.check(&TokenKind::LBrace) {
+                    None
+                } else {
+                    Some(self.parse_expression()?)
+                };
+                StmtKind::Loop {
+                    count,
+                    body: self.parse_block()?,
+                }
+            }
+            TokenKind::Keyword(Keyword::While | Keyword::Until) => {
+                let until = self.advance().kind == TokenKind::Keyword(Keyword::Until);
+                let condition = self.parse_expression()?;
+                StmtKind::While {
+                    condition: negate_if(until, condition),
+                    body: self.parse_block()?,
+                    do_while: false,
+                }
+            }
+            TokenKind::Keyword(Keyword::Do) => {
+                self.advance();
+                let body = self.parse_block()?;
+                self.skip_newlines();
+                let until = match self.peek_kind() {
+                    TokenKind::Keyword(Keyword::While) => false,
+                    TokenKind::Keyword(Keyword::Until) => true,
+                    _ => return Err(self.expected("«while» после блока «do»")),
+                };
+                self.advance();
+                let condition = self.parse_expression()?;
+                StmtKind::While {
+                    condition: negate_if(until, condition),
+                    body,
+                    do_while: true,
+                }
+            }
+            TokenKind::Keyword(Keyword::Match) => self.parse_match()?,
+            TokenKind::Keyword(Keyword::Try) => self.parse_try()?,
+            TokenKind::Keyword(Keyword::Yield) => self.parse_yield()?,
+            TokenKind::Keyword(Keyword::Return) => StmtKind::Return(self.parse_jump_value()?),
+            TokenKind::Keyword(Keyword::Break) => StmtKind::Break(self.parse_jump_value()?),
+            TokenKind::Keyword(Keyword::Continue) => StmtKind::Continue(self.parse_jump_value()?),
+            TokenKind::Keyword(Keyword::Cancel) => StmtKind::Cancel(self.parse_jump_value()?),
+            TokenKind::Keyword(Keyword::Throw) => StmtKind::Throw(self.parse_jump_value()?),
+            TokenKind::LBrace => StmtKind::Block(self.parse_brace_block()?),
+            TokenKind::LBracket => self.parse_multi_key_entry()?,
+            TokenKind::Identifier(_) | TokenKind::String { .. } | TokenKind::Dollar => {
+                if self.is_method_shorthand() {
+                    StmtKind::Routine(self.parse_routine(RoutineKind::Function, Vec::new())?)
+                } else {
+                    self.parse_entry_or_expression()?
+                }
+            }
+            _ => StmtKind::Expression(self.parse_expression()?),
+        };
+
+        Ok(Node::new(kind, start.to(self.previous().span)))
+    }
+
+    /// `include "./file.json"`; путь — строка без интерполяции.
+    fn parse_include(&mut self) -> ParseResult<StmtKind> {
+        self.advance();
+        let path = self.parse_static_path("include")?;
+        Ok(StmtKind::Include { path })
+    }
+
+    /// Путь директивы — строка, известная до вычисления.
+    fn parse_static_path(&mut self, directive: &str) -> ParseResult<String> {
+        let token = self.advance();
+        let parts = match token.kind {
+            TokenKind::String { parts, .. } => parts,
+            _ => unreachable!("перед директивой проверено, что дальше строка"),
+        };
+        let mut path = String::new();
+        for part in parts {
+            match part {
+                StringPart::Text(text) => path.push_str(&text),
+                StringPart::Interpolation(_) | StringPart::CodePoint(_) => {
+                    return Err(self.invalid(
+                        format!(
+                        "путь в {} должен быть известен до вычисления, интерполяция недопустима",
+                        directive
+                    ),
+                        token.span,
+                    ))
+                }
+            }
+        }
+        Ok(path)
+    }
+
+    /// `provide { key_1, key_2 }`; ключи разделяются запятыми или переводами строк.
+    fn parse_provide(&mut self) -> ParseResult<StmtKind> {
+        self.advance();
+        self.expect(&TokenKind::LBrace)?;
+        let mut keys = Vec::new();
+        loop {
+            self.skip_newlines();
+            if self.eat(&TokenKind::RBrace) {
+                break;
+            }
+            let (key, _) = self.expect_identifier()?;
+            keys.push(key);
+            self.skip_newlines();
+            self.eat(&TokenKind::Comma);
+        }
+        Ok(StmtKind::Provide { keys })
+    }
+
+    fn parse_jump_value(&mut self) -> ParseResult<Option<Expr>> {
+        self.advance();
+        if self.at_statement_end() {
+            Ok(None)
+        } else {
+            Ok(Some(self.parse_expression()?))
+        }
+    }
+
+    // --- Объявления ---
+
+    fn parse_modifiers(&mut self) -> Vec<Modifier> {
+        let mut modifiers = Vec::new();
+        loop {
+            let modifier = match self.peek_kind() {
+                TokenKind::Keyword(Keyword::Local) => Modifier::Local,
+                TokenKind::Keyword(Keyword::Global) => Modifier::Global,
+                TokenKind::Keyword(Keyword::Static) => Modifier::Static,
+                TokenKind::Keyword(Keyword::Public) => Modifier::Public,
+                TokenKind::Keyword(Keyword::Private) => Modifier::Private,
+                TokenKind::Keyword(Keyword::Strict) => Modifier::Strict,
+                TokenKind::Keyword(Keyword::Async) => Modifier::Async,
+                _ => return modifiers,
+            };
+            self.advance();
+            modifiers.push(modifier);
+        }
+    }
+
+    /// Модификаторы и то, к чему они относятся: переменная, функция, класс или секция.
+    fn parse_declaration(&mut self) -> ParseResult<StmtKind> {
+        let modifiers = self.parse_modifiers();
+
+        match self.peek_kind().clone() {
+            TokenKind::Keyword(Keyword::Const | Keyword::Let) => self.parse_var_decl(modifiers),
+            TokenKind::Keyword(Keyword::Function) => Ok(StmtKind::Routine(
+                self.parse_routine(RoutineKind::Function, modifiers)?,
+            )),
+            TokenKind::Keyword(Keyword::Procedure) => Ok(StmtKind::Routine(
+                self.parse_routine(RoutineKind::Procedure, modifiers)?,
+            )),
+            TokenKind::Keyword(Keyword::Iterator) => Ok(StmtKind::Routine(
+                self.parse_routine(RoutineKind::Iterator, modifiers)?,
+            )),
+            TokenKind::Keyword(Keyword::Class) => self.parse_class(ClassKind::Class, modifiers),
+            TokenKind::Keyword(Keyword::Structure) => {
+                self.parse_class(ClassKind::Structure, modifiers)
+            }
+            TokenKind::Section(name) => {
+                self.advance();
+                Ok(StmtKind::Section {
+                    name,
+                    modifiers,
+                    body: self.parse_brace_block()?,
+                })
+            }
+            // `static Init() { ... }` внутри класса
+            TokenKind::Identifier(_) if self.is_method_shorthand() => Ok(StmtKind::Routine(
+                self.parse_routine(RoutineKind::Function, modifiers)?,
+            )),
+            // `local code-point = ...`, `public version = 1` — объявление без const/let
+            TokenKind::Identifier(_) | TokenKind::Amp | TokenKind::LParen
+                if !modifiers.is_empty() =>
+            {
+                self.parse_var_decl(modifiers)
+            }
+            _ => Err(self.expected("объявление")),
+        }
+    }
+
+    fn parse_var_decl(&mut self, modifiers: Vec<Modifier>) -> ParseResult<StmtKind> {
+        let kind = if self.eat_keyword(Keyword::Const) {
+            DeclKind::Const
+        } else {
+            self.eat_keyword(Keyword::Let);
+            DeclKind::Let
+        };
+        let by_ref = self.eat(&TokenKind::Amp);
+
+        let pattern = if self.check(&TokenKind::LParen) {
+            Pattern::Tuple(self.parse_name_tuple()?)
+        } else {
+            let (name, _) = self.expect_identifier()?;
+            // `const converter(input<Volume>: 1000L):<Volume> { ... }`
+            if self.check(&TokenKind::LParen) && self.is_adjacent() {
+                let routine = self.parse_routine_rest(
+                    RoutineKind::Function,
+                    modifiers,
+                    Some(name),
+                    Vec::new(),
+                )?;
+                return Ok(StmtKind::Routine(routine));
+            }
+            Pattern::Name(name)
+        };
+
+        let ty = self.parse_optional_type_annotation()?;
+        let attributes = self.parse_optional_attributes()?;
+        let optional = self.check(&TokenKind::Question) && self.is_adjacent();
+        if optional {
+            self.advance();
+        }
+
+        let mut lazy = false;
+        let value = if self.eat(&TokenKind::Assign) || self.eat(&TokenKind::Colon) {
+            Some(self.parse_value()?)
+        } else if self.eat(&TokenKind::FatArrow) {
+            lazy = true;
+            Some(self.parse_value()?)
+        } else if self.at_statement_end() && !self.has_indented_block() {
+            None
+        } else {
+            Some(self.parse_value()?)
+        };
+
+        if let Pattern::Name(name) = &pattern {
+            let operator_value =
+                matches!(&value, Some(expr) if matches!(expr.kind, ExprKind::Operator(_)));
+            if operator_value || is_operator_type(ty.as_ref()) {
+                self.operator_names.insert(name.clone());
+            }
+        }
+
+        Ok(StmtKind::VarDecl(VarDecl {
+            kind,
+            modifiers,
+            pattern,
+            by_ref,
+            ty,
+            attributes,
+            optional,
+            value,
+            lazy,
+        }))
+    }
+
+    /// `(a, b)` — кортеж имён в объявлениях и циклах.
+    pub(super) fn parse_name_tuple(&mut self) -> ParseResult<Vec<String>> {
+        self.expect(&TokenKind::LParen)?;
+        let mut names = Vec::new();
+        loop {
+            self.skip_newlines();
+            if self.eat(&TokenKind::RParen) {
+                break;
+            }
+            names.push(self.expect_identifier()?.0);
+            self.skip_newlines();
+            if !self.eat(&TokenKind::Comma) {
+                self.skip_newlines();
+                self.expect(&TokenKind::RParen)?;
+                break;
+            }
+        }
+        Ok(names)
+    }
+
+    /// `name(...) {` или `name(...):<Type> {` — короткая запись метода.
+    fn is_method_shorthand(&self) -> bool {
+        if !matches!(self.peek_kind(), TokenKind::Identifier(_))
+            || self.nth(1).kind != TokenKind::LParen
+            || !self.nth(1).is_adjacent_to(self.nth(0))
+        {
+            return false;
+        }
+        let close = match self.matching_close(1) {
+            Some(close) => close,
+            None => return false,
+        };
+        match self.tokens.get(close + 1).map(|t| &t.kind) {
+            Some(TokenKind::LBrace) => true,
+            Some(TokenKind::Colon) => {
+                matches!(
+                    self.tokens.get(close + 2).map(|t| &t.kind),
+                    Some(TokenKind::Less)
+                )
+            }
+            _ => false,
+        }
+    }
+
+    pub(super) fn parse_routine(
+        &mut self,
+        kind: RoutineKind,
+        modifiers: Vec<Modifier>,
+    ) -> ParseResult<RoutineDecl> {
+        if matches!(
+            self.peek_kind(),
+            TokenKind::Keyword(Keyword::Function | Keyword::Procedure | Keyword::Iterator)
+        ) {
+            self.advance();
+        }
+        let name = match self.peek_kind().clone() {
+            TokenKind::Identifier(name) => {
+                self.advance();
+                Some(name)
+            }
+            _ => None,
+        };
+        let generics = if self.check(&TokenKind::Less) {
+            self.parse_generic_params()?
+        } else {
+            Vec::new()
+        };
+        self.parse_routine_rest(kind, modifiers, name, generics)
+    }
+
+    fn parse_routine_rest(
+        &mut self,
+        kind: RoutineKind,
+        modifiers: Vec<Modifier>,
+        name: Option<String>,
+        generics: Vec<GenericParam>,
+    ) -> ParseResult<RoutineDecl> {
+        let params = self.parse_params()?;
+        let return_type = if self.eat(&TokenKind::Colon) {
+            Some(self.parse_type_annotation()?)
+        } else {
+            None
+        };
+        let body = if self.eat(&TokenKind::FatArrow) {
+            Body::Expression(self.parse_expression()?)
+        } else {
+            Body::Block(self.parse_brace_block()?)
+        };
+        Ok(RoutineDecl {
+            kind,
+            modifiers,
+            name,
+            generics,
+            params,
+            return_type,
+            body,
+        })
+    }
+
+    /// `<T: Number, U: Number>`
+    fn parse_generic_params(&mut self) -> ParseResult<Vec<GenericParam>> {
+        self.expect(&TokenKind::Less)?;
+        let mut generics = Vec::new();
+        loop {
+            let (name, _) = self.expect_identifier()?;
+            let bound = if self.eat(&TokenKind::Colon) {
+                Some(self.parse_type_expr()?)
+            } else {
+                None
+            };
+            generics.push(GenericParam { name, bound });
+            if !self.eat(&TokenKind::Comma) {
+                break;
+            }
+        }
+        self.expect_type_close()?;
+        Ok(generics)
+    }
+
+    /// Параметры функции. Значение по умолчанию задаётся через `=`, `:` или просто пробел:
+    /// `a<float>`, `b: 0`, `c<operator> +`, `&argument<Int>?`, `args...`.
+    pub(super) fn parse_params(&mut self) -> ParseResult<Vec<Param>> {
+        self.expect(&TokenKind::LParen)?;
+        let mut params = Vec::new();
+        loop {
+            self.skip_newlines();
+            if self.eat(&TokenKind::RParen) {
+                break;
+            }
+            let start = self.peek().span;
+            let mut variadic = self.eat(&TokenKind::Ellipsis);
+            let by_ref = self.eat(&TokenKind::Amp);
+            self.eat(&TokenKind::Dollar);
+            let (name, _) = self.expect_identifier()?;
+            variadic |= self.eat(&TokenKind::Ellipsis);
+            let mut ty = self.parse_optional_type_annotation()?;
+            let optional = self.eat(&TokenKind::Question);
+
+            let default = if self.eat(&TokenKind::Assign) {
+                Some(self.parse_expression()?)
+            } else if self.eat(&TokenKind::Colon) {
+                // `amount:<Float>` — тип после двоеточия, иначе значение по умолчанию
+                if self.check(&TokenKind::Less) && ty.is_none() {
+                    ty = Some(self.parse_type_annotation()?);
+                    None
+                } else {
+                    Some(self.parse_expression()?)
+                }
+            } else if matches!(
+                self.peek_kind(),
+                TokenKind::Comma | TokenKind::RParen | TokenKind::Newline
+            ) {
+                None
+            } else {
+                Some(self.parse_expression()?)
+            };
+
+            if is_operator_type(ty.as_ref()) {
+                self.operator_names.insert(name.clone());
+            }
+            params.push(Param {
+                name,
+                by_ref,
+                variadic,
+                ty,
+                optional,
+                default,
+                span: start.to(self.previous().span),
+            });
+
+            self.skip_newlines();
+            if !self.eat(&TokenKind::Comma) {
+                self.skip_newlines();
+                self.expect(&TokenKind::RParen)?;
+                break;
+            }
+        }
+        Ok(params)
+    }
+
+    fn parse_class(&mut self, kind: ClassKind, modifiers: Vec<Modifier>) -> ParseResult<StmtKind> {
+        self.advance();
+        let (name, _) = self.expect_identifier()?;
+        let extends = if self.eat_keyword(Keyword::Extends) {
+            Some(self.parse_type_expr()?)
+        } else {
+            None
+        };
+        Ok(StmtKind::Class(ClassDecl {
+            kind,
+            modifiers,
+            name,
+            extends,
+            body: self.parse_brace_block()?,
+        }))
+    }
+
+    // --- Типы и атрибуты ---
+
+    /// `<Type>` вплотную к имени: `key<Length>`. С пробелом `<` — это сравнение.
+    pub(super) fn parse_optional_type_annotation(&mut self) -> ParseResult<Option<TypeExpr>> {
+        if self.check(&TokenKind::Less) && self.is_adjacent() {
+            Ok(Some(self.parse_type_annotation()?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    pub(super) fn parse_type_annotation(&mut self) -> ParseResult<TypeExpr> {
+        self.expect(&TokenKind::Less)?;
+        let ty = self.parse_type_expr()?;
+        self.expect_type_close()?;
+        Ok(ty)
+    }
+
+    /// Закрывающая `>` указателя типа. В `key<Length>= 15m` лексер видит `>=`,
+    /// поэтому такой токен расщепляется на `>` и `=`.
+    pub(super) fn expect_type_close(&mut self) -> ParseResult<()> {
+        match self.peek_kind() {
+            TokenKind::Greater => {
+                self.advance();
+                Ok(())
+            }
+            TokenKind::GreaterEq => {
+                let token = &mut self.tokens[self.position];
+                token.kind = TokenKind::Assign;
+                token.span.start.offset += 1;
+                token.span.start.column += 1;
+                Ok(())
+            }
+            _ => Err(self.expected("«>»")),
+        }
+    }
+
+    pub(super) fn parse_type_expr(&mut self) -> ParseResult<TypeExpr> {
+        let start = self.peek().span;
+
+        let mut ty = if self.check(&TokenKind::LParen) {
+            self.advance();
+            let mut fields = Vec::new();
+            loop {
+                if self.eat(&TokenKind::RParen) {
+                    break;
+                }
+                let field = match self.peek_kind().clone() {
+                    TokenKind::Identifier(name)
+                        if self.nth(1).kind == TokenKind::Less
+                            && self.nth(1).is_adjacent_to(self.nth(0)) =>
+                    {
+                        self.advance();
+                        (Some(name), self.parse_type_annotation()?)
+                    }
+                    _ => (None, self.parse_type_expr()?),
+                };
+                fields.push(field);
+                if !self.eat(&TokenKind::Comma) {
+                    self.expect(&TokenKind::RParen)?;
+                    break;
+                }
+            }
+            Node::new(TypeExprKind::Tuple(fields), start.to(self.previous().span))
+        } else {
+            let mut path = vec![self.expect_type_name()?];
+            while self.eat(&TokenKind::ColonColon) {
+                path.push(self.expect_type_name()?);
+            }
+            let mut arguments = Vec::new();
+            if self.check(&TokenKind::Less) && self.is_adjacent() {
+                self.advance();
+                loop {
+                    arguments.push(self.parse_type_expr()?);
+                    if !self.eat(&TokenKind::Comma) {
+                        break;
+                    }
+                }
+                self.expect_type_close()?;
+            }
+            Node::new(
+                TypeExprKind::Named { path, arguments },
+                start.to(self.previous().span),
+            )
+        };
+
+        // Суффиксы `[]` и `{}`: `String[]{}` — словарь массивов строк
+        loop {
+            if self.check(&TokenKind::LBracket) && self.nth(1).kind == TokenKind::RBracket {
+                self.advance();
+                self.advance();
+                ty = Node::new(
+                    TypeExprKind::Array(Box::new(ty)),
+                    start.to(self.previous().span),
+                );
+            } else if self.check(&TokenKind::LBrace) && self.nth(1).kind == TokenKind::RBrace {
+                self.advance();
+                self.advance();
+                ty = Node::new(
+                    TypeExprKind::Dictionary(Box::new(ty)),
+                    start.to(self.previous().span),
+                );
+            } else {
+                break;
+            }
+        }
+
+        if self.eat(&TokenKind::StarStar) {
+            let negative = self.eat(&TokenKind::Minus);
+            let exponent = match self.peek_kind().clone() {
+                TokenKind::Number {
+                    literal,
+                    unit: None,
+                } => {
+                    self.advance();
+                    literal.parse::<i32>().map_err(|_| {
+                        self.invalid("некорректная степень типа", self.previous().span)
+                    })?
+                }
+                _ => return Err(self.expected("целая степень")),
+            };
+            let exponent = if negative { -exponent } else { exponent };
+            ty = Node::new(
+                TypeExprKind::Power(Box::new(ty), exponent),
+                start.to(self.previous().span),
+            );
+        }
+
+        Ok(ty)
+    }
+
+    /// Имя типа: обычное имя или ключевое слово-тип (`none`, `nil`, `auto`, `undefined`).
+    fn expect_type_name(&mut self) -> ParseResult<String> {
+        match self.peek_kind().clone() {
+            TokenKind::Identifier(name) => {
+                self.advance();
+                Ok(name)
+            }
+            TokenKind::Keyword(
+                keyword @ (Keyword::None | Keyword::Nil | Keyword::Auto | Keyword::Undefined),
+            ) => {
+                self.advance();
+                Ok(keyword.as_str().to_string())
+            }
+            _ => Err(self.expected("имя типа")),
+        }
+    }
+
+    /// `@[unit: nm, value > 0m, clamp]`
+    pub(super) fn parse_optional_attributes(&mut self) -> ParseResult<Vec<Attribute>> {
+        if !self.eat(&TokenKind::AttributeOpen) {
+            return Ok(Vec::new());
+        }
+        let mut attributes = Vec::new();
+        loop {
+            self.skip_newlines();
+            if self.eat(&TokenKind::RBracket) {
+                break;
+            }
+            let start = self.peek().span;
+            let name = match self.peek_kind().clone() {
+                TokenKind::Identifier(name) if self.nth(1).kind == TokenKind::Colon => {
+                    self.advance();
+                    self.advance();
+                    Some(name)
+                }
+                _ => None,
+            };
+            let value = self.parse_expression()?;
+            attributes.push(Attribute {
+                name,
+                value,
+                span: start.to(self.previous().span),
+            });
+            self.skip_newlines();
+            if !self.eat(&TokenKind::Comma) {
+                self.skip_newlines();
+                self.expect(&TokenKind::RBracket)?;
+                break;
+            }
+        }
+        Ok(attributes)
+    }
+
+    // --- Элементы данных и присваивания ---
+
+    /// Инструкция, начинающаяся с имени или строки: элемент данных `key value`,
+    /// присваивание `obj.key = value`, либо просто выражение `print(x)`.
+    fn parse_entry_or_expression(&mut self) -> ParseResult<StmtKind> {
+        let key_start = self.peek().span;
+        let target = self.parse_without_lambda(|parser| parser.parse_postfix())?;
+        let key = entry_key(&target);
+
+        let ty = if key.is_some() {
+            self.parse_optional_type_annotation()?
+        } else {
+            None
+        };
+        let attributes = self.parse_optional_attributes()?;
+        let optional = self.check(&TokenKind::Question) && self.is_adjacent();
+        if optional {
+            self.advance();
+        }
+        let decorated = ty.is_some() || !attributes.is_empty() || optional;
+
+        let assign_op = match self.peek_kind() {
+            TokenKind::PlusAssign => Some(AssignOp::Add),
+            TokenKind::MinusAssign => Some(AssignOp::Sub),
+            TokenKind::StarAssign => Some(AssignOp::Mul),
+            TokenKind::SlashAssign => Some(AssignOp::Div),
+            TokenKind::PercentAssign => Some(AssignOp::Mod),
+            TokenKind::DotAssign => Some(AssignOp::Concat),
+            TokenKind::AmpAssign => Some(AssignOp::And),
+            TokenKind::PlusPlus => Some(AssignOp::Increment),
+            TokenKind::MinusMinus => Some(AssignOp::Decrement),
+            _ => None,
+        };
+        if let Some(op) = assign_op {
+            self.advance();
+            let value = match op {
+                AssignOp::Increment | AssignOp::Decrement => None,
+                _ => Some(self.parse_expression()?),
+            };
+            return Ok(StmtKind::Assign { target, op, value });
+        }
+
+        let make_entry = |key: EntryKey, value: Expr, lazy: bool| {
+            StmtKind::Entry(Entry {
+                key,
+                key_span: key_start.to(target.span),
+                ty: ty.clone(),
+                attributes: attributes.clone(),
+                optional,
+                value,
+                lazy,
+            })
+        };
+
+        if self.eat(&TokenKind::Assign) || self.eat(&TokenKind::Colon) {
+            let value = self.parse_value()?;
+            return Ok(match key {
+                Some(key) => make_entry(key, value, false),
+                None => StmtKind::Assign {
+                    target,
+                    op: AssignOp::Set,
+                    value: Some(value),
+                },
+            });
+        }
+
+        if let Some(key) = key.clone() {
+            if self.eat(&TokenKind::FatArrow) {
+                let value = self.parse_value()?;
+                return Ok(make_entry(key, value, true));
+            }
+        }
+
+        if self.at_statement_end() && !self.has_indented_block() {
+            if decorated {
+                return Err(self.expected("значение"));
+            }
+            return Ok(StmtKind::Expression(target));
+        }
+
+        if !decorated && self.continues_as_binary() {
+            let expression = self.parse_expression_from(target)?;
+            return Ok(StmtKind::Expression(expression));
+        }
+
+        match key {
+            Some(key) => {
+                let value = self.parse_value()?;
+                Ok(make_entry(key, value, false))
+            }
+            // `data.key { ... }`, `merged[k] value` — запись в поле, как с `=`
+            None if !decorated
+                && matches!(
+                    target.kind,
+                    ExprKind::Member { .. } | ExprKind::Index { .. }
+                ) =>
+            {
+                Ok(StmtKind::Assign {
+                    target,
+                    op: AssignOp::Set,
+                    value: Some(self.parse_value()?),
+                })
+            }
+            None => Err(self.invalid(
+                "ключом элемента может быть только имя или строка",
+                target.span,
+            )),
+        }
+    }
+
+    /// После цели идёт бинарный оператор, а не значение элемента.
+    /// `key -15m` — значение со знаком, а `a - b` и `a -b`… различаются пробелами.
+    fn continues_as_binary(&self) -> bool {
+        if binary_operator(self.peek_kind()).is_none() {
+            return false;
+        }
+        let signed_value = matches!(self.peek_kind(), TokenKind::Minus | TokenKind::Plus)
+            && !self.is_adjacent()
+            && self.nth(1).is_adjacent_to(self.nth(0));
+        // `key <immut>{...}` и `key [a, b]` — это значения
+        let type_value = self.check(&TokenKind::Less) && !self.is_adjacent();
+        !signed_value && !type_value
+    }
+
+    /// `[key, key2] value` — несколько ключей с одним значением.
+    fn parse_multi_key_entry(&mut self) -> ParseResult<StmtKind> {
+        let start = self.peek().span;
+        let array = self.parse_postfix()?;
+        if self.at_statement_end() {
+            return Ok(StmtKind::Expression(array));
+        }
+        let elements = match &array.kind {
+            ExprKind::Array(elements) => elements,
+            _ => return Ok(StmtKind::Expression(self.parse_expression_from(array)?)),
+        };
+        let mut keys = Vec::new();
+        for element in elements {
+            match entry_key(element) {
+                Some(key) => keys.push(key),
+                None => {
+                    return Err(self.invalid(
+                        "в массиве ключей допустимы только имена и строки",
+                        element.span,
+                    ))
+                }
+            }
+        }
+        self.eat(&TokenKind::Assign);
+        let value = self.parse_value()?;
+        Ok(StmtKind::Entry(Entry {
+            key: EntryKey::Multiple(keys),
+            key_span: start.to(array.span),
+            ty: None,
+            attributes: Vec::new(),
+            optional: false,
+            value,
+            lazy: false,
+        }))
+    }
+
+    /// Значение элемента или объявления. Если значения на строке нет, а следующие строки
+    /// сдвинуты вправо — это словарь, заданный отступами.
+    pub(super) fn parse_value(&mut self) -> ParseResult<Expr> {
+        if self.check(&TokenKind::Newline) && self.has_indented_block() {
+            let block = self.parse_indented_block()?;
+            return Ok(Node::new(ExprKind::Dictionary(block.kind), block.span));
+        }
+        self.parse_expression()
+    }
+
+    // --- Блоки ---
+
+    /// Блок в фигурных скобках, блок с отступом или одна инструкция на той же строке.
+    pub(super) fn parse_block(&mut self) -> ParseResult<Block> {
+        if self.check(&TokenKind::LBrace) {
+            return self.parse_brace_block();
+        }
+        if self.check(&TokenKind::Newline) {
+            if self.has_indented_block() {
+                return self.parse_indented_block();
+            }
+            return Err(self.expected("блок"));
+        }
+        let statement = self.parse_statement()?;
+        let span = statement.span;
+        Ok(Node::new(vec![statement], span))
+    }
+
+    pub(super) fn parse_brace_block(&mut self) -> ParseResult<Block> {
+        let start = self.expect(&TokenKind::LBrace)?.span;
+        let statements = self.parse_statements(|parser| parser.check(&TokenKind::RBrace));
+        let end = self.expect(&TokenKind::RBrace)?.span;
+        Ok(Node::new(statements, start.to(end)))
+    }
+
+    /// Следующая непустая строка сдвинута правее текущей.
+    fn has_indented_block(&self) -> bool {
+        if !self.check(&TokenKind::Newline) || self.position == 0 {
+            return false;
+        }
+        let base = self.line_indent(self.position - 1);
+        let mut n = 0;
+        while self.nth(n).kind == TokenKind::Newline {
+            n += 1;
+        }
+        let first = self.nth(n);
+        first.kind != TokenKind::Eof && first.span.start.column > base
+    }
+
+    fn parse_indented_block(&mut self) -> ParseResult<Block> {
+        let start = self.peek().span;
+        self.skip_newlines();
+        let indent = self.peek().span.start.column;
+        let statements =
+            self.parse_statements(|parser| parser.line_indent(parser.position) != indent);
+        let end = statements.last().map_or(start, |s| s.span);
+        Ok(Node::new(statements, start.to(end)))
+    }
+
+    // --- Управляющие конструкции ---
+
+    fn parse_if(&mut self) -> ParseResult<StmtKind> {
+        self.expect(&TokenKind::Keyword(Keyword::If))?;
+        let mut branches = vec![(self.parse_expression()?, self.parse_block()?)];
+        let mut otherwise = None;
+
+        loop {
+            match self.peek_past_newlines() {
+                TokenKind::Keyword(Keyword::Elif) => {
+                    self.skip_newlines();
+                    self.advance();
+                    branches.push((self.parse_expression()?, self.parse_block()?));
+                }
+                TokenKind::Keyword(Keyword::Else) => {
+                    self.skip_newlines();
+                    self.advance();
+                    // `else if` — то же самое, что `elif`
+                    if self.eat_keyword(Keyword::If) {
+                        branches.push((self.parse_expression()?, self.parse_block()?));
+                        continue;
+                    }
+                    otherwise = Some(self.parse_block()?);
+                    break;
+                }
+                _ => break,
+            }
+        }
+
+        Ok(StmtKind::If {
+            branches,
+            otherwise,
+        })
+    }
+
+    /// `if` внутри `${...}`: ветки — выражения, в скобках или без, они становятся
+    /// тернарным оператором. Без `else` получается пустая строка.
+    pub(super) fn parse_if_expression(&mut self) -> ParseResult<Expr> {
+        let start = self.expect(&TokenKind::Keyword(Keyword::If))?.span;
+        let mut branches = vec![(self.parse_expression()?, self.parse_branch_value()?)];
+        let mut otherwise = None;
+        while self.eat_keyword(Keyword::Else) {
+            if self.eat_keyword(Keyword::If) {
+                branches.push((self.parse_expression()?, self.parse_branch_value()?));
+                continue;
+            }
+            otherwise = Some(self.parse_branch_value()?);
+            break;
+        }
+        let span = start.to(self.previous().span);
+        let mut result = otherwise.unwrap_or_else(|| {
+            Node::new(
+                ExprKind::String {
+                    quote: QuoteStyle::Double,
+                    segments: Vec::new(),
+                },
+                span,
+            )
+        });
+        for (condition, value) in branches.into_iter().rev() {
+            result = Node::new(
+                ExprKind::Ternary {
+                    condition: Box::new(condition),
+                    then_branch: Box::new(value),
+                    else_branch: Box::new(result),
+                },
+                span,
+            );
+        }
+        Ok(result)
+    }
+
+    /// Значение ветки `if` внутри строки: `"готово"` или `{ "готово" }`.
+    fn parse_branch_value(&mut self) -> ParseResult<Expr> {
+        if !self.eat(&TokenKind::LBrace) {
+            return self.parse_expression();
+        }
+        let value = self.parse_expression()?;
+        self.expect(&TokenKind::RBrace)?;
+        Ok(value)
+    }
+
+    fn parse_for(&mut self) -> ParseResult<StmtKind> {
+        self.expect(&TokenKind::Keyword(Keyword::For))?;
+        let pattern = if self.check(&TokenKind::LParen) {
+            Pattern::Tuple(self.parse_name_tuple()?)
+        } else {
+            Pattern::Name(self.expect_identifier()?.0)
+        };
+        self.expect(&TokenKind::Keyword(Keyword::In))?;
+        let iterable = self.parse_expression()?;
+        Ok(StmtKind::For {
+            pattern,
+            iterable,
+            body: self.parse_block()?,
+        })
+    }
+
+    /// Дальше `generate`, `spread` или `filter` с источником и `as`: `spread (a, b) as ...`.
+    /// Без `as` это обычное имя, например ключ `filter "text"`.
+    pub(super) fn expansion_follows(&self) -> bool {
+        let TokenKind::Identifier(name) = self.peek_kind() else {
+            return false;
+        };
+        if ExpansionKind::from_name(name).is_none() {
+            return false;
+        }
+        let after_source = match self.nth(1).kind {
+            TokenKind::LParen | TokenKind::LBracket | TokenKind::LBrace => {
+                self.matching_close(1).map(|close| close + 1)
+            }
+            TokenKind::Identifier(_) => Some(self.position + 2),
+            _ => None,
+        };
+        after_source
+            .and_then(|index| self.tokens.get(index))
+            .is_some_and(|token| token.kind == TokenKind::Keyword(Keyword::As))
+    }
+
+    /// `generate (0 .. 7) as local let i { ... }`; `local`, `let` и `const` перед именами
+    /// допустимы, но ничего не меняют: имена всегда видны только в теле.
+    pub(super) fn parse_expansion(&mut self) -> ParseResult<Expr> {
+        let (name, start) = self.expect_identifier()?;
+        let kind = ExpansionKind::from_name(&name).expect("проверено в expansion_follows");
+        let source = self.parse_expression()?;
+        self.expect(&TokenKind::Keyword(Keyword::As))?;
+        self.eat_keyword(Keyword::Local);
+        let _ = self.eat_keyword(Keyword::Let) || self.eat_keyword(Keyword::Const);
+        let pattern = if self.check(&TokenKind::LParen) {
+            Pattern::Tuple(self.parse_name_tuple()?)
+        } else {
+            Pattern::Name(self.expect_identifier()?.0)
+        };
+        let body = self.parse_block()?;
+        let expansion = Expansion {
+            kind,
+            source,
+            pattern,
+            body,
+        };
+        Ok(Node::new(
+            ExprKind::Expansion(Box::new(expansion)),
+            start.to(self.previous().span),
+        ))
+    }
+
+    /// `yield value`, `yield name, value` или `yield [key], value`.
+    fn parse_yield(&mut self) -> ParseResult<StmtKind> {
+        self.expect(&TokenKind::Keyword(Keyword::Yield))?;
+        // Имя перед запятой — ключ как есть, без вычисления
+        if let TokenKind::Identifier(name) = self.peek_kind().clone() {
+            if self.nth(1).kind == TokenKind::Comma {
+                let span = self.advance().span;
+                self.advance();
+                let key = ExprKind::String {
+                    quote: QuoteStyle::Double,
+                    segments: vec![StringSegment::Text(name)],
+                };
+                return Ok(StmtKind::Yield {
+                    key: Some(Node::new(key, span)),
+                    value: self.parse_expression()?,
+                });
+            }
+        }
+        let value = self.parse_expression()?;
+        if !self.eat(&TokenKind::Comma) {
+            return Ok(StmtKind::Yield { key: None, value });
+        }
+        let key = match value.kind {
+            ExprKind::Array(mut items) if items.len() == 1 => items.pop().unwrap(),
+            _ => value,
+        };
+        Ok(StmtKind::Yield {
+            key: Some(key),
+            value: self.parse_expression()?,
+        })
+    }
+
+    fn parse_match(&mut self) -> ParseResult<StmtKind> {
+        self.expect(&TokenKind::Keyword(Keyword::Match))?;
+        let subject = self.parse_expression()?;
+        self.expect(&TokenKind::LBrace)?;
+        let mut arms = Vec::new();
+        loop {
+            self.skip_separators();
+            if self.eat(&TokenKind::RBrace) {
+                break;
+            }
+            let pattern = self.parse_without_lambda(|parser| parser.parse_expression())?;
+            let guard = if self.eat_keyword(Keyword::If) {
+                Some(self.parse_without_lambda(|parser| parser.parse_expression())?)
+            } else {
+                None
+            };
+            self.expect(&TokenKind::FatArrow)?;
+            let body = if self.check(&TokenKind::LBrace) {
+                Body::Block(self.parse_brace_block()?)
+            } else {
+                Body::Expression(self.parse_expression()?)
+            };
+            arms.push(MatchArm {
+                pattern,
+                guard,
+                body,
+            });
+        }
+        Ok(StmtKind::Match { subject, arms })
+    }
+
+    fn parse_try(&mut self) -> ParseResult<StmtKind> {
+        self.expect(&TokenKind::Keyword(Keyword::Try))?;
+        let body = self.parse_block()?;
+
+        let catch = if self.peek_past_newlines() == &TokenKind::Keyword(Keyword::Catch) {
+            self.skip_newlines();
+            self.advance();
+            // `catch as e`, `catch (e)` или `catch e`
+            let binding = if self.eat_keyword(Keyword::As) {
+                Some(self.expect_identifier()?.0)
+            } else if self.check(&TokenKind::LParen) {
+                self.parse_name_tuple()?.into_iter().next()
+            } else if let TokenKind::Identifier(name) = self.peek_kind().clone() {
+                self.advance();
+                Some(name)
+            } else {
+                None
+            };
+            Some(CatchClause {
+                binding,
+                body: self.parse_block()?,
+            })
+        } else {
+            None
+        };
+
+        let finally = if self.peek_past_newlines() == &TokenKind::Keyword(Keyword::Finally) {
+            self.skip_newlines();
+            self.advance();
+            Some(self.parse_block()?)
+        } else {
+            None
+        };
+
+        Ok(StmtKind::Try {
+            body,
+            catch,
+            finally,
+        })
+    }
+}
+
+/// Ключ элемента данных из выражения-цели, если оно им может быть.
+fn entry_key(target: &Expr) -> Option<EntryKey> {
+    match &target.kind {
+        ExprKind::Identifier(name) => Some(EntryKey::Name(name.clone())),
+        ExprKind::String { .. } => Some(EntryKey::String(target.clone())),
+        _ => None,
+    }
+}
+
+fn negate_if(negate: bool, condition: Expr) -> Expr {
+    if !negate {
+        return condition;
+    }
+    let span = condition.span;
+    Node::new(
+        ExprKind::Unary {
+            op: UnaryOp::Not,
+            operand: Box::new(condition),
+        },
+        span,
+    )
+}
+
+/// `<Operator>` в объявлении делает имя применимым в инфиксной позиции.
+fn is_operator_type(ty: Option<&TypeExpr>) -> bool {
+    ty.and_then(|ty| ty.kind.simple_name())
+        .is_some_and(|name| name.eq_ignore_ascii_case("operator"))
+}
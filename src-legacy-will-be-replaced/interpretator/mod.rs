@@ -1 +1,7 @@
+pub mod analysis;
+pub mod engine;
+pub mod frontend;
+pub mod modules;
 pub mod prototypes;
+pub mod runtime;
+pub mod stdlib;
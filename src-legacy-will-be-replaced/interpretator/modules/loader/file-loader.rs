@@ -0,0 +1,98 @@
+use crate::interpretator::engine::dialect::Dialect;
+use std::fs;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// Формат вставляемого файла, определяемый по расширению.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SourceFormat {
+    Json,
+    /// `.lacon` или `.llacon`; у второго — ограничения List LaCoN
+    Lacon(Dialect),
+    /// `.slacon` — схема SLaCoN
+    Schema,
+}
+
+impl SourceFormat {
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+        match extension.as_str() {
+            "json" => Some(SourceFormat::Json),
+            "lacon" | "llacon" => Some(SourceFormat::Lacon(Dialect::from_path(path))),
+            "slacon" => Some(SourceFormat::Schema),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum LoadError {
+    #[error("файлы «.{0}» не вставляются, ожидался .lacon, .llacon, .slacon или .json")]
+    UnsupportedFormat(String),
+    #[error("файл включает сам себя через {0}")]
+    Cycle(String),
+    #[error("{0}")]
+    Io(#[from] std::io::Error),
+}
+
+/// Прочитанный файл документа.
+#[derive(Debug, Clone)]
+pub struct SourceFile {
+    pub path: PathBuf,
+    pub source: String,
+    pub format: SourceFormat,
+}
+
+impl SourceFile {
+    /// Каталог файла: от него отсчитываются пути в его собственных `include`.
+    pub fn base_dir(&self) -> &Path {
+        self.path.parent().unwrap_or(Path::new("."))
+    }
+}
+
+/// Загрузчик файлов для `include`. Помнит цепочку файлов, через которую до него дошли,
+/// и отказывает в загрузке файла, который уже в ней есть.
+#[derive(Debug, Clone, Default)]
+pub struct FileLoader {
+    chain: Vec<PathBuf>,
+}
+
+impl FileLoader {
+    pub fn new() -> Self {
+        FileLoader::default()
+    }
+
+    pub fn load(&self, path: &Path) -> Result<SourceFile, LoadError> {
+        let format = SourceFormat::from_path(path).ok_or_else(|| {
+            let extension = path.extension().unwrap_or_default();
+            LoadError::UnsupportedFormat(extension.to_string_lossy().into_owned())
+        })?;
+        let identity = identity(path);
+        if self.chain.contains(&identity) {
+            let chain: Vec<_> = self
+                .chain
+                .iter()
+                .chain([&identity])
+                .map(|path| format!("«{}»", path.display()))
+                .collect();
+            return Err(LoadError::Cycle(chain.join(" → ")));
+        }
+        Ok(SourceFile {
+            path: path.to_path_buf(),
+            source: fs::read_to_string(path)?,
+            format,
+        })
+    }
+
+    /// Загрузчик для включений внутри файла `path`.
+    pub fn nested(&self, path: &Path) -> FileLoader {
+        let mut chain = self.chain.clone();
+        chain.push(identity(path));
+        FileLoader { chain }
+    }
+}
+
+/// Путь, по которому файлы сравниваются в цепочке: канонический, если файл существует.
+fn identity(path: &Path) -> PathBuf {
+    fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
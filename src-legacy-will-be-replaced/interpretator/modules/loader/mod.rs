@@ -0,0 +1,2 @@
+#[path = "file-loader.rs"]
+pub mod file_loader;
@@ -0,0 +1,2 @@
+#[path = "path-resolution.rs"]
+pub mod path_resolution;
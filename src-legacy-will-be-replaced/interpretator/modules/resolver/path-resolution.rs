@@ -0,0 +1,60 @@
+use std::path::{Component, Path, PathBuf};
+
+/// Путь из `include` относительно каталога включающего файла.
+///
+/// Абсолютный путь берётся как есть. `.` и `..` сворачиваются без обращения к диску,
+/// поэтому один и тот же файл, записанный по-разному, даёт один путь.
+pub fn resolve_include(base_dir: &Path, path: &str) -> PathBuf {
+    normalize(&base_dir.join(path))
+}
+
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                let parent = matches!(
+                    normalized.components().next_back(),
+                    Some(Component::Normal(_))
+                );
+                if parent {
+                    normalized.pop();
+                } else if !normalized.has_root() {
+                    normalized.push("..");
+                }
+            }
+            other => normalized.push(other.as_os_str()),
+        }
+    }
+    if normalized.as_os_str().is_empty() {
+        normalized.push(".");
+    }
+    normalized
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_include() {
+        let base = Path::new("/data/configs");
+        assert_eq!(
+            resolve_include(base, "./shared/keys.llacon"),
+            PathBuf::from("/data/configs/shared/keys.llacon")
+        );
+        assert_eq!(
+            resolve_include(base, "../common.lacon"),
+            PathBuf::from("/data/common.lacon")
+        );
+        assert_eq!(
+            resolve_include(base, "/etc/lacon/base.json"),
+            PathBuf::from("/etc/lacon/base.json")
+        );
+        assert_eq!(
+            resolve_include(Path::new("."), "../a/./b.lacon"),
+            PathBuf::from("../a/b.lacon")
+        );
+    }
+}
@@ -0,0 +1,41 @@
+use crate::interpretator::prototypes::types::object::date_time::DateTimeValue;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Источник текущего времени для `now()` и `today()`.
+///
+/// Хост подставляет свой через `Engine::with_clock`; тесты — [`FixedClock`],
+/// чтобы результат документа не зависел от момента запуска.
+pub trait Clock {
+    fn now(&self) -> DateTimeValue;
+}
+
+/// Системные часы, время в UTC.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTimeValue {
+        let (seconds, nanos) = match SystemTime::now().duration_since(UNIX_EPOCH) {
+            Ok(elapsed) => (elapsed.as_secs() as i64, elapsed.subsec_nanos()),
+            // Часы системы стоят раньше 1970 года
+            Err(error) => {
+                let before = error.duration();
+                match before.subsec_nanos() {
+                    0 => (-(before.as_secs() as i64), 0),
+                    nanos => (-(before.as_secs() as i64) - 1, 1_000_000_000 - nanos),
+                }
+            }
+        };
+        DateTimeValue::from_timestamp(seconds, nanos, Some(0))
+    }
+}
+
+/// Часы, которые всегда показывают один и тот же момент.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FixedClock(pub DateTimeValue);
+
+impl Clock for FixedClock {
+    fn now(&self) -> DateTimeValue {
+        self.0.clone()
+    }
+}
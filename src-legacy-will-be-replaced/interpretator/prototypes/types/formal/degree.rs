@@ -0,0 +1,7 @@
+use crate::interpretator::prototypes::types::formal::formal::{Dimension, Factor, UnitDefinition};
+
+/// Градус — `π/180` радиан.
+pub const UNITS: &[UnitDefinition] = &[
+    UnitDefinition::new("deg", Factor::with_pi(1, 180), Dimension::ANGLE),
+    UnitDefinition::new("°", Factor::with_pi(1, 180), Dimension::ANGLE),
+];
@@ -0,0 +1,864 @@
+use crate::interpretator::prototypes::types::formal::length::UnitContext;
+use crate::interpretator::prototypes::types::formal::{
+    degree, frequency, length, percent, radian, size, speed, time,
+};
+use crate::interpretator::prototypes::types::object::object::{Object, ObjectRef, ObjectValue};
+use crate::interpretator::prototypes::types::primitive::number::float::write_real;
+use crate::interpretator::prototypes::types::prototype::PrototypeRef;
+use std::any::Any;
+use std::f64::consts::PI;
+use std::fmt;
+use std::ops::{Div, Mul};
+use thiserror::Error;
+
+/// Число базовых измерений в `Dimension`.
+const BASES: usize = 9;
+
+/// Символы базовых единиц в порядке измерений `Dimension`.
+const BASE_SYMBOLS: [&str; BASES] = ["kg", "m", "s", "A", "K", "mol", "cd", "rad", "bit"];
+
+/// Размерность — показатели степеней базовых измерений:
+/// семь базовых величин СИ, плюс угол и объём информации.
+///
+/// Угол вынесен в отдельное измерение, чтобы градусы и радианы не смешивались с числами,
+/// а световой поток (`cd⋅sr`) отличался от силы света (`cd`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Dimension(pub [i8; BASES]);
+
+impl Dimension {
+    pub const NONE: Dimension = Dimension([0; BASES]);
+    pub const MASS: Dimension = Dimension::base(0);
+    pub const LENGTH: Dimension = Dimension::base(1);
+    pub const TIME: Dimension = Dimension::base(2);
+    pub const CURRENT: Dimension = Dimension::base(3);
+    pub const TEMPERATURE: Dimension = Dimension::base(4);
+    pub const AMOUNT: Dimension = Dimension::base(5);
+    pub const LUMINOUS_INTENSITY: Dimension = Dimension::base(6);
+    pub const ANGLE: Dimension = Dimension::base(7);
+    pub const INFORMATION: Dimension = Dimension::base(8);
+
+    const fn base(index: usize) -> Self {
+        let mut exponents = [0; BASES];
+        exponents[index] = 1;
+        Dimension(exponents)
+    }
+
+    /// Размерность по степеням `[масса, длина, время, ток, температура]`;
+    /// остальные измерения нулевые.
+    pub const fn mechanical(mass: i8, length: i8, time: i8, current: i8, temperature: i8) -> Self {
+        Dimension([mass, length, time, current, temperature, 0, 0, 0, 0])
+    }
+
+    pub const fn mul(self, other: Dimension) -> Self {
+        let mut exponents = self.0;
+        let mut i = 0;
+        while i < BASES {
+            exponents[i] += other.0[i];
+            i += 1;
+        }
+        Dimension(exponents)
+    }
+
+    pub const fn powi(self, power: i8) -> Self {
+        let mut exponents = self.0;
+        let mut i = 0;
+        while i < BASES {
+            exponents[i] *= power;
+            i += 1;
+        }
+        Dimension(exponents)
+    }
+
+    pub const fn div(self, other: Dimension) -> Self {
+        self.mul(other.powi(-1))
+    }
+
+    pub fn is_dimensionless(&self) -> bool {
+        *self == Dimension::NONE
+    }
+
+    /// Имя вида величины: `Energy` для `kg⋅m2/s2`.
+    pub fn kind(&self) -> Option<&'static str> {
+        KINDS
+            .iter()
+            .find(|(_, dimension)| dimension == self)
+            .map(|(name, _)| *name)
+    }
+
+    /// Имя вида, а если его нет — запись через базовые единицы.
+    pub fn describe(&self) -> String {
+        match self.kind() {
+            Some(kind) => kind.to_string(),
+            None => self.to_string(),
+        }
+    }
+}
+
+/// Запись через базовые единицы: `kg⋅m2/s2`.
+impl fmt::Display for Dimension {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let terms = BASE_SYMBOLS
+            .iter()
+            .zip(self.0)
+            .filter(|(_, power)| *power != 0)
+            .map(|(symbol, power)| (symbol.to_string(), power as i32));
+        write!(f, "{}", format_terms(terms))
+    }
+}
+
+/// Виды величин из типов `Unit` и их размерности.
+/// Для размерности берётся первое подходящее имя, поэтому основные имена идут раньше синонимов.
+pub const KINDS: &[(&str, Dimension)] = &[
+    ("Length", Dimension::LENGTH),
+    ("Mass", Dimension::MASS),
+    ("Time", Dimension::TIME),
+    ("ElectricCurrent", Dimension::CURRENT),
+    ("Temperature", Dimension::TEMPERATURE),
+    ("Amount", Dimension::AMOUNT),
+    ("LuminousIntensity", Dimension::LUMINOUS_INTENSITY),
+    ("Angle", Dimension::ANGLE),
+    ("Size", Dimension::INFORMATION),
+    ("Percent", Dimension::NONE),
+    ("Area", Dimension::mechanical(0, 2, 0, 0, 0)),
+    ("Volume", Dimension::mechanical(0, 3, 0, 0, 0)),
+    ("Frequency", Dimension::mechanical(0, 0, -1, 0, 0)),
+    ("Velocity", Dimension::mechanical(0, 1, -1, 0, 0)),
+    ("Acceleration", Dimension::mechanical(0, 1, -2, 0, 0)),
+    ("Jerk", Dimension::mechanical(0, 1, -3, 0, 0)),
+    ("Snap", Dimension::mechanical(0, 1, -4, 0, 0)),
+    ("Crackle", Dimension::mechanical(0, 1, -5, 0, 0)),
+    ("Pop", Dimension::mechanical(0, 1, -6, 0, 0)),
+    ("Momentum", Dimension::mechanical(1, 1, -1, 0, 0)),
+    ("Force", Dimension::mechanical(1, 1, -2, 0, 0)),
+    ("Pressure", Dimension::mechanical(1, -1, -2, 0, 0)),
+    ("Energy", Dimension::mechanical(1, 2, -2, 0, 0)),
+    ("ElectricPower", Dimension::mechanical(1, 2, -3, 0, 0)),
+    ("ElectricCharge", Dimension::mechanical(0, 0, 1, 1, 0)),
+    ("ElectricVoltage", Dimension::mechanical(1, 2, -3, -1, 0)),
+    ("ElectricResistance", Dimension::mechanical(1, 2, -3, -2, 0)),
+    (
+        "ElectricConductance",
+        Dimension::mechanical(-1, -2, 3, 2, 0),
+    ),
+    (
+        "ElectricCapacitance",
+        Dimension::mechanical(-1, -2, 4, 2, 0),
+    ),
+    (
+        "LuminousFlux",
+        Dimension::LUMINOUS_INTENSITY.mul(Dimension::ANGLE.powi(2)),
+    ),
+    (
+        "Illuminance",
+        Dimension::LUMINOUS_INTENSITY
+            .mul(Dimension::ANGLE.powi(2))
+            .div(Dimension::mechanical(0, 2, 0, 0, 0)),
+    ),
+    ("BitRate", Dimension::INFORMATION.div(Dimension::TIME)),
+    // Синонимы
+    ("Degree", Dimension::ANGLE),
+    ("Radian", Dimension::ANGLE),
+    ("Speed", Dimension::mechanical(0, 1, -1, 0, 0)),
+    ("Power", Dimension::mechanical(1, 2, -3, 0, 0)),
+];
+
+/// Размерность вида по имени типа: `Energy`, `Velocity`.
+pub fn kind_dimension(name: &str) -> Option<Dimension> {
+    KINDS
+        .iter()
+        .find(|(kind, _)| kind.eq_ignore_ascii_case(name))
+        .map(|(_, dimension)| *dimension)
+}
+
+/// Множитель перевода в когерентную единицу СИ:
+/// `numerator / denominator × 10^exponent × π^pi`.
+///
+/// Множитель хранится несократимой дробью целых чисел, а десятичная степень — отдельно,
+/// поэтому цепочки приставок и имперских единиц перемножаются без округлений,
+/// а при переводе значение делится один раз: `25m3 → 25000L`, `1mi → 5280ft`
+/// и `1mph → 1.609344km/h` получаются точно.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Factor {
+    pub numerator: u128,
+    pub denominator: u128,
+    pub exponent: i32,
+    pub pi: i32,
+}
+
+impl Factor {
+    pub const ONE: Factor = Factor::new(1, 0);
+
+    pub const fn new(scale: u64, exponent: i32) -> Self {
+        Factor::ratio(scale, 1, exponent)
+    }
+
+    /// Дробный множитель: миля в час — это `1609344 / 3600 × 10^-3` м/с.
+    pub const fn ratio(numerator: u64, denominator: u64, exponent: i32) -> Self {
+        Factor {
+            numerator: numerator as u128,
+            denominator: denominator as u128,
+            exponent,
+            pi: 0,
+        }
+    }
+
+    pub const fn decimal(exponent: i32) -> Self {
+        Factor::new(1, exponent)
+    }
+
+    /// Дробь, умноженная на π: градус — это `π/180` радиан.
+    pub const fn with_pi(numerator: u64, denominator: u64) -> Self {
+        Factor {
+            numerator: numerator as u128,
+            denominator: denominator as u128,
+            exponent: 0,
+            pi: 1,
+        }
+    }
+
+    /// Множитель из десятичного числа, которое задаёт хост: `14.5` — `145 × 10^-1`.
+    pub fn from_f64(value: f64) -> Self {
+        let (mut scaled, mut exponent) = (value, 0);
+        while scaled.fract() != 0.0 && exponent > -15 {
+            scaled *= 10.0;
+            exponent -= 1;
+        }
+        Factor::new(scaled.round() as u64, exponent)
+    }
+
+    pub fn powi(self, power: i32) -> Self {
+        let base = match power < 0 {
+            true => Factor {
+                numerator: self.denominator,
+                denominator: self.numerator,
+                exponent: -self.exponent,
+                pi: -self.pi,
+            },
+            false => self,
+        };
+        (0..power.unsigned_abs()).fold(Factor::ONE, |factor, _| factor * base)
+    }
+
+    /// Умножает значение на множитель.
+    pub fn apply(self, value: f64) -> f64 {
+        let (mut numerator, mut denominator) = (self.numerator as f64, self.denominator as f64);
+        // 10^n для n ≤ 22 представимо точно, так что деление остаётся единственным округлением
+        match self.exponent {
+            exponent if exponent >= 0 => numerator *= 10f64.powi(exponent),
+            exponent => denominator *= 10f64.powi(-exponent),
+        }
+        let mut result = value * numerator / denominator;
+        if self.pi != 0 {
+            result *= PI.powi(self.pi);
+        }
+        result
+    }
+}
+
+impl Mul for Factor {
+    type Output = Factor;
+
+    fn mul(self, other: Factor) -> Factor {
+        let (numerator, up) = product(self.numerator, other.numerator);
+        let (denominator, down) = product(self.denominator, other.denominator);
+        let divisor = gcd(numerator, denominator).max(1);
+        Factor {
+            numerator: numerator / divisor,
+            denominator: denominator / divisor,
+            exponent: self.exponent + other.exponent + up - down,
+            pi: self.pi + other.pi,
+        }
+    }
+}
+
+impl Div for Factor {
+    type Output = Factor;
+
+    fn div(self, other: Factor) -> Factor {
+        self * other.powi(-1)
+    }
+}
+
+/// Произведение целых частей множителя и сдвиг десятичной степени. Больше `u128`
+/// оно бывает только в огромных степенях вроде `px20`; тогда младшие цифры, которых
+/// `f64` всё равно не различит, округляются и уходят в десятичную степень.
+fn product(mut a: u128, mut b: u128) -> (u128, i32) {
+    let mut shift = 0;
+    loop {
+        if let Some(product) = a.checked_mul(b) {
+            return (product, shift);
+        }
+        match a >= b {
+            true => a = a.div_ceil(10),
+            false => b = b.div_ceil(10),
+        }
+        shift += 1;
+    }
+}
+
+fn gcd(mut a: u128, mut b: u128) -> u128 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// Единица из каталога.
+#[derive(Debug, Clone, Copy)]
+pub struct UnitDefinition {
+    pub symbol: &'static str,
+    pub factor: Factor,
+    pub dimension: Dimension,
+    /// Допускает ли единица десятичные приставки: `km`, `mA`, но не `kft`
+    pub prefixable: bool,
+}
+
+impl UnitDefinition {
+    pub const fn new(symbol: &'static str, factor: Factor, dimension: Dimension) -> Self {
+        UnitDefinition {
+            symbol,
+            factor,
+            dimension,
+            prefixable: false,
+        }
+    }
+
+    /// Метрическая единица, к которой можно добавить приставку.
+    pub const fn metric(symbol: &'static str, factor: Factor, dimension: Dimension) -> Self {
+        UnitDefinition {
+            symbol,
+            factor,
+            dimension,
+            prefixable: true,
+        }
+    }
+}
+
+/// Десятичные приставки СИ.
+pub const PREFIXES: &[(&str, i32)] = &[
+    ("p", -12),
+    ("n", -9),
+    ("μ", -6),
+    ("µ", -6),
+    ("m", -3),
+    ("c", -2),
+    ("d", -1),
+    ("h", 2),
+    ("k", 3),
+    ("M", 6),
+    ("G", 9),
+    ("T", 12),
+    ("P", 15),
+    ("E", 18),
+];
+
+/// Единицы СИ, у которых нет отдельного модуля вида.
+const SI_UNITS: &[UnitDefinition] = &[
+    UnitDefinition::metric("g", Factor::decimal(-3), Dimension::MASS),
+    UnitDefinition::metric("t", Factor::decimal(3), Dimension::MASS),
+    UnitDefinition::metric("A", Factor::ONE, Dimension::CURRENT),
+    UnitDefinition::metric("K", Factor::ONE, Dimension::TEMPERATURE),
+    UnitDefinition::metric("mol", Factor::ONE, Dimension::AMOUNT),
+    UnitDefinition::metric("cd", Factor::ONE, Dimension::LUMINOUS_INTENSITY),
+    UnitDefinition::metric("N", Factor::ONE, Dimension::mechanical(1, 1, -2, 0, 0)),
+    UnitDefinition::metric("Pa", Factor::ONE, Dimension::mechanical(1, -1, -2, 0, 0)),
+    UnitDefinition::metric(
+        "bar",
+        Factor::decimal(5),
+        Dimension::mechanical(1, -1, -2, 0, 0),
+    ),
+    UnitDefinition::metric("J", Factor::ONE, Dimension::mechanical(1, 2, -2, 0, 0)),
+    UnitDefinition::metric(
+        "Wh",
+        Factor::new(3600, 0),
+        Dimension::mechanical(1, 2, -2, 0, 0),
+    ),
+    UnitDefinition::metric("W", Factor::ONE, Dimension::mechanical(1, 2, -3, 0, 0)),
+    UnitDefinition::metric("C", Factor::ONE, Dimension::mechanical(0, 0, 1, 1, 0)),
+    UnitDefinition::metric("V", Factor::ONE, Dimension::mechanical(1, 2, -3, -1, 0)),
+    UnitDefinition::metric("Ω", Factor::ONE, Dimension::mechanical(1, 2, -3, -2, 0)),
+    UnitDefinition::metric("Ohm", Factor::ONE, Dimension::mechanical(1, 2, -3, -2, 0)),
+    UnitDefinition::metric("S", Factor::ONE, Dimension::mechanical(-1, -2, 3, 2, 0)),
+    UnitDefinition::metric("F", Factor::ONE, Dimension::mechanical(-1, -2, 4, 2, 0)),
+    UnitDefinition::metric(
+        "L",
+        Factor::decimal(-3),
+        Dimension::mechanical(0, 3, 0, 0, 0),
+    ),
+    UnitDefinition::metric(
+        "l",
+        Factor::decimal(-3),
+        Dimension::mechanical(0, 3, 0, 0, 0),
+    ),
+    UnitDefinition::metric(
+        "lm",
+        Factor::ONE,
+        Dimension::LUMINOUS_INTENSITY.mul(Dimension::ANGLE.powi(2)),
+    ),
+    UnitDefinition::metric(
+        "lx",
+        Factor::ONE,
+        Dimension::LUMINOUS_INTENSITY
+            .mul(Dimension::ANGLE.powi(2))
+            .div(Dimension::mechanical(0, 2, 0, 0, 0)),
+    ),
+];
+
+/// Все известные единицы.
+fn catalogue() -> impl Iterator<Item = &'static UnitDefinition> {
+    [
+        SI_UNITS,
+        length::UNITS,
+        time::UNITS,
+        frequency::UNITS,
+        speed::UNITS,
+        degree::UNITS,
+        radian::UNITS,
+        percent::UNITS,
+        size::UNITS,
+    ]
+    .into_iter()
+    .flatten()
+}
+
+/// Ошибка разбора единицы измерения.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum UnitError {
+    #[error("неизвестная единица измерения «{symbol}» в «{unit}»")]
+    Unknown { unit: String, symbol: String },
+    #[error("некорректная запись единицы измерения «{unit}»")]
+    Malformed { unit: String },
+    #[error("размер шрифта для «{unit}» должен быть положительным числом, а не {size}")]
+    FontSize { unit: &'static str, size: String },
+}
+
+/// Множитель составной единицы: `km` в степени 2 в `km2/h`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnitTerm {
+    /// Символ вместе с приставкой: `km`
+    pub symbol: String,
+    pub power: i32,
+    /// Множитель и размерность символа в первой степени
+    pub factor: Factor,
+    pub dimension: Dimension,
+    /// Относительная единица (`em`, `rem`): её множитель взят из `UnitContext`
+    pub relative: bool,
+}
+
+impl UnitTerm {
+    /// Символ с приставкой или без: `m`, `km`, `kg`, `mph`, `em`.
+    pub fn resolve(symbol: &str, unit: &str, context: &UnitContext) -> Result<Self, UnitError> {
+        let term = |definition: &UnitDefinition, factor: Factor| UnitTerm {
+            symbol: symbol.to_string(),
+            power: 1,
+            factor,
+            dimension: definition.dimension,
+            relative: false,
+        };
+        if let Some(factor) = context.relative_factor(symbol) {
+            return Ok(UnitTerm {
+                symbol: symbol.to_string(),
+                power: 1,
+                factor,
+                dimension: Dimension::LENGTH,
+                relative: true,
+            });
+        }
+        if let Some(definition) = catalogue().find(|definition| definition.symbol == symbol) {
+            return Ok(term(definition, definition.factor));
+        }
+        let decimal = PREFIXES
+            .iter()
+            .map(|(prefix, exponent)| (*prefix, Factor::decimal(*exponent), false));
+        // Двоичные приставки и `K` относятся только к объёму информации: `KiB`, `KB`
+        let binary = size::PREFIXES
+            .iter()
+            .map(|(prefix, factor)| (*prefix, *factor, true));
+        for (prefix, prefix_factor, information_only) in decimal.chain(binary) {
+            let Some(rest) = symbol.strip_prefix(prefix) else {
+                continue;
+            };
+            let found = catalogue().find(|definition| {
+                definition.prefixable
+                    && definition.symbol == rest
+                    && (!information_only || definition.dimension == Dimension::INFORMATION)
+            });
+            if let Some(definition) = found {
+                return Ok(term(definition, definition.factor * prefix_factor));
+            }
+        }
+        Err(UnitError::Unknown {
+            unit: unit.to_string(),
+            symbol: symbol.to_string(),
+        })
+    }
+}
+
+/// Единица измерения — произведение символов в целых степенях: `kg⋅m/s2`.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Unit {
+    pub terms: Vec<UnitTerm>,
+}
+
+impl Unit {
+    /// Разбирает запись единицы: символы соединяются `⋅`, `·` или `*`,
+    /// всё после `/` относится к знаменателю, степень пишется цифрами сразу после символа:
+    /// `kW⋅s`, `m/s2`, `kg*m/s`, `Tm/h`. Относительные единицы берутся из контекста по умолчанию.
+    pub fn parse(text: &str) -> Result<Self, UnitError> {
+        Unit::parse_in(text, &UnitContext::default())
+    }
+
+    /// Разбирает запись единицы с размерами шрифта хоста для `em` и `rem`.
+    pub fn parse_in(text: &str, context: &UnitContext) -> Result<Self, UnitError> {
+        let malformed = || UnitError::Malformed {
+            unit: text.to_string(),
+        };
+        let mut unit = Unit::default();
+        let mut denominator = false;
+        let mut rest = text;
+        loop {
+            let end = rest.find(['⋅', '·', '*', '/']).unwrap_or(rest.len());
+            let (symbol, power) = split_power(&rest[..end]).ok_or_else(malformed)?;
+            let mut term = UnitTerm::resolve(symbol, text, context)?;
+            term.power = if denominator { -power } else { power };
+            unit = unit.mul(&Unit { terms: vec![term] });
+
+            let Some(joiner) = rest[end..].chars().next() else {
+                break;
+            };
+            denominator |= joiner == '/';
+            rest = &rest[end + joiner.len_utf8()..];
+        }
+        Ok(unit)
+    }
+
+    /// Целевая единица преобразования `->`: `nL`, где `n` обозначает искомое число,
+    /// или просто `L`. Запись с `n` проверяется первой, поэтому единицы, которые сами
+    /// начинаются с `n`, пишутся со своим `n`: `-> ns` — это секунды, `-> nm` — метры,
+    /// а наносекунды и нанометры — `-> nns` и `-> nnm`.
+    pub fn parse_target(text: &str, context: &UnitContext) -> Result<Self, UnitError> {
+        match text
+            .strip_prefix('n')
+            .map(|rest| Unit::parse_in(rest, context))
+        {
+            Some(Ok(unit)) => Ok(unit),
+            _ => Unit::parse_in(text, context),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    /// Множитель перевода значения в этой единице в когерентную единицу СИ.
+    pub fn factor(&self) -> Factor {
+        self.terms.iter().fold(Factor::ONE, |factor, term| {
+            factor * term.factor.powi(term.power)
+        })
+    }
+
+    pub fn dimension(&self) -> Dimension {
+        self.terms.iter().fold(Dimension::NONE, |dimension, term| {
+            dimension.mul(term.dimension.powi(term.power as i8))
+        })
+    }
+
+    /// Произведение единиц; степени одинаковых символов складываются: `m/s ⋅ 1/s = m/s2`.
+    pub fn mul(&self, other: &Unit) -> Unit {
+        let mut terms = self.terms.clone();
+        for term in &other.terms {
+            match terms
+                .iter_mut()
+                .find(|existing| existing.symbol == term.symbol)
+            {
+                Some(existing) => existing.power += term.power,
+                None => terms.push(term.clone()),
+            }
+        }
+        terms.retain(|term| term.power != 0);
+        Unit { terms }
+    }
+
+    pub fn div(&self, other: &Unit) -> Unit {
+        self.mul(&other.powi(-1))
+    }
+
+    pub fn powi(&self, power: i32) -> Unit {
+        let terms = self
+            .terms
+            .iter()
+            .map(|term| UnitTerm {
+                power: term.power * power,
+                ..term.clone()
+            })
+            .filter(|term| term.power != 0)
+            .collect();
+        Unit { terms }
+    }
+
+    /// Сокращает символы одной размерности, стоящие в числителе и знаменателе:
+    /// `MiByte/s ⋅ min` → `MiByte` с множителем 60. Множитель нужно применить к значению.
+    /// Безразмерные символы (`%`) не сокращаются.
+    pub fn simplify(&self) -> (Factor, Unit) {
+        let mut factor = Factor::ONE;
+        let mut terms = self.terms.clone();
+        loop {
+            let pair = terms.iter().enumerate().find_map(|(i, numerator)| {
+                let j = terms.iter().position(|denominator| {
+                    numerator.power > 0
+                        && denominator.power < 0
+                        && numerator.dimension == denominator.dimension
+                        && !numerator.dimension.is_dimensionless()
+                })?;
+                Some((i, j))
+            });
+            let Some((i, j)) = pair else {
+                break;
+            };
+            let power = terms[i].power.min(-terms[j].power);
+            factor = factor * (terms[i].factor / terms[j].factor).powi(power);
+            terms[i].power -= power;
+            terms[j].power += power;
+            terms.retain(|term| term.power != 0);
+        }
+        (factor, Unit { terms })
+    }
+
+    /// Есть ли в единице относительные символы (`em`, `rem`).
+    pub fn is_relative(&self) -> bool {
+        self.terms.iter().any(|term| term.relative)
+    }
+
+    /// Доля — единица только из безразмерных символов: `%`, `‰`.
+    pub fn is_fraction(&self) -> bool {
+        !self.terms.is_empty()
+            && self
+                .terms
+                .iter()
+                .all(|term| term.dimension.is_dimensionless())
+    }
+
+    /// Остались ли в единице символы, которые сами по себе безразмерны (`%`, `‰`).
+    /// Такие значения не сводятся к простому числу.
+    pub fn has_dimensionless_terms(&self) -> bool {
+        self.terms
+            .iter()
+            .any(|term| term.dimension.is_dimensionless())
+    }
+}
+
+impl fmt::Display for Unit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let terms = self
+            .terms
+            .iter()
+            .map(|term| (term.symbol.clone(), term.power));
+        write!(f, "{}", format_terms(terms))
+    }
+}
+
+/// `m`, `s2` → символ и степень.
+fn split_power(term: &str) -> Option<(&str, i32)> {
+    let symbol = term.trim_end_matches(|ch: char| ch.is_ascii_digit());
+    let digits = &term[symbol.len()..];
+    if symbol.is_empty() {
+        return None;
+    }
+    if digits.is_empty() {
+        return Some((symbol, 1));
+    }
+    let power = digits.parse::<i32>().ok()?;
+    (power != 0).then_some((symbol, power))
+}
+
+/// Числитель через `⋅`, затем `/` и знаменатель: `kg⋅m2/s2`, `1/s`.
+fn format_terms(terms: impl Iterator<Item = (String, i32)>) -> String {
+    let write = |terms: &[(String, i32)]| {
+        terms
+            .iter()
+            .map(|(symbol, power)| match power.abs() {
+                1 => symbol.clone(),
+                power => format!("{}{}", symbol, power),
+            })
+            .collect::<Vec<_>>()
+            .join("⋅")
+    };
+    let (numerator, denominator): (Vec<_>, Vec<_>) = terms.partition(|(_, power)| *power > 0);
+    match (numerator.is_empty(), denominator.is_empty()) {
+        (_, true) => write(&numerator),
+        (true, false) => format!("1/{}", write(&denominator)),
+        (false, false) => format!("{}/{}", write(&numerator), write(&denominator)),
+    }
+}
+
+/// Величина — число в заданной единице измерения: `15kW`, `2.5m/s2`.
+/// Значение хранится в той единице, в которой записано, а не в СИ,
+/// чтобы вывод совпадал с исходником.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QuantityValue {
+    pub value: f64,
+    pub unit: Unit,
+}
+
+impl QuantityValue {
+    pub fn new(value: f64, unit: Unit) -> Self {
+        QuantityValue { value, unit }
+    }
+
+    pub fn dimension(&self) -> Dimension {
+        self.unit.dimension()
+    }
+
+    /// Значение в когерентных единицах СИ.
+    pub fn si_value(&self) -> f64 {
+        self.unit.factor().apply(self.value)
+    }
+
+    /// Значение в другой единице той же размерности.
+    pub fn value_in(&self, unit: &Unit) -> Option<f64> {
+        if self.dimension() != unit.dimension() {
+            return None;
+        }
+        Some((self.unit.factor() / unit.factor()).apply(self.value))
+    }
+}
+
+impl ObjectValue for QuantityValue {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn clone_value(&self) -> Box<dyn ObjectValue> {
+        Box::new(self.clone())
+    }
+}
+
+impl fmt::Display for QuantityValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_real(f, self.value)?;
+        write!(f, "{}", self.unit)
+    }
+}
+
+/// Конструктор для создания величины
+pub struct Quantity;
+
+impl Quantity {
+    pub fn new_instance(proto: PrototypeRef, value: f64, unit: Unit) -> ObjectRef {
+        Object::new(proto, Some(Box::new(QuantityValue::new(value, unit))))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interpretator::engine::engine::tests::entry;
+    use crate::interpretator::engine::engine::Engine;
+
+    #[test]
+    fn test_parse_compound_units() {
+        let momentum = Unit::parse("kg*m/s").unwrap();
+        assert_eq!(momentum.dimension().kind(), Some("Momentum"));
+        assert_eq!(momentum.to_string(), "kg⋅m/s");
+
+        let speed = Unit::parse("Tm/h").unwrap();
+        assert_eq!(speed.dimension().kind(), Some("Velocity"));
+        let value = QuantityValue::new(2.5, speed);
+        assert!((value.si_value() - 2.5e12 / 3600.0).abs() < 1e-3);
+
+        let acceleration = Unit::parse("m/s2").unwrap();
+        assert_eq!(acceleration.dimension().kind(), Some("Acceleration"));
+
+        let energy = Unit::parse("kW⋅s").unwrap();
+        assert_eq!(energy.dimension().kind(), Some("Energy"));
+
+        let volume = QuantityValue::new(25.0, Unit::parse("m3").unwrap());
+        assert_eq!(volume.value_in(&Unit::parse("L").unwrap()), Some(25_000.0));
+        assert_eq!(Unit::parse("μs").unwrap().factor().apply(25.0), 25e-6);
+
+        // Множители перемножаются дробью без округлений: (mph / kph)³ = (201168/125)³ × 10⁻⁹
+        let cube = |unit: &str| Unit::parse(unit).unwrap().factor().powi(3);
+        let ratio = cube("mph") / cube("kph");
+        assert_eq!(
+            (ratio.numerator, ratio.denominator, ratio.exponent),
+            (201168u128.pow(3), 125u128.pow(3), -9)
+        );
+
+        assert_eq!(
+            Unit::parse("kZz").unwrap_err().to_string(),
+            "неизвестная единица измерения «kZz» в «kZz»"
+        );
+    }
+
+    #[test]
+    fn test_exact_conversions() {
+        let convert = |value: f64, from: &str, to: &str| {
+            let quantity = QuantityValue::new(value, Unit::parse(from).unwrap());
+            quantity.value_in(&Unit::parse_target(to, &UnitContext::default()).unwrap())
+        };
+        assert_eq!(convert(25.0, "m3", "nL"), Some(25_000.0));
+        assert_eq!(convert(1000.0, "L", "nkL"), Some(1.0));
+        assert_eq!(convert(3.0, "ML", "kL"), Some(3000.0));
+        assert_eq!(convert(1.0, "mi", "nft"), Some(5280.0));
+        assert_eq!(convert(1.0, "ft", "nin"), Some(12.0));
+        assert_eq!(convert(1.0, "mph", "nkm/h"), Some(1.609344));
+        assert_eq!(convert(60.0, "mph", "nmi/h"), Some(60.0));
+        assert_eq!(convert(1.0, "MB", "nkbit"), Some(8000.0));
+        assert_eq!(convert(1.0, "m", "ns"), None);
+    }
+
+    #[test]
+    fn test_formal_arithmetic() {
+        let source = r#"
+const ops = [+, -]
+a = (10em) + 5
+b = 5 - 50%
+c = 25m3 + 3000L
+d = 16pt + 10em
+e = 10m * 50%
+f = ops[1](10em, 5)
+g = 60mph -> nkm/h
+h = 1m/s2 -> nkm/h**2
+i = 25K -> n°C
+j = 98.6°F -> n°C
+"#;
+        let root = Engine::new().evaluate_document(source).unwrap();
+        assert_eq!(entry(&root, "a"), "15em");
+        assert_eq!(entry(&root, "b"), "2.5");
+        assert_eq!(entry(&root, "c"), "28m3");
+        assert_eq!(entry(&root, "d"), "136pt");
+        assert_eq!(entry(&root, "e"), "5m");
+        assert_eq!(entry(&root, "f"), "15em");
+        assert_eq!(entry(&root, "g"), "96.56064km/h");
+        assert_eq!(entry(&root, "h"), "12960km/h2");
+        assert_eq!(entry(&root, "i"), "-248.15°C");
+        assert_eq!(entry(&root, "j"), "37°C");
+
+        let errors = Engine::new()
+            .evaluate_document("a = 10em + 16pt\n")
+            .unwrap_err();
+        assert_eq!(
+            errors[0].message,
+            "«pt» нельзя выразить в относительной единице «em»"
+        );
+
+        let root = Engine::new()
+            .with_unit_context(UnitContext::new(16.0, 20.0))
+            .unwrap()
+            .evaluate_document("a = 1rem -> npx\n")
+            .unwrap();
+        assert_eq!(entry(&root, "a"), "20px");
+
+        let error = Engine::new()
+            .with_unit_context(UnitContext::new(0.0, 16.0))
+            .err()
+            .unwrap();
+        assert_eq!(
+            error.to_string(),
+            "размер шрифта для «em» должен быть положительным числом, а не 0"
+        );
+        assert!(Engine::new()
+            .with_unit_context(UnitContext::new(16.0, -2.0))
+            .is_err());
+    }
+}
@@ -0,0 +1,253 @@
+use crate::interpretator::prototypes::types::object::object::{Object, ObjectRef, ObjectValue};
+use crate::interpretator::prototypes::types::primitive::number::big_integer::BigInt;
+use crate::interpretator::prototypes::types::prototype::PrototypeRef;
+use std::any::Any;
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, Mul, Neg, Sub};
+
+/// Точная рациональная дробь. Всегда несократима, знаменатель положительный,
+/// поэтому равные дроби равны и структурно: `2/4 == 1/2`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Rational {
+    numerator: BigInt,
+    denominator: BigInt,
+}
+
+impl Rational {
+    /// Сокращённая дробь; `None`, если знаменатель ноль.
+    pub fn new(numerator: BigInt, denominator: BigInt) -> Option<Self> {
+        if denominator.is_zero() {
+            return None;
+        }
+        let gcd = numerator.gcd(&denominator);
+        let (mut numerator, _) = numerator.div_rem(&gcd)?;
+        let (mut denominator, _) = denominator.div_rem(&gcd)?;
+        if denominator.is_negative() {
+            numerator = -numerator;
+            denominator = -denominator;
+        }
+        Some(Rational {
+            numerator,
+            denominator,
+        })
+    }
+
+    pub fn integer(value: BigInt) -> Self {
+        Rational {
+            numerator: value,
+            denominator: BigInt::from(1),
+        }
+    }
+
+    /// Точное значение дробного числа. У `f64` конечная двоичная запись,
+    /// поэтому `0.1` — это 3602879701896397/36028797018963968.
+    /// У бесконечностей и `NaN` точного значения нет.
+    pub fn from_f64(value: f64) -> Option<Self> {
+        if !value.is_finite() {
+            return None;
+        }
+        let bits = value.to_bits();
+        let exponent = ((bits >> 52) & 0x7ff) as i32;
+        let fraction = bits & ((1 << 52) - 1);
+        let (mantissa, exponent) = match exponent {
+            0 => (fraction, -1074),
+            _ => (fraction | 1 << 52, exponent - 1075),
+        };
+        let mut mantissa = BigInt::from(mantissa as i64);
+        if value.is_sign_negative() {
+            mantissa = -mantissa;
+        }
+        let two = BigInt::from(2);
+        match exponent >= 0 {
+            true => Some(Rational::integer(&mantissa * &two.pow(exponent as u32))),
+            false => Rational::new(mantissa, two.pow(exponent.unsigned_abs())),
+        }
+    }
+
+    /// Десятичная запись литерала без округления через `f64`: `2.5` — это 5/2.
+    pub fn parse_decimal(text: &str) -> Option<Self> {
+        let (whole, fraction) = text.split_once('.').unwrap_or((text, ""));
+        let digits = BigInt::parse_radix(&format!("{}{}", whole, fraction), 10)?;
+        Rational::new(digits, BigInt::from(10).pow(fraction.len() as u32))
+    }
+
+    pub fn numerator(&self) -> &BigInt {
+        &self.numerator
+    }
+
+    pub fn denominator(&self) -> &BigInt {
+        &self.denominator
+    }
+
+    pub fn is_integer(&self) -> bool {
+        self.denominator == BigInt::from(1)
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.numerator.is_zero()
+    }
+
+    pub fn to_f64(&self) -> f64 {
+        self.numerator.to_f64() / self.denominator.to_f64()
+    }
+
+    /// Наибольшее целое, не превосходящее дробь.
+    pub fn floor(&self) -> BigInt {
+        let (quotient, _) = self
+            .numerator
+            .div_mod_floor(&self.denominator)
+            .expect("знаменатель не ноль");
+        quotient
+    }
+
+    /// Частное; `None` при делении на ноль.
+    pub fn checked_div(&self, other: &Rational) -> Option<Self> {
+        Rational::new(
+            &self.numerator * &other.denominator,
+            &self.denominator * &other.numerator,
+        )
+    }
+
+    /// Целая степень; отрицательная переворачивает дробь, поэтому у нуля её нет.
+    pub fn pow(&self, exponent: i32) -> Option<Self> {
+        let power = exponent.unsigned_abs();
+        let (numerator, denominator) = (self.numerator.pow(power), self.denominator.pow(power));
+        match exponent < 0 {
+            true => Rational::new(denominator, numerator),
+            false => Some(Rational {
+                numerator,
+                denominator,
+            }),
+        }
+    }
+}
+
+impl Neg for Rational {
+    type Output = Rational;
+
+    fn neg(self) -> Rational {
+        Rational {
+            numerator: -self.numerator,
+            denominator: self.denominator,
+        }
+    }
+}
+
+impl Add for &Rational {
+    type Output = Rational;
+
+    fn add(self, other: &Rational) -> Rational {
+        Rational::new(
+            &(&self.numerator * &other.denominator) + &(&other.numerator * &self.denominator),
+            &self.denominator * &other.denominator,
+        )
+        .expect("знаменатель не ноль")
+    }
+}
+
+impl Sub for &Rational {
+    type Output = Rational;
+
+    fn sub(self, other: &Rational) -> Rational {
+        Rational::new(
+            &(&self.numerator * &other.denominator) - &(&other.numerator * &self.denominator),
+            &self.denominator * &other.denominator,
+        )
+        .expect("знаменатель не ноль")
+    }
+}
+
+impl Mul for &Rational {
+    type Output = Rational;
+
+    fn mul(self, other: &Rational) -> Rational {
+        Rational::new(
+            &self.numerator * &other.numerator,
+            &self.denominator * &other.denominator,
+        )
+        .expect("знаменатель не ноль")
+    }
+}
+
+impl Ord for Rational {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Знаменатели положительны, так что знак при перемножении не меняется
+        (&self.numerator * &other.denominator).cmp(&(&other.numerator * &self.denominator))
+    }
+}
+
+impl PartialOrd for Rational {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for Rational {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.is_integer() {
+            true => write!(f, "{}", self.numerator),
+            false => write!(f, "{}/{}", self.numerator, self.denominator),
+        }
+    }
+}
+
+/// Точная дробь `fr`: `1fr / 7` остаётся ровно 1/7, а не 0.14285714285714285.
+/// Арифметика с целыми сохраняет точность, с дробными `Float` — переходит к ним.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FractionValue(pub Rational);
+
+impl ObjectValue for FractionValue {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn clone_value(&self) -> Box<dyn ObjectValue> {
+        Box::new(self.clone())
+    }
+}
+
+impl fmt::Display for FractionValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}fr", self.0)
+    }
+}
+
+/// Конструктор для создания дроби
+pub struct Fraction;
+
+impl Fraction {
+    pub fn new_instance(proto: PrototypeRef, value: Rational) -> ObjectRef {
+        Object::new(proto, Some(Box::new(FractionValue(value))))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exact_fractions() {
+        let seventh = Rational::new(BigInt::from(1), BigInt::from(7)).unwrap();
+        let sum = &seventh + &Rational::new(BigInt::from(-3), BigInt::from(-14)).unwrap();
+        assert_eq!(sum.to_string(), "5/14");
+        assert_eq!(
+            (&sum * &Rational::integer(BigInt::from(14))).to_string(),
+            "5"
+        );
+        assert_eq!(seventh.pow(-2).unwrap().to_string(), "49");
+        assert_eq!(Rational::integer(BigInt::zero()).pow(-1), None);
+
+        assert_eq!(Rational::parse_decimal("2.50").unwrap().to_string(), "5/2");
+        assert_eq!(Rational::from_f64(-0.75).unwrap().to_string(), "-3/4");
+        assert_eq!(Rational::from_f64(f64::NAN), None);
+        assert_eq!(
+            Rational::new(BigInt::from(-7), BigInt::from(2))
+                .unwrap()
+                .floor()
+                .to_i64(),
+            Some(-4)
+        );
+        assert!(seventh < Rational::parse_decimal("0.143").unwrap());
+    }
+}
@@ -0,0 +1,11 @@
+use crate::interpretator::prototypes::types::formal::formal::{Dimension, Factor, UnitDefinition};
+
+/// Единицы частоты; обороты в минуту считаются частотой без учёта угла.
+pub const UNITS: &[UnitDefinition] = &[
+    UnitDefinition::metric("Hz", Factor::ONE, Dimension::mechanical(0, 0, -1, 0, 0)),
+    UnitDefinition::new(
+        "rpm",
+        Factor::ratio(1, 60, 0),
+        Dimension::mechanical(0, 0, -1, 0, 0),
+    ),
+];
@@ -0,0 +1,67 @@
+use crate::interpretator::prototypes::types::formal::formal::{
+    Dimension, Factor, UnitDefinition, UnitError,
+};
+
+/// CSS-пиксель — 1/96 дюйма.
+pub const PIXEL: Factor = Factor::ratio(254, 96, -4);
+
+/// Единицы длины. Дюйм определён как ровно 25.4 мм, остальные имперские единицы — через него;
+/// типографские `px`, `pt`, `pc` — доли дюйма по CSS.
+/// Относительные `em` и `rem` зависят от размеров шрифта и описаны в `UnitContext`.
+pub const UNITS: &[UnitDefinition] = &[
+    UnitDefinition::metric("m", Factor::ONE, Dimension::LENGTH),
+    UnitDefinition::new("in", Factor::new(254, -4), Dimension::LENGTH),
+    UnitDefinition::new("ft", Factor::new(3048, -4), Dimension::LENGTH),
+    UnitDefinition::new("yd", Factor::new(9144, -4), Dimension::LENGTH),
+    UnitDefinition::new("mi", Factor::new(1609344, -3), Dimension::LENGTH),
+    UnitDefinition::new("px", PIXEL, Dimension::LENGTH),
+    UnitDefinition::new("pt", Factor::ratio(254, 72, -4), Dimension::LENGTH),
+    UnitDefinition::new("pc", Factor::ratio(254, 6, -4), Dimension::LENGTH),
+];
+
+/// Размеры шрифта в пикселях, от которых отсчитываются `em` (текущий шрифт)
+/// и `rem` (шрифт корня). Их задаёт хост; по умолчанию оба равны 16px, как в браузерах.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct UnitContext {
+    pub font_size: f64,
+    pub root_font_size: f64,
+}
+
+impl UnitContext {
+    pub fn new(font_size: f64, root_font_size: f64) -> Self {
+        UnitContext {
+            font_size,
+            root_font_size,
+        }
+    }
+
+    /// Оба размера — конечные положительные числа: на них делится и умножается
+    /// каждый перевод `em` и `rem`.
+    pub fn validate(&self) -> Result<(), UnitError> {
+        for (unit, size) in [("em", self.font_size), ("rem", self.root_font_size)] {
+            if !(size.is_finite() && size > 0.0) {
+                return Err(UnitError::FontSize {
+                    unit,
+                    size: size.to_string(),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Множитель относительной единицы или `None`, если символ не относительный.
+    pub fn relative_factor(&self, symbol: &str) -> Option<Factor> {
+        let pixels = match symbol {
+            "em" => self.font_size,
+            "rem" => self.root_font_size,
+            _ => return None,
+        };
+        Some(PIXEL * Factor::from_f64(pixels))
+    }
+}
+
+impl Default for UnitContext {
+    fn default() -> Self {
+        UnitContext::new(16.0, 16.0)
+    }
+}
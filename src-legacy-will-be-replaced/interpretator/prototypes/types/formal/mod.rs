@@ -0,0 +1,12 @@
+pub mod degree;
+#[allow(clippy::module_inception, reason = "общие размерности живут в «formal/formal.rs» по раскладке каталогов")]
+pub mod formal;
+pub mod fraction;
+pub mod frequency;
+pub mod length;
+pub mod percent;
+pub mod radian;
+pub mod size;
+pub mod speed;
+pub mod temperature;
+pub mod time;
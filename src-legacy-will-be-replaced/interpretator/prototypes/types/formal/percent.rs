@@ -0,0 +1,8 @@
+use crate::interpretator::prototypes::types::formal::formal::{Dimension, Factor, UnitDefinition};
+
+/// Доли: процент, промилле и базисный пункт. Безразмерны, но сохраняют свою единицу.
+pub const UNITS: &[UnitDefinition] = &[
+    UnitDefinition::new("%", Factor::decimal(-2), Dimension::NONE),
+    UnitDefinition::new("‰", Factor::decimal(-3), Dimension::NONE),
+    UnitDefinition::new("‱", Factor::decimal(-4), Dimension::NONE),
+];
@@ -0,0 +1,7 @@
+use crate::interpretator::prototypes::types::formal::formal::{Dimension, Factor, UnitDefinition};
+
+/// Радиан и стерадиан; телесный угол — квадрат плоского.
+pub const UNITS: &[UnitDefinition] = &[
+    UnitDefinition::metric("rad", Factor::ONE, Dimension::ANGLE),
+    UnitDefinition::metric("sr", Factor::ONE, Dimension::ANGLE.powi(2)),
+];
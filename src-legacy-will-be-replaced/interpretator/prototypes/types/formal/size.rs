@@ -0,0 +1,55 @@
+use crate::interpretator::prototypes::types::formal::formal::{Dimension, Factor, UnitDefinition};
+
+/// Объём информации: `b`/`bit` — биты, `B`/`byte`/`Byte` — байты по 8 бит.
+/// Биты и байты — разные единицы одной размерности: `1B == 8bit`, но `1B` остаётся байтом.
+pub const UNITS: &[UnitDefinition] = &[
+    UnitDefinition::metric("bit", Factor::ONE, Dimension::INFORMATION),
+    UnitDefinition::metric("b", Factor::ONE, Dimension::INFORMATION),
+    UnitDefinition::metric("B", Factor::new(8, 0), Dimension::INFORMATION),
+    UnitDefinition::metric("byte", Factor::new(8, 0), Dimension::INFORMATION),
+    UnitDefinition::metric("Byte", Factor::new(8, 0), Dimension::INFORMATION),
+];
+
+/// Приставки, которые допускают только единицы информации, в дополнение к десятичным СИ:
+/// двоичные по IEC (`KiB` = 1024 B) и заглавная `K`, равная `k` (`KB` = `kB` = 1000 B).
+pub const PREFIXES: &[(&str, Factor)] = &[
+    ("Ki", Factor::new(binary(1), 0)),
+    ("Mi", Factor::new(binary(2), 0)),
+    ("Gi", Factor::new(binary(3), 0)),
+    ("Ti", Factor::new(binary(4), 0)),
+    ("Pi", Factor::new(binary(5), 0)),
+    ("Ei", Factor::new(binary(6), 0)),
+    ("K", Factor::decimal(3)),
+];
+
+/// `1024^power`.
+const fn binary(power: u32) -> u64 {
+    1u64 << (10 * power)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::interpretator::prototypes::types::formal::formal::{QuantityValue, Unit};
+
+    #[test]
+    fn test_sizes_and_bit_rates() {
+        let parse = |unit: &str| Unit::parse(unit).unwrap();
+        let in_unit = |value: f64, from: &str, to: &str| {
+            QuantityValue::new(value, parse(from)).value_in(&parse(to))
+        };
+        assert_eq!(in_unit(1.0, "KiB", "B"), Some(1024.0));
+        assert_eq!(in_unit(1.0, "kB", "B"), Some(1000.0));
+        assert_eq!(in_unit(1.0, "KB", "kB"), Some(1.0));
+        assert_eq!(in_unit(1.0, "MB", "Mb"), Some(8.0));
+        assert_eq!(in_unit(1.0, "GiByte", "MiB"), Some(1024.0));
+        assert_eq!(in_unit(25.0, "kbit/s", "b/s"), Some(25_000.0));
+        assert!(Unit::parse("Kim").is_err());
+
+        let rate = parse("MiByte/s");
+        assert_eq!(rate.dimension().kind(), Some("BitRate"));
+        let (factor, size) = rate.mul(&parse("min")).simplify();
+        assert_eq!(size.to_string(), "MiByte");
+        assert_eq!(size.dimension().kind(), Some("Size"));
+        assert_eq!(factor.apply(25.0), 1500.0);
+    }
+}
@@ -0,0 +1,20 @@
+use crate::interpretator::prototypes::types::formal::formal::{Dimension, Factor, UnitDefinition};
+
+/// Единицы скорости, которые не записываются через `/`: миля, узел и километр в час.
+pub const UNITS: &[UnitDefinition] = &[
+    UnitDefinition::new(
+        "mph",
+        Factor::ratio(1609344, 3600, -3),
+        Dimension::mechanical(0, 1, -1, 0, 0),
+    ),
+    UnitDefinition::new(
+        "kn",
+        Factor::ratio(1852, 3600, 0),
+        Dimension::mechanical(0, 1, -1, 0, 0),
+    ),
+    UnitDefinition::new(
+        "kph",
+        Factor::ratio(1000, 3600, 0),
+        Dimension::mechanical(0, 1, -1, 0, 0),
+    ),
+];
@@ -0,0 +1,228 @@
+use crate::interpretator::prototypes::types::formal::fraction::Rational;
+use crate::interpretator::prototypes::types::object::object::{Object, ObjectRef, ObjectValue};
+use crate::interpretator::prototypes::types::primitive::number::big_integer::BigInt;
+use crate::interpretator::prototypes::types::primitive::number::float::write_real;
+use crate::interpretator::prototypes::types::prototype::PrototypeRef;
+use std::any::Any;
+use std::fmt;
+
+/// Температурная шкала. Шкалы аффинны — различаются не только множителем, но и нулём,
+/// поэтому температура хранится отдельно от мультипликативных величин `Quantity`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TemperatureScale {
+    Kelvin,
+    Celsius,
+    Fahrenheit,
+}
+
+impl TemperatureScale {
+    /// Шкала по символу, записанному после числа: `K`, `°C`, `℃`, `°F`, `℉`.
+    pub fn parse(symbol: &str) -> Option<Self> {
+        match symbol {
+            "K" => Some(TemperatureScale::Kelvin),
+            "°C" | "℃" => Some(TemperatureScale::Celsius),
+            "°F" | "℉" => Some(TemperatureScale::Fahrenheit),
+            _ => None,
+        }
+    }
+
+    /// Целевая шкала преобразования `->`: `n°F` или `°F`.
+    pub fn parse_target(text: &str) -> Option<Self> {
+        text.strip_prefix('n')
+            .and_then(TemperatureScale::parse)
+            .or_else(|| TemperatureScale::parse(text))
+    }
+
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            TemperatureScale::Kelvin => "K",
+            TemperatureScale::Celsius => "°C",
+            TemperatureScale::Fahrenheit => "°F",
+        }
+    }
+
+    /// Абсолютная температура в градусах Цельсия.
+    fn to_celsius(self, value: &Rational) -> Rational {
+        match self {
+            TemperatureScale::Kelvin => value - &celsius_zero(),
+            TemperatureScale::Celsius => value.clone(),
+            TemperatureScale::Fahrenheit => &(value - &integer(32)) * &self.step(),
+        }
+    }
+
+    fn celsius_in_scale(self, celsius: &Rational) -> Rational {
+        match self {
+            TemperatureScale::Kelvin => celsius + &celsius_zero(),
+            TemperatureScale::Celsius => celsius.clone(),
+            TemperatureScale::Fahrenheit => {
+                let degrees = celsius
+                    .checked_div(&self.step())
+                    .expect("цена деления не ноль");
+                &degrees + &integer(32)
+            }
+        }
+    }
+
+    /// Цена деления шкалы в кельвинах: разность в 1°F — это ровно 5/9 K.
+    fn step(self) -> Rational {
+        match self {
+            TemperatureScale::Kelvin | TemperatureScale::Celsius => integer(1),
+            TemperatureScale::Fahrenheit => {
+                Rational::new(BigInt::from(5), BigInt::from(9)).expect("знаменатель не ноль")
+            }
+        }
+    }
+}
+
+/// Смещение шкалы Цельсия относительно абсолютного нуля, 273.15 K.
+fn celsius_zero() -> Rational {
+    Rational::new(BigInt::from(27315), BigInt::from(100)).expect("знаменатель не ноль")
+}
+
+fn integer(value: i64) -> Rational {
+    Rational::integer(BigInt::from(value))
+}
+
+/// Температура: абсолютная (`25°C`) или разность температур (`Δ14°F`).
+///
+/// Абсолютные температуры можно вычитать друг из друга и сдвигать на разность,
+/// но не складывать и не умножать. Разности ведут себя как обычные величины.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TemperatureValue {
+    pub value: f64,
+    pub scale: TemperatureScale,
+    pub delta: bool,
+}
+
+impl TemperatureValue {
+    pub fn absolute(value: f64, scale: TemperatureScale) -> Self {
+        TemperatureValue {
+            value,
+            scale,
+            delta: false,
+        }
+    }
+
+    pub fn delta(value: f64, scale: TemperatureScale) -> Self {
+        TemperatureValue {
+            value,
+            scale,
+            delta: true,
+        }
+    }
+
+    /// Значение в другой шкале: абсолютная температура пересчитывается с учётом нуля шкалы,
+    /// разность — только по цене деления. Считается точной дробью от десятичной записи
+    /// значения и округляется один раз, поэтому `25K -> n°C` — ровно `-248.15°C`.
+    pub fn value_in(&self, scale: TemperatureScale) -> f64 {
+        if self.scale == scale {
+            return self.value;
+        }
+        // у бесконечностей и `NaN` десятичной записи нет, и шкала их не меняет
+        let Some(value) = Rational::parse_decimal(&self.value.to_string()) else {
+            return self.value;
+        };
+        let converted = match self.delta {
+            true => (&value * &self.scale.step())
+                .checked_div(&scale.step())
+                .expect("цена деления не ноль"),
+            false => scale.celsius_in_scale(&self.scale.to_celsius(&value)),
+        };
+        converted.to_f64()
+    }
+
+    pub fn convert(&self, scale: TemperatureScale) -> Self {
+        TemperatureValue {
+            value: self.value_in(scale),
+            scale,
+            delta: self.delta,
+        }
+    }
+
+    /// Имя вида для сообщений и `is`.
+    pub fn kind(&self) -> &'static str {
+        match self.delta {
+            true => "TemperatureDelta",
+            false => "Temperature",
+        }
+    }
+}
+
+impl ObjectValue for TemperatureValue {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn clone_value(&self) -> Box<dyn ObjectValue> {
+        Box::new(self.clone())
+    }
+}
+
+impl fmt::Display for TemperatureValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.delta {
+            write!(f, "Δ")?;
+        }
+        write_real(f, self.value)?;
+        write!(f, "{}", self.scale.symbol())
+    }
+}
+
+/// Конструктор для создания температуры
+pub struct Temperature;
+
+impl Temperature {
+    pub fn new_instance(proto: PrototypeRef, value: TemperatureValue) -> ObjectRef {
+        Object::new(proto, Some(Box::new(value)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interpretator::engine::engine::tests::entry;
+    use crate::interpretator::engine::engine::Engine;
+
+    #[test]
+    fn test_temperature_conversions() {
+        let boiling = TemperatureValue::absolute(100.0, TemperatureScale::Celsius);
+        assert_eq!(boiling.value_in(TemperatureScale::Fahrenheit), 212.0);
+        assert_eq!(boiling.value_in(TemperatureScale::Kelvin), 373.15);
+
+        let room = TemperatureValue::absolute(77.0, TemperatureScale::Fahrenheit);
+        assert_eq!(room.value_in(TemperatureScale::Celsius), 25.0);
+
+        let step = TemperatureValue::delta(9.0, TemperatureScale::Fahrenheit);
+        assert_eq!(step.value_in(TemperatureScale::Celsius), 5.0);
+        assert_eq!(step.convert(TemperatureScale::Kelvin).to_string(), "Δ5K");
+
+        assert_eq!(
+            TemperatureScale::parse_target("n°F"),
+            Some(TemperatureScale::Fahrenheit)
+        );
+        assert_eq!(TemperatureScale::parse("°"), None);
+    }
+
+    #[test]
+    fn test_absolute_zero() {
+        let root = Engine::new()
+            .evaluate_document("a = 20°C + Δ9°F\nb = 10K - Δ10K\nc = -273.15°C\n")
+            .unwrap();
+        assert_eq!(entry(&root, "a"), "25°C");
+        assert_eq!(entry(&root, "b"), "0K");
+        assert_eq!(entry(&root, "c"), "-273.15°C");
+
+        let errors = Engine::new()
+            .evaluate_document("a = 0K - Δ10K\nb = -300°C\nc = ⌊-273.15°C⌋\n")
+            .unwrap_err();
+        let messages: Vec<_> = errors.iter().map(|error| error.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            [
+                "температура «-10K» ниже абсолютного нуля",
+                "температура «-300°C» ниже абсолютного нуля",
+                "температура «-274°C» ниже абсолютного нуля",
+            ]
+        );
+    }
+}
@@ -0,0 +1,24 @@
+use crate::interpretator::prototypes::types::formal::formal::{Dimension, Factor, UnitDefinition};
+
+/// Единицы времени. Год — юлианский, 365.25 суток, месяц — двенадцатая часть года:
+/// так они переводятся в другие единицы. К дате годы и месяцы прибавляются по календарю.
+pub const UNITS: &[UnitDefinition] = &[
+    UnitDefinition::metric("s", Factor::ONE, Dimension::TIME),
+    UnitDefinition::new("sec", Factor::ONE, Dimension::TIME),
+    UnitDefinition::new("min", Factor::new(60, 0), Dimension::TIME),
+    UnitDefinition::new("h", Factor::new(3600, 0), Dimension::TIME),
+    UnitDefinition::new("d", Factor::new(86400, 0), Dimension::TIME),
+    UnitDefinition::new("day", Factor::new(86400, 0), Dimension::TIME),
+    UnitDefinition::new("w", Factor::new(604800, 0), Dimension::TIME),
+    UnitDefinition::new("mo", Factor::new(2629800, 0), Dimension::TIME),
+    UnitDefinition::new("y", Factor::new(31557600, 0), Dimension::TIME),
+];
+
+/// Сколько календарных месяцев в единице: у года 12, у месяца 1, у остальных — `None`.
+pub fn calendar_months(symbol: &str) -> Option<i64> {
+    match symbol {
+        "y" => Some(12),
+        "mo" => Some(1),
+        _ => None,
+    }
+}
@@ -1,4 +1,5 @@
 pub mod enumerator;
+pub mod formal;
 pub mod object;
 pub mod primitive;
 pub mod prototype;
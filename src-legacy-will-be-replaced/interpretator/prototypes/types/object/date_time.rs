@@ -0,0 +1,763 @@
+use crate::interpretator::prototypes::types::object::object::{Object, ObjectRef, ObjectValue};
+use crate::interpretator::prototypes::types::prototype::PrototypeRef;
+use std::any::Any;
+use std::fmt;
+use thiserror::Error;
+
+const NANOS_PER_SECOND: i128 = 1_000_000_000;
+const SECONDS_PER_DAY: i64 = 86_400;
+/// Самое большое смещение зоны, которое допускает ISO 8601: ±18:00.
+const MAX_OFFSET: i32 = 18 * 3600;
+
+/// Ошибка шаблона даты или разбора по нему.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum DateTimeError {
+    #[error("неизвестное поле «{field}» в шаблоне даты «{pattern}»")]
+    UnknownField { field: char, pattern: String },
+    #[error("«{text}» не соответствует шаблону даты «{pattern}»")]
+    Mismatch { text: String, pattern: String },
+    #[error("некорректное смещение часового пояса «{0}»")]
+    InvalidOffset(String),
+    #[error("дата вне допустимого диапазона")]
+    OutOfRange,
+    #[error("к дате прибавляется только целое число месяцев, а не «{0}»")]
+    FractionalMonths(String),
+}
+
+/// Поля даты по местным часам.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Fields {
+    pub year: i64,
+    pub month: u32,
+    pub day: u32,
+    pub hour: u32,
+    pub minute: u32,
+    pub second: u32,
+    pub nanosecond: u32,
+}
+
+impl Default for Fields {
+    fn default() -> Self {
+        Fields {
+            year: 1970,
+            month: 1,
+            day: 1,
+            hour: 0,
+            minute: 0,
+            second: 0,
+            nanosecond: 0,
+        }
+    }
+}
+
+/// Момент времени ISO 8601: `2024-03-15`, `2024-03-15T10:30:00`, `2024-03-15T10:30:00.5+03:00`.
+///
+/// Хранится как секунды от начала эпохи Unix. Часовой пояс — только фиксированное смещение;
+/// без него время местное и в вычислениях считается UTC, а печатается без смещения.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DateTimeValue {
+    /// Секунды от `1970-01-01T00:00:00Z`
+    pub seconds: i64,
+    pub nanos: u32,
+    /// Смещение зоны в секундах к востоку от UTC
+    pub offset: Option<i32>,
+    /// Только дата, без времени суток: `2024-03-15`
+    pub date_only: bool,
+}
+
+impl DateTimeValue {
+    /// Момент по полям местных часов; `None`, если такой даты нет (`2023-02-29`).
+    pub fn from_fields(fields: Fields, offset: Option<i32>, date_only: bool) -> Option<Self> {
+        let valid = (1..=12).contains(&fields.month)
+            && fields.day >= 1
+            && fields.day <= days_in_month(fields.year, fields.month)
+            && fields.hour < 24
+            && fields.minute < 60
+            && fields.second < 60
+            && fields.nanosecond < NANOS_PER_SECOND as u32
+            && offset.is_none_or(|offset| offset.abs() <= MAX_OFFSET);
+        if !valid {
+            return None;
+        }
+        let days = days_from_civil(fields.year, fields.month, fields.day);
+        let local = days
+            .checked_mul(SECONDS_PER_DAY)?
+            .checked_add((fields.hour * 3600 + fields.minute * 60 + fields.second) as i64)?;
+        Some(DateTimeValue {
+            seconds: local - offset.unwrap_or(0) as i64,
+            nanos: fields.nanosecond,
+            offset,
+            date_only,
+        })
+    }
+
+    /// Момент из секунд эпохи Unix в заданной зоне.
+    pub fn from_timestamp(seconds: i64, nanos: u32, offset: Option<i32>) -> Self {
+        DateTimeValue {
+            seconds,
+            nanos,
+            offset,
+            date_only: false,
+        }
+    }
+
+    /// Литерал ISO 8601. Время отделяется `T`, секунды и дробная часть необязательны,
+    /// смещение записывается как `Z` или `±hh:mm`.
+    pub fn parse_iso(text: &str) -> Option<Self> {
+        let (date, time) = match text.split_once('T') {
+            Some((date, time)) => (date, Some(time)),
+            None => (text, None),
+        };
+        let mut date_parts = date.splitn(3, '-');
+        let mut fields = Fields {
+            year: digits(date_parts.next()?, 4)? as i64,
+            month: digits(date_parts.next()?, 2)?,
+            day: digits(date_parts.next()?, 2)?,
+            ..Fields::default()
+        };
+        let Some(time) = time else {
+            return DateTimeValue::from_fields(fields, None, true);
+        };
+
+        let (clock, offset) = match time.find(['Z', '+', '-']) {
+            Some(position) => (&time[..position], Some(parse_offset(&time[position..])?)),
+            None => (time, None),
+        };
+        let (clock, fraction) = match clock.split_once('.') {
+            Some((clock, fraction)) => (clock, Some(fraction)),
+            None => (clock, None),
+        };
+        let mut clock_parts = clock.split(':');
+        fields.hour = digits(clock_parts.next()?, 2)?;
+        fields.minute = digits(clock_parts.next()?, 2)?;
+        if let Some(second) = clock_parts.next() {
+            fields.second = digits(second, 2)?;
+        }
+        if clock_parts.next().is_some() {
+            return None;
+        }
+        if let Some(fraction) = fraction {
+            if fraction.is_empty() || fraction.len() > 9 {
+                return None;
+            }
+            fields.nanosecond =
+                digits(fraction, fraction.len())? * 10u32.pow(9 - fraction.len() as u32);
+        }
+        DateTimeValue::from_fields(fields, offset, false)
+    }
+
+    /// Поля по местным часам этого момента.
+    pub fn fields(&self) -> Fields {
+        let local = self.seconds + self.offset.unwrap_or(0) as i64;
+        let (year, month, day) = civil_from_days(local.div_euclid(SECONDS_PER_DAY));
+        let seconds = local.rem_euclid(SECONDS_PER_DAY) as u32;
+        Fields {
+            year,
+            month,
+            day,
+            hour: seconds / 3600,
+            minute: seconds / 60 % 60,
+            second: seconds % 60,
+            nanosecond: self.nanos,
+        }
+    }
+
+    /// Дата этого момента по его местным часам, без времени суток.
+    pub fn date(&self) -> Self {
+        let offset = self.offset.unwrap_or(0) as i64;
+        let local = self.seconds + offset;
+        DateTimeValue {
+            seconds: local - local.rem_euclid(SECONDS_PER_DAY) - offset,
+            nanos: 0,
+            offset: self.offset,
+            date_only: true,
+        }
+    }
+
+    /// День недели по ISO: 1 — понедельник, 7 — воскресенье.
+    pub fn weekday(&self) -> u32 {
+        let local = self.seconds + self.offset.unwrap_or(0) as i64;
+        // 1970-01-01 был четвергом
+        ((local.div_euclid(SECONDS_PER_DAY) + 3).rem_euclid(7) + 1) as u32
+    }
+
+    /// Наносекунды от начала эпохи Unix.
+    pub fn timestamp_nanos(&self) -> i128 {
+        self.seconds as i128 * NANOS_PER_SECOND + self.nanos as i128
+    }
+
+    /// Момент, сдвинутый на `nanos`. Дата остаётся датой, если сдвиг — целое число суток.
+    pub fn shifted(&self, nanos: i128) -> Option<Self> {
+        let total = self.timestamp_nanos().checked_add(nanos)?;
+        let day = SECONDS_PER_DAY as i128 * NANOS_PER_SECOND;
+        Some(DateTimeValue {
+            seconds: i64::try_from(total.div_euclid(NANOS_PER_SECOND)).ok()?,
+            nanos: total.rem_euclid(NANOS_PER_SECOND) as u32,
+            offset: self.offset,
+            date_only: self.date_only && nanos % day == 0,
+        })
+    }
+
+    /// Момент через `months` календарных месяцев по местным часам. День, которого
+    /// в новом месяце нет, становится последним: `2024-01-31` + 1 месяц — `2024-02-29`.
+    pub fn add_months(&self, months: i64) -> Option<Self> {
+        let mut fields = self.fields();
+        let total = fields
+            .year
+            .checked_mul(12)?
+            .checked_add(fields.month as i64 - 1)?
+            .checked_add(months)?;
+        fields.year = total.div_euclid(12);
+        fields.month = total.rem_euclid(12) as u32 + 1;
+        fields.day = fields.day.min(days_in_month(fields.year, fields.month));
+        DateTimeValue::from_fields(fields, self.offset, self.date_only)
+    }
+
+    /// Тот же момент по часам другой зоны.
+    pub fn in_zone(&self, offset: i32) -> Self {
+        DateTimeValue {
+            offset: Some(offset),
+            date_only: false,
+            ..self.clone()
+        }
+    }
+
+    /// Запись по шаблону: `yyyy` — год, `MM` — месяц, `dd` — день, `HH`, `mm`, `ss` — время,
+    /// `SSS` — доли секунды, `X` — смещение зоны, текст в апострофах — как есть.
+    /// Число букв задаёт ширину поля: `d.M.yyyy` — это `5.3.2024`.
+    pub fn format(&self, pattern: &str) -> Result<String, DateTimeError> {
+        let fields = self.fields();
+        let mut text = String::new();
+        for field in compile(pattern)? {
+            match field {
+                Field::Year(2) => text.push_str(&format!("{:02}", fields.year.rem_euclid(100))),
+                Field::Year(width) => text.push_str(&pad(fields.year, width)),
+                Field::Month(width) => text.push_str(&pad(fields.month as i64, width)),
+                Field::Day(width) => text.push_str(&pad(fields.day as i64, width)),
+                Field::Hour(width) => text.push_str(&pad(fields.hour as i64, width)),
+                Field::Minute(width) => text.push_str(&pad(fields.minute as i64, width)),
+                Field::Second(width) => text.push_str(&pad(fields.second as i64, width)),
+                Field::Fraction(width) => {
+                    text.push_str(&format!("{:09}", fields.nanosecond)[..width.min(9)])
+                }
+                Field::Offset => {
+                    if let Some(offset) = self.offset {
+                        text.push_str(&format_offset(offset));
+                    }
+                }
+                Field::Text(literal) => text.push_str(&literal),
+            }
+        }
+        Ok(text)
+    }
+
+    /// Разбор по шаблону из [`DateTimeValue::format`]. Недостающие поля берутся из
+    /// `1970-01-01T00:00:00`; если в шаблоне нет времени суток, получается дата.
+    pub fn parse(text: &str, pattern: &str) -> Result<Self, DateTimeError> {
+        let mismatch = || DateTimeError::Mismatch {
+            text: text.to_string(),
+            pattern: pattern.to_string(),
+        };
+        let template = compile(pattern)?;
+        let mut fields = Fields::default();
+        let mut offset = None;
+        let mut rest = text;
+        for field in &template {
+            match field {
+                Field::Text(literal) => {
+                    rest = rest.strip_prefix(literal.as_str()).ok_or_else(mismatch)?;
+                }
+                Field::Offset => {
+                    let end = match rest.starts_with('Z') {
+                        true => 1,
+                        false => rest
+                            .char_indices()
+                            .skip(1)
+                            .find(|(_, ch)| !ch.is_ascii_digit() && *ch != ':')
+                            .map_or(rest.len(), |(index, _)| index),
+                    };
+                    offset = Some(parse_offset(&rest[..end]).ok_or_else(mismatch)?);
+                    rest = &rest[end..];
+                }
+                Field::Year(2) => {
+                    fields.year = 2000 + take_number(&mut rest, 2, 2).ok_or_else(mismatch)? as i64
+                }
+                Field::Year(width) => {
+                    let max = if *width == 1 { 9 } else { *width };
+                    fields.year = take_number(&mut rest, *width, max).ok_or_else(mismatch)? as i64;
+                }
+                Field::Fraction(width) => {
+                    let value = take_number(&mut rest, *width, *width).ok_or_else(mismatch)?;
+                    fields.nanosecond = (value * 10u64.pow(9 - (*width).min(9) as u32)) as u32;
+                }
+                Field::Month(width)
+                | Field::Day(width)
+                | Field::Hour(width)
+                | Field::Minute(width)
+                | Field::Second(width) => {
+                    let value = take_number(&mut rest, *width, (*width).max(2))
+                        .ok_or_else(mismatch)? as u32;
+                    match field {
+                        Field::Month(_) => fields.month = value,
+                        Field::Day(_) => fields.day = value,
+                        Field::Hour(_) => fields.hour = value,
+                        Field::Minute(_) => fields.minute = value,
+                        _ => fields.second = value,
+                    }
+                }
+            }
+        }
+        if !rest.is_empty() {
+            return Err(mismatch());
+        }
+        let date_only = !template.iter().any(|field| {
+            matches!(
+                field,
+                Field::Hour(_) | Field::Minute(_) | Field::Second(_) | Field::Fraction(_)
+            )
+        });
+        DateTimeValue::from_fields(fields, offset, date_only).ok_or_else(mismatch)
+    }
+}
+
+impl ObjectValue for DateTimeValue {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn clone_value(&self) -> Box<dyn ObjectValue> {
+        Box::new(self.clone())
+    }
+}
+
+impl fmt::Display for DateTimeValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let fields = self.fields();
+        write!(
+            f,
+            "{}-{:02}-{:02}",
+            pad(fields.year, 4),
+            fields.month,
+            fields.day
+        )?;
+        if self.date_only {
+            return Ok(());
+        }
+        write!(
+            f,
+            "T{:02}:{:02}:{:02}",
+            fields.hour, fields.minute, fields.second
+        )?;
+        if fields.nanosecond > 0 {
+            let fraction = format!("{:09}", fields.nanosecond);
+            write!(f, ".{}", fraction.trim_end_matches('0'))?;
+        }
+        if let Some(offset) = self.offset {
+            write!(f, "{}", format_offset(offset))?;
+        }
+        Ok(())
+    }
+}
+
+/// Конструктор для создания даты и времени
+pub struct DateTime;
+
+impl DateTime {
+    pub fn new_instance(proto: PrototypeRef, value: DateTimeValue) -> ObjectRef {
+        Object::new(proto, Some(Box::new(value)))
+    }
+}
+
+/// Промежуток времени с точностью до наносекунды: разность двух дат или `Duration(90min)`.
+/// Складывается с величинами времени (`10s`, `25μs`) и переводится в них: `-> nmin`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct DurationValue {
+    pub nanos: i128,
+}
+
+impl DurationValue {
+    /// Промежуток в секундах; `None` для бесконечностей и `NaN`.
+    pub fn from_seconds(seconds: f64) -> Option<Self> {
+        let nanos = (seconds * NANOS_PER_SECOND as f64).round();
+        match nanos.is_finite() && nanos.abs() < i128::MAX as f64 {
+            true => Some(DurationValue {
+                nanos: nanos as i128,
+            }),
+            false => None,
+        }
+    }
+
+    pub fn seconds(&self) -> f64 {
+        self.nanos as f64 / NANOS_PER_SECOND as f64
+    }
+
+    /// Запись ISO 8601 без лет и месяцев, длина которых непостоянна: `P1DT2H30M`, `PT0.5S`, `-P2W`.
+    pub fn parse_iso(text: &str) -> Option<Self> {
+        let (negative, text) = match text.strip_prefix('-') {
+            Some(text) => (true, text),
+            None => (false, text),
+        };
+        let text = text.strip_prefix('P')?;
+        let (date, time) = match text.split_once('T') {
+            Some((date, time)) if !time.is_empty() => (date, Some(time)),
+            Some(_) => return None,
+            None => (text, None),
+        };
+        if date.is_empty() && time.is_none() {
+            return None;
+        }
+        let mut nanos = designators(date, &[('W', 7 * SECONDS_PER_DAY), ('D', SECONDS_PER_DAY)])?;
+        if let Some(time) = time {
+            nanos += designators(time, &[('H', 3600), ('M', 60), ('S', 1)])?;
+        }
+        Some(DurationValue {
+            nanos: if negative { -nanos } else { nanos },
+        })
+    }
+}
+
+/// Части вида `2H30M` в порядке `units`; число с дробной частью допускается только в последней.
+fn designators(text: &str, units: &[(char, i64)]) -> Option<i128> {
+    let mut nanos = 0i128;
+    let mut rest = text;
+    let mut units = units.iter();
+    while !rest.is_empty() {
+        let end = rest.find(|ch: char| !ch.is_ascii_digit() && ch != '.')?;
+        let (number, designator) = (&rest[..end], rest[end..].chars().next()?);
+        let (_, seconds) = units.find(|(unit, _)| *unit == designator)?;
+        rest = &rest[end + 1..];
+        let (whole, fraction) = number.split_once('.').unwrap_or((number, ""));
+        if whole.is_empty() || (!fraction.is_empty() && !rest.is_empty()) || fraction.len() > 9 {
+            return None;
+        }
+        let unit = *seconds as i128 * NANOS_PER_SECOND;
+        nanos = nanos.checked_add(whole.parse::<i128>().ok()?.checked_mul(unit)?)?;
+        if !fraction.is_empty() {
+            let scale = 10i128.pow(fraction.len() as u32);
+            nanos += fraction.parse::<i128>().ok()? * unit / scale;
+        }
+    }
+    Some(nanos)
+}
+
+impl ObjectValue for DurationValue {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn clone_value(&self) -> Box<dyn ObjectValue> {
+        Box::new(*self)
+    }
+}
+
+impl fmt::Display for DurationValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.nanos < 0 {
+            write!(f, "-")?;
+        }
+        let nanos = self.nanos.unsigned_abs();
+        let seconds = nanos / NANOS_PER_SECOND as u128;
+        let fraction = (nanos % NANOS_PER_SECOND as u128) as u32;
+        let days = seconds / SECONDS_PER_DAY as u128;
+        let (hours, minutes, seconds) = (seconds / 3600 % 24, seconds / 60 % 60, seconds % 60);
+
+        write!(f, "P")?;
+        if days > 0 {
+            write!(f, "{}D", days)?;
+        }
+        if hours == 0 && minutes == 0 && seconds == 0 && fraction == 0 {
+            return match days {
+                0 => write!(f, "T0S"),
+                _ => Ok(()),
+            };
+        }
+        write!(f, "T")?;
+        if hours > 0 {
+            write!(f, "{}H", hours)?;
+        }
+        if minutes > 0 {
+            write!(f, "{}M", minutes)?;
+        }
+        if seconds > 0 || fraction > 0 {
+            write!(f, "{}", seconds)?;
+            if fraction > 0 {
+                let fraction = format!("{:09}", fraction);
+                write!(f, ".{}", fraction.trim_end_matches('0'))?;
+            }
+            write!(f, "S")?;
+        }
+        Ok(())
+    }
+}
+
+/// Конструктор для создания промежутка времени
+pub struct Duration;
+
+impl Duration {
+    pub fn new_instance(proto: PrototypeRef, value: DurationValue) -> ObjectRef {
+        Object::new(proto, Some(Box::new(value)))
+    }
+}
+
+/// Смещение зоны: `Z`, `UTC`, `+03:00`, `+0300`, `-05`, `UTC+3`.
+pub fn parse_offset(text: &str) -> Option<i32> {
+    if matches!(text, "Z" | "UTC") {
+        return Some(0);
+    }
+    let text = text.strip_prefix("UTC").unwrap_or(text);
+    let sign = match text.chars().next()? {
+        '+' => 1,
+        '-' => -1,
+        _ => return None,
+    };
+    let text = &text[1..];
+    let (hours, minutes) = match text.split_once(':') {
+        Some((hours, minutes)) => (hours, minutes),
+        None if text.len() == 4 => text.split_at(2),
+        None => (text, "00"),
+    };
+    if hours.is_empty() || hours.len() > 2 || minutes.len() != 2 {
+        return None;
+    }
+    let (hours, minutes) = (digits(hours, hours.len())?, digits(minutes, 2)?);
+    let offset = (hours * 3600 + minutes * 60) as i32;
+    (minutes < 60 && offset <= MAX_OFFSET).then_some(sign * offset)
+}
+
+/// `Z` для UTC, иначе `±hh:mm`.
+pub fn format_offset(offset: i32) -> String {
+    if offset == 0 {
+        return "Z".to_string();
+    }
+    let sign = if offset < 0 { '-' } else { '+' };
+    let offset = offset.unsigned_abs();
+    format!("{}{:02}:{:02}", sign, offset / 3600, offset / 60 % 60)
+}
+
+/// Поле шаблона даты.
+#[derive(Debug, Clone, PartialEq)]
+enum Field {
+    Year(usize),
+    Month(usize),
+    Day(usize),
+    Hour(usize),
+    Minute(usize),
+    Second(usize),
+    Fraction(usize),
+    Offset,
+    Text(String),
+}
+
+fn compile(pattern: &str) -> Result<Vec<Field>, DateTimeError> {
+    let mut fields = Vec::new();
+    let mut chars = pattern.chars().peekable();
+    while let Some(ch) = chars.next() {
+        if ch == '\'' {
+            // `'T'` — текст как есть, `''` — сам апостроф
+            let mut literal = String::new();
+            while let Some(ch) = chars.next() {
+                if ch != '\'' {
+                    literal.push(ch);
+                } else if chars.peek() == Some(&'\'') {
+                    literal.push(chars.next().unwrap_or('\''));
+                } else {
+                    break;
+                }
+            }
+            fields.push(Field::Text(match literal.is_empty() {
+                true => "'".to_string(),
+                false => literal,
+            }));
+            continue;
+        }
+        if !ch.is_ascii_alphabetic() {
+            fields.push(Field::Text(ch.to_string()));
+            continue;
+        }
+        let mut width = 1;
+        while chars.peek() == Some(&ch) {
+            chars.next();
+            width += 1;
+        }
+        fields.push(match ch {
+            'y' => Field::Year(width),
+            'M' => Field::Month(width),
+            'd' => Field::Day(width),
+            'H' => Field::Hour(width),
+            'm' => Field::Minute(width),
+            's' => Field::Second(width),
+            'S' => Field::Fraction(width),
+            'X' => Field::Offset,
+            _ => {
+                return Err(DateTimeError::UnknownField {
+                    field: ch,
+                    pattern: pattern.to_string(),
+                })
+            }
+        });
+    }
+    Ok(fields)
+}
+
+/// Число из `min..=max` цифр в начале `rest`.
+fn take_number(rest: &mut &str, min: usize, max: usize) -> Option<u64> {
+    let length = rest
+        .chars()
+        .take(max)
+        .take_while(|ch| ch.is_ascii_digit())
+        .count();
+    if length < min {
+        return None;
+    }
+    let value = rest[..length].parse().ok()?;
+    *rest = &rest[length..];
+    Some(value)
+}
+
+/// Ровно `count` цифр.
+fn digits(text: &str, count: usize) -> Option<u32> {
+    if text.len() != count || !text.chars().all(|ch| ch.is_ascii_digit()) {
+        return None;
+    }
+    text.parse().ok()
+}
+
+fn pad(value: i64, width: usize) -> String {
+    match value < 0 {
+        true => format!("-{:0width$}", value.unsigned_abs(), width = width),
+        false => format!("{:0width$}", value, width = width),
+    }
+}
+
+fn is_leap_year(year: i64) -> bool {
+    year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+}
+
+fn days_in_month(year: i64, month: u32) -> u32 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Номер дня от `1970-01-01` по пролептическому григорианскому календарю.
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let month = month as i64;
+    let day_of_year =
+        (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day as i64 - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * shifted_month + 2) / 5 + 1) as u32;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    } as u32;
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interpretator::engine::engine::tests::entry;
+    use crate::interpretator::engine::engine::Engine;
+    use crate::interpretator::prototypes::environment::time::FixedClock;
+
+    #[test]
+    fn test_date_time_calendar_and_patterns() {
+        let moment = DateTimeValue::parse_iso("2024-02-29T23:30:05.25+03:00").unwrap();
+        assert_eq!(moment.to_string(), "2024-02-29T23:30:05.25+03:00");
+        assert_eq!(moment.in_zone(0).to_string(), "2024-02-29T20:30:05.25Z");
+        assert_eq!(moment.weekday(), 4);
+        assert_eq!(
+            moment.shifted(3600 * NANOS_PER_SECOND).unwrap().to_string(),
+            "2024-03-01T00:30:05.25+03:00"
+        );
+        assert_eq!(DateTimeValue::parse_iso("2023-02-29"), None);
+        assert_eq!(
+            DateTimeValue::parse_iso("1969-12-31T23:59:59Z")
+                .unwrap()
+                .seconds,
+            -1
+        );
+
+        assert_eq!(
+            moment.format("d.M.yy HH:mm 'UTC'X").unwrap(),
+            "29.2.24 23:30 UTC+03:00"
+        );
+        let parsed = DateTimeValue::parse("15.03.2024", "dd.MM.yyyy").unwrap();
+        assert_eq!(parsed.to_string(), "2024-03-15");
+        assert!(DateTimeValue::parse("15.3.2024", "dd.MM.yyyy").is_err());
+        assert!(matches!(
+            moment.format("yyyy-QQ"),
+            Err(DateTimeError::UnknownField { field: 'Q', .. })
+        ));
+
+        let duration = DurationValue::parse_iso("P1DT2H30M").unwrap();
+        assert_eq!(duration.seconds(), 95_400.0);
+        assert_eq!(duration.to_string(), "P1DT2H30M");
+        assert_eq!(
+            DurationValue::from_seconds(-0.000025).unwrap().to_string(),
+            "-PT0.000025S"
+        );
+        assert_eq!(DurationValue::parse_iso("PT"), None);
+        assert_eq!(parse_offset("UTC-5"), Some(-5 * 3600));
+    }
+
+    #[test]
+    fn test_date_time_and_durations() {
+        let source = r#"
+const start 2024-03-15T10:30:00+03:00
+a start + 90min
+b start - 2024-03-14
+c [Duration(1h) + 30min, 30min + Duration(1h), Duration(1d)]
+d start -> UTC
+e [start.format("dd.MM.yyyy HH:mm"), DateTime("15.03.2024", "dd.MM.yyyy")]
+f (start - now()) -> nh
+g today()
+h [2024-03-15T10:30:00Z + 1y, 2024-01-31 + 1mo, 2024-02-29 - 1y, 2024-03-15 + 18mo]
+i 1y -> nd
+"#;
+        let clock = FixedClock(DateTimeValue::parse_iso("2024-03-15T23:30:00+01:00").unwrap());
+        let root = Engine::new()
+            .with_clock(clock)
+            .evaluate_document(source)
+            .unwrap();
+        assert_eq!(entry(&root, "a"), "2024-03-15T12:00:00+03:00");
+        assert_eq!(entry(&root, "b"), "P1DT7H30M");
+        assert_eq!(entry(&root, "c"), "[PT1H30M, 90min, P1D]");
+        assert_eq!(entry(&root, "d"), "2024-03-15T07:30:00Z");
+        assert_eq!(entry(&root, "e"), "[15.03.2024 10:30, 2024-03-15]");
+        assert_eq!(entry(&root, "f"), "-15h");
+        assert_eq!(entry(&root, "g"), "2024-03-15");
+        assert_eq!(
+            entry(&root, "h"),
+            "[2025-03-15T10:30:00Z, 2024-02-29, 2023-02-28, 2025-09-15]"
+        );
+        assert_eq!(entry(&root, "i"), "365.25d");
+
+        let errors = Engine::new()
+            .evaluate_document("a 2024-03-15 + 1.3mo\n")
+            .unwrap_err();
+        assert_eq!(
+            errors[0].message,
+            "к дате прибавляется только целое число месяцев, а не «1.3mo»"
+        );
+    }
+}
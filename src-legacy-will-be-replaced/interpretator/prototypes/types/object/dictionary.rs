@@ -1,23 +1,32 @@
 use crate::interpretator::prototypes::types::object::object::{Object, ObjectRef, ObjectValue};
 use crate::interpretator::prototypes::types::prototype::PrototypeRef;
+use indexmap::IndexMap;
 use std::any::Any;
 use std::cell::RefCell;
-use std::collections::HashMap;
 use std::fmt;
 use std::rc::Rc;
 
 /// Внутренние данные словаря.
 /// В отличие от Object.properties (которые хранят системные поля/методы),
 /// DictionaryValue хранит данные самого пользователя языка.
+/// Словарь не сортирует содержимое: элементы идут в порядке объявления,
+/// а замена значения по существующему ключу не меняет его места.
 #[derive(Debug, Clone)]
 pub struct DictionaryValue {
-    pub entries: HashMap<String, ObjectRef>,
+    pub entries: IndexMap<String, ObjectRef>,
 }
 
 impl DictionaryValue {
-    pub fn new(entries: HashMap<String, ObjectRef>) -> Self {
+    pub fn new(entries: IndexMap<String, ObjectRef>) -> Self {
         DictionaryValue { entries }
     }
+
+    /// Элемент по номеру в порядке объявления, с единицы — как у массивов.
+    pub fn entry_at(&self, position: usize) -> Option<(&String, &ObjectRef)> {
+        position
+            .checked_sub(1)
+            .and_then(|index| self.entries.get_index(index))
+    }
 }
 
 impl ObjectValue for DictionaryValue {
@@ -47,7 +56,42 @@ pub struct Dictionary;
 impl Dictionary {
     /// Создает новый Object, который ведет себя как словарь.
     /// Цепочка прототипов: Dictionary Instance -> Dictionary Prototype -> Object Prototype
-    pub fn new_instance(proto: PrototypeRef, entries: HashMap<String, ObjectRef>) -> ObjectRef {
+    pub fn new_instance(proto: PrototypeRef, entries: IndexMap<String, ObjectRef>) -> ObjectRef {
         Object::new(proto, Some(Box::new(DictionaryValue::new(entries))))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interpretator::stdlib::core::prelude::Prelude;
+
+    #[test]
+    fn test_declaration_order() {
+        let prelude = Prelude::new();
+        let mut entries = IndexMap::new();
+        for (key, value) in [("zeta", 3), ("alpha", 1), ("mid", 2)] {
+            entries.insert(key.to_string(), prelude.integer_value(value));
+        }
+        let mut dictionary = DictionaryValue::new(entries);
+        assert_eq!(
+            dictionary.to_string(),
+            r#"{"zeta": 3, "alpha": 1, "mid": 2}"#
+        );
+
+        // Новое значение по старому ключу остаётся на своём месте
+        dictionary
+            .entries
+            .insert("zeta".to_string(), prelude.integer_value(0));
+        assert_eq!(
+            dictionary.to_string(),
+            r#"{"zeta": 0, "alpha": 1, "mid": 2}"#
+        );
+
+        let (key, value) = dictionary.entry_at(2).unwrap();
+        assert_eq!(key, "alpha");
+        assert_eq!(value.borrow().to_string(), "1");
+        assert!(dictionary.entry_at(0).is_none());
+        assert!(dictionary.entry_at(4).is_none());
+    }
+}
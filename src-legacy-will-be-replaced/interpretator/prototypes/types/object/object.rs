@@ -1,7 +1,7 @@
 use crate::interpretator::prototypes::types::prototype::PrototypeRef;
+use indexmap::IndexMap;
 use std::any::Any;
 use std::cell::RefCell;
-use std::collections::HashMap;
 use std::fmt;
 use std::rc::Rc;
 
@@ -30,7 +30,7 @@ pub struct Object {
     pub prototype: PrototypeRef,
 
     /// Динамические свойства объекта (поля).
-    pub properties: HashMap<String, ObjectRef>,
+    pub properties: IndexMap<String, ObjectRef>,
 
     /// Само значение объекта. Box<dyn Any> позволяет хранить что угодно.
     /// Мы используем Option, чтобы объект мог быть "пустым" (как простой Dictionary).
@@ -42,7 +42,7 @@ impl Object {
     pub fn new(prototype: PrototypeRef, value: Option<Box<dyn ObjectValue>>) -> ObjectRef {
         Rc::new(RefCell::new(Object {
             prototype,
-            properties: HashMap::new(),
+            properties: IndexMap::new(),
             value,
         }))
     }
@@ -12,6 +12,9 @@ pub enum RoutineContent {
     NativeProcedure(fn(Vec<ObjectRef>)),
     /// Пользовательское определение (например, тело функции в виде строки или AST)
     Defined(String),
+    /// Встроенная функция (`RGBA`) или метод встроенного значения с запомненным
+    /// получателем (`color.lighten`). Вычисляется в `stdlib::core::builtins` по имени
+    Builtin { receiver: Option<ObjectRef> },
 }
 
 #[derive(Debug, Clone)]
@@ -33,7 +36,9 @@ impl ObjectValue for RoutineValue {
 impl fmt::Display for RoutineValue {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let r_type = match self.content {
-            RoutineContent::NativeFunction(_) | RoutineContent::NativeProcedure(_) => "native ",
+            RoutineContent::NativeFunction(_)
+            | RoutineContent::NativeProcedure(_)
+            | RoutineContent::Builtin { .. } => "native ",
             _ => "",
         };
         write!(
@@ -53,6 +58,7 @@ impl fmt::Debug for RoutineContent {
             RoutineContent::NativeFunction(_) => write!(f, "NativeFunction"),
             RoutineContent::NativeProcedure(_) => write!(f, "NativeProcedure"),
             RoutineContent::Defined(s) => write!(f, "Defined({})", s),
+            RoutineContent::Builtin { .. } => write!(f, "Builtin"),
         }
     }
 }
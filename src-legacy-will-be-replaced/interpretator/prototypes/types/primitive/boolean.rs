@@ -0,0 +1,29 @@
+use crate::interpretator::prototypes::types::object::object::{Object, ObjectRef, ObjectValue};
+use crate::interpretator::prototypes::types::prototype::PrototypeRef;
+use std::any::Any;
+use std::fmt;
+
+#[derive(Debug, Clone)]
+pub struct BooleanValue(pub bool);
+
+impl ObjectValue for BooleanValue {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn clone_value(&self) -> Box<dyn ObjectValue> {
+        Box::new(self.clone())
+    }
+}
+
+impl fmt::Display for BooleanValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+pub struct Boolean;
+impl Boolean {
+    pub fn new_instance(proto: PrototypeRef, value: bool) -> ObjectRef {
+        Object::new(proto, Some(Box::new(BooleanValue(value))))
+    }
+}
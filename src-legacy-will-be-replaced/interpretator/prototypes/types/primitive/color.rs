@@ -0,0 +1,315 @@
+use crate::interpretator::prototypes::types::object::object::{Object, ObjectRef, ObjectValue};
+use crate::interpretator::prototypes::types::prototype::PrototypeRef;
+use std::any::Any;
+use std::fmt;
+
+/// Запись, в которой цвет печатается и попадает в выходные форматы.
+/// Все три понятны CSS.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ColorSpace {
+    /// `#fa8246`, `#fa8246cc`
+    Hex,
+    /// `rgb(250, 130, 70)`, `rgba(250, 130, 70, 0.8)`
+    Rgb,
+    /// `hsl(20, 95%, 63%)`, `hsla(20, 95%, 63%, 0.8)`
+    Hsl,
+}
+
+impl ColorSpace {
+    /// Пространство по имени цели `->`: `hex`, `rgb`, `rgba`, `hsl`, `hsla`.
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "hex" => Some(ColorSpace::Hex),
+            "rgb" | "rgba" => Some(ColorSpace::Rgb),
+            "hsl" | "hsla" => Some(ColorSpace::Hsl),
+            _ => None,
+        }
+    }
+}
+
+/// Цвет sRGB с прозрачностью. Каналы хранятся дробными (0–255, прозрачность 0–1),
+/// чтобы переводы между RGB и HSL не накапливали ошибку округления;
+/// округляются они только при печати.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColorValue {
+    pub red: f64,
+    pub green: f64,
+    pub blue: f64,
+    pub alpha: f64,
+    pub space: ColorSpace,
+}
+
+impl ColorValue {
+    pub fn rgba(red: f64, green: f64, blue: f64, alpha: f64) -> Self {
+        ColorValue {
+            red: red.clamp(0.0, 255.0),
+            green: green.clamp(0.0, 255.0),
+            blue: blue.clamp(0.0, 255.0),
+            alpha: alpha.clamp(0.0, 1.0),
+            space: ColorSpace::Rgb,
+        }
+    }
+
+    /// Цвет по тону в градусах, насыщенности и светлоте в долях от 0 до 1.
+    pub fn hsla(hue: f64, saturation: f64, lightness: f64, alpha: f64) -> Self {
+        let (saturation, lightness) = (saturation.clamp(0.0, 1.0), lightness.clamp(0.0, 1.0));
+        let chroma = (1.0 - (2.0 * lightness - 1.0).abs()) * saturation;
+        let sector = hue.rem_euclid(360.0) / 60.0;
+        let second = chroma * (1.0 - (sector % 2.0 - 1.0).abs());
+        let (red, green, blue) = match sector as u32 {
+            0 => (chroma, second, 0.0),
+            1 => (second, chroma, 0.0),
+            2 => (0.0, chroma, second),
+            3 => (0.0, second, chroma),
+            4 => (second, 0.0, chroma),
+            _ => (chroma, 0.0, second),
+        };
+        let offset = lightness - chroma / 2.0;
+        ColorValue {
+            space: ColorSpace::Hsl,
+            ..ColorValue::rgba(
+                (red + offset) * 255.0,
+                (green + offset) * 255.0,
+                (blue + offset) * 255.0,
+                alpha,
+            )
+        }
+    }
+
+    /// Шестнадцатеричная запись без `#`: `f84`, `f84c`, `ff8844`, `ff8844cc`.
+    pub fn parse_hex(digits: &str) -> Option<Self> {
+        if !digits.chars().all(|ch| ch.is_ascii_hexdigit()) {
+            return None;
+        }
+        let channels: Vec<f64> = match digits.len() {
+            3 | 4 => digits
+                .chars()
+                .map(|ch| ch.to_digit(16).map(|value| (value * 17) as f64))
+                .collect::<Option<_>>()?,
+            6 | 8 => (0..digits.len())
+                .step_by(2)
+                .map(|i| {
+                    u8::from_str_radix(&digits[i..i + 2], 16)
+                        .ok()
+                        .map(f64::from)
+                })
+                .collect::<Option<_>>()?,
+            _ => return None,
+        };
+        let alpha = channels.get(3).map_or(1.0, |alpha| alpha / 255.0);
+        Some(ColorValue {
+            space: ColorSpace::Hex,
+            ..ColorValue::rgba(channels[0], channels[1], channels[2], alpha)
+        })
+    }
+
+    /// Тон в градусах, насыщенность и светлота в долях.
+    pub fn to_hsl(&self) -> (f64, f64, f64) {
+        let (red, green, blue) = (self.red / 255.0, self.green / 255.0, self.blue / 255.0);
+        let max = red.max(green).max(blue);
+        let min = red.min(green).min(blue);
+        let lightness = (max + min) / 2.0;
+        let chroma = max - min;
+        if chroma == 0.0 {
+            return (0.0, 0.0, lightness);
+        }
+        let saturation = chroma / (1.0 - (2.0 * lightness - 1.0).abs());
+        let hue = match max {
+            _ if max == red => ((green - blue) / chroma).rem_euclid(6.0),
+            _ if max == green => (blue - red) / chroma + 2.0,
+            _ => (red - green) / chroma + 4.0,
+        };
+        (hue * 60.0, saturation, lightness)
+    }
+
+    /// Тот же цвет в другой записи.
+    pub fn in_space(&self, space: ColorSpace) -> Self {
+        ColorValue {
+            space,
+            ..self.clone()
+        }
+    }
+
+    /// Светлота, сдвинутая на `amount` (доля от 0 до 1, отрицательная затемняет):
+    /// `#808080` светлее на 10% — это `#9a9a9a`.
+    pub fn lighten(&self, amount: f64) -> Self {
+        let (hue, saturation, lightness) = self.to_hsl();
+        ColorValue {
+            space: self.space,
+            ..ColorValue::hsla(hue, saturation, lightness + amount, self.alpha)
+        }
+    }
+
+    /// Смесь с `other`, где `weight` — доля второго цвета: при 0 остаётся этот цвет, при 1 — `other`.
+    pub fn mix(&self, other: &ColorValue, weight: f64) -> Self {
+        let weight = weight.clamp(0.0, 1.0);
+        let blend = |a: f64, b: f64| a + (b - a) * weight;
+        ColorValue {
+            space: self.space,
+            ..ColorValue::rgba(
+                blend(self.red, other.red),
+                blend(self.green, other.green),
+                blend(self.blue, other.blue),
+                blend(self.alpha, other.alpha),
+            )
+        }
+    }
+
+    /// Относительная яркость по WCAG 2: 0 у чёрного, 1 у белого.
+    pub fn luminance(&self) -> f64 {
+        let linear = |channel: f64| {
+            let channel = channel / 255.0;
+            match channel <= 0.04045 {
+                true => channel / 12.92,
+                false => ((channel + 0.055) / 1.055).powf(2.4),
+            }
+        };
+        0.2126 * linear(self.red) + 0.7152 * linear(self.green) + 0.0722 * linear(self.blue)
+    }
+
+    /// Контраст по WCAG 2 — от 1 (одинаковые цвета) до 21 (чёрный на белом).
+    pub fn contrast(&self, other: &ColorValue) -> f64 {
+        let (a, b) = (self.luminance(), other.luminance());
+        (a.max(b) + 0.05) / (a.min(b) + 0.05)
+    }
+
+    /// Совпадают ли цвета после округления каналов до печатаемых значений; запись не важна.
+    pub fn same_color(&self, other: &ColorValue) -> bool {
+        self.channels() == other.channels()
+    }
+
+    /// Каналы, округлённые до целых, и прозрачность до сотых.
+    fn channels(&self) -> [u8; 4] {
+        [
+            self.red.round() as u8,
+            self.green.round() as u8,
+            self.blue.round() as u8,
+            (self.alpha * 100.0).round() as u8,
+        ]
+    }
+
+    pub fn hex(&self) -> String {
+        let [red, green, blue, _] = self.channels();
+        let mut text = format!("#{:02x}{:02x}{:02x}", red, green, blue);
+        if self.alpha < 1.0 {
+            text.push_str(&format!("{:02x}", (self.alpha * 255.0).round() as u8));
+        }
+        text
+    }
+}
+
+/// Число для CSS: не больше двух знаков после запятой и без лишних нулей.
+fn css_number(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
+
+impl ObjectValue for ColorValue {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn clone_value(&self) -> Box<dyn ObjectValue> {
+        Box::new(self.clone())
+    }
+}
+
+impl fmt::Display for ColorValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let opaque = self.alpha >= 1.0;
+        let alpha = css_number(self.alpha);
+        match self.space {
+            ColorSpace::Hex => write!(f, "{}", self.hex()),
+            ColorSpace::Rgb => {
+                let [red, green, blue, _] = self.channels();
+                match opaque {
+                    true => write!(f, "rgb({}, {}, {})", red, green, blue),
+                    false => write!(f, "rgba({}, {}, {}, {})", red, green, blue, alpha),
+                }
+            }
+            ColorSpace::Hsl => {
+                let (hue, saturation, lightness) = self.to_hsl();
+                let (hue, saturation, lightness) = (
+                    css_number(hue),
+                    css_number(saturation * 100.0),
+                    css_number(lightness * 100.0),
+                );
+                match opaque {
+                    true => write!(f, "hsl({}, {}%, {}%)", hue, saturation, lightness),
+                    false => write!(
+                        f,
+                        "hsla({}, {}%, {}%, {})",
+                        hue, saturation, lightness, alpha
+                    ),
+                }
+            }
+        }
+    }
+}
+
+/// Конструктор для создания цвета
+pub struct Color;
+
+impl Color {
+    pub fn new_instance(proto: PrototypeRef, value: ColorValue) -> ObjectRef {
+        Object::new(proto, Some(Box::new(value)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interpretator::engine::engine::tests::entry;
+    use crate::interpretator::engine::engine::Engine;
+
+    #[test]
+    fn test_color_spaces() {
+        let orange = ColorValue::rgba(250.0, 130.0, 70.0, 1.0);
+        assert_eq!(orange.to_string(), "rgb(250, 130, 70)");
+        assert_eq!(orange.in_space(ColorSpace::Hex).to_string(), "#fa8246");
+        assert_eq!(
+            orange.in_space(ColorSpace::Hsl).to_string(),
+            "hsl(20, 94.74%, 62.75%)"
+        );
+
+        let (hue, saturation, lightness) = orange.to_hsl();
+        assert!(ColorValue::hsla(hue, saturation, lightness, 1.0).same_color(&orange));
+
+        let translucent = ColorValue::parse_hex("fa8246cc").unwrap();
+        assert_eq!(
+            translucent.in_space(ColorSpace::Rgb).to_string(),
+            "rgba(250, 130, 70, 0.8)"
+        );
+        assert_eq!(ColorValue::parse_hex("f84").unwrap().hex(), "#ff8844");
+        assert_eq!(ColorValue::parse_hex("f84c").unwrap().hex(), "#ff8844cc");
+        assert_eq!(ColorValue::parse_hex("ff884"), None);
+
+        let gray = ColorValue::parse_hex("808080").unwrap();
+        assert_eq!(gray.lighten(0.1).hex(), "#9a9a9a");
+        assert_eq!(gray.lighten(-1.0).hex(), "#000000");
+
+        let black = ColorValue::parse_hex("000").unwrap();
+        let white = ColorValue::parse_hex("fff").unwrap();
+        assert_eq!(black.mix(&white, 0.5).hex(), "#808080");
+        assert_eq!(black.contrast(&white), 21.0);
+    }
+
+    #[test]
+    fn test_colors() {
+        let source = r#"
+const brand #fa8246
+a RGBA(250, 130, 70, 80%)
+b brand -> hsl
+c HSL(180deg, 50, 50)
+d brand.lighten(10%)
+e #000.mix(#fff).contrast(#fff) < 4
+f [brand.red, brand == RGB(250, 130, 70)]
+"#;
+        let root = Engine::new().evaluate_document(source).unwrap();
+        assert_eq!(entry(&root, "a"), "rgba(250, 130, 70, 0.8)");
+        assert_eq!(entry(&root, "b"), "hsl(20, 94.74%, 62.75%)");
+        assert_eq!(entry(&root, "c"), "hsl(180, 50%, 50%)");
+        assert_eq!(entry(&root, "d"), "#fba478");
+        assert_eq!(entry(&root, "e"), "true");
+        assert_eq!(entry(&root, "f"), "[250, true]");
+    }
+}
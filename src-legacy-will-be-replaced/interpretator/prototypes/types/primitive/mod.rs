@@ -1,5 +1,6 @@
 pub mod auto;
 pub mod boolean;
+pub mod color;
 pub mod nil;
 pub mod none;
 pub mod number;
@@ -0,0 +1,434 @@
+use crate::interpretator::prototypes::types::object::object::{Object, ObjectRef, ObjectValue};
+use crate::interpretator::prototypes::types::primitive::number::hexadecimal::Radix;
+use crate::interpretator::prototypes::types::prototype::PrototypeRef;
+use std::any::Any;
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, Mul, Neg, Sub};
+
+/// Основание разряда модуля.
+const LIMB: u64 = 1 << 32;
+
+/// Целое произвольной длины: знак и модуль разрядами по 32 бита, младший разряд первый.
+/// Модуль хранится без старших нулей, у нуля нет разрядов и знак всегда положительный,
+/// поэтому равные числа равны и структурно.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct BigInt {
+    negative: bool,
+    magnitude: Vec<u32>,
+}
+
+impl BigInt {
+    pub fn zero() -> Self {
+        BigInt::default()
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.magnitude.is_empty()
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    /// Число значащих битов модуля; у нуля — 0.
+    pub fn bit_length(&self) -> u64 {
+        match self.magnitude.last() {
+            Some(top) => self.magnitude.len() as u64 * 32 - top.leading_zeros() as u64,
+            None => 0,
+        }
+    }
+
+    /// Значение, если оно помещается в `i64`.
+    pub fn to_i64(&self) -> Option<i64> {
+        if self.magnitude.len() > 2 {
+            return None;
+        }
+        let magnitude = self
+            .magnitude
+            .iter()
+            .rev()
+            .fold(0u64, |acc, &limb| (acc << 32) | limb as u64);
+        match self.negative {
+            true if magnitude <= i64::MIN.unsigned_abs() => Some((magnitude as i64).wrapping_neg()),
+            false => i64::try_from(magnitude).ok(),
+            true => None,
+        }
+    }
+
+    /// Ближайшее дробное; слишком большие числа дают бесконечность.
+    pub fn to_f64(&self) -> f64 {
+        let magnitude = self
+            .magnitude
+            .iter()
+            .rev()
+            .fold(0.0, |acc, &limb| acc * LIMB as f64 + limb as f64);
+        match self.negative {
+            true => -magnitude,
+            false => magnitude,
+        }
+    }
+
+    /// Разбирает цифры в системе счисления `radix` (от 2 до 36) без учёта регистра букв.
+    /// Перед цифрами может стоять `-`. Пустая запись и чужие цифры дают `None`.
+    pub fn parse_radix(text: &str, radix: u32) -> Option<Self> {
+        let (negative, digits) = match text.strip_prefix('-') {
+            Some(digits) => (true, digits),
+            None => (false, text),
+        };
+        if digits.is_empty() {
+            return None;
+        }
+        let mut magnitude = Vec::new();
+        for ch in digits.chars() {
+            let digit = ch.to_digit(radix)?;
+            mul_add_small(&mut magnitude, radix, digit);
+        }
+        Some(BigInt::from_parts(negative, magnitude))
+    }
+
+    /// Запись в системе счисления `radix` заглавными буквами: `348FABD1`.
+    pub fn to_string_radix(&self, radix: u32) -> String {
+        if self.is_zero() {
+            return "0".to_string();
+        }
+        let mut magnitude = self.magnitude.clone();
+        let mut digits = Vec::new();
+        while !magnitude.is_empty() {
+            let digit = div_rem_small(&mut magnitude, radix);
+            let ch = char::from_digit(digit, radix).expect("цифра меньше основания");
+            digits.push(ch.to_ascii_uppercase());
+        }
+        if self.negative {
+            digits.push('-');
+        }
+        digits.iter().rev().collect()
+    }
+
+    /// Степень возведением в квадрат: `2 ** 100` считается за семь умножений.
+    pub fn pow(&self, mut exponent: u32) -> Self {
+        let mut base = self.clone();
+        let mut result = BigInt::from(1);
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                result = &result * &base;
+            }
+            exponent >>= 1;
+            if exponent > 0 {
+                base = &base * &base;
+            }
+        }
+        result
+    }
+
+    pub fn abs(&self) -> Self {
+        BigInt::from_parts(false, self.magnitude.clone())
+    }
+
+    /// Деление с отбрасыванием дробной части: остаток имеет знак делимого.
+    /// `None` при делении на ноль.
+    pub fn div_rem(&self, divisor: &BigInt) -> Option<(BigInt, BigInt)> {
+        if divisor.is_zero() {
+            return None;
+        }
+        let (quotient, remainder) = div_rem_magnitude(&self.magnitude, &divisor.magnitude);
+        Some((
+            BigInt::from_parts(self.negative != divisor.negative, quotient),
+            BigInt::from_parts(self.negative, remainder),
+        ))
+    }
+
+    /// Деление с округлением вниз, как у `//` и `%`: остаток имеет знак делителя,
+    /// `-7 // 2 = -4`, `-7 % 2 = 1`.
+    pub fn div_mod_floor(&self, divisor: &BigInt) -> Option<(BigInt, BigInt)> {
+        let (quotient, remainder) = self.div_rem(divisor)?;
+        if !remainder.is_zero() && remainder.negative != divisor.negative {
+            return Some((&quotient - &BigInt::from(1), &remainder + divisor));
+        }
+        Some((quotient, remainder))
+    }
+
+    /// Наибольший общий делитель модулей; `gcd(0, 0) = 0`.
+    pub fn gcd(&self, other: &BigInt) -> BigInt {
+        let (mut a, mut b) = (self.abs(), other.abs());
+        while let Some((_, remainder)) = a.div_rem(&b) {
+            (a, b) = (b, remainder);
+        }
+        a
+    }
+
+    fn from_parts(negative: bool, mut magnitude: Vec<u32>) -> Self {
+        while magnitude.last() == Some(&0) {
+            magnitude.pop();
+        }
+        BigInt {
+            negative: negative && !magnitude.is_empty(),
+            magnitude,
+        }
+    }
+}
+
+impl From<i64> for BigInt {
+    fn from(value: i64) -> Self {
+        let magnitude = value.unsigned_abs();
+        BigInt::from_parts(value < 0, vec![magnitude as u32, (magnitude >> 32) as u32])
+    }
+}
+
+impl Ord for BigInt {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.negative, other.negative) {
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+            (false, false) => cmp_magnitude(&self.magnitude, &other.magnitude),
+            (true, true) => cmp_magnitude(&other.magnitude, &self.magnitude),
+        }
+    }
+}
+
+impl PartialOrd for BigInt {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Neg for BigInt {
+    type Output = BigInt;
+
+    fn neg(self) -> BigInt {
+        BigInt::from_parts(!self.negative, self.magnitude)
+    }
+}
+
+impl Add for &BigInt {
+    type Output = BigInt;
+
+    fn add(self, other: &BigInt) -> BigInt {
+        if self.negative == other.negative {
+            return BigInt::from_parts(
+                self.negative,
+                add_magnitude(&self.magnitude, &other.magnitude),
+            );
+        }
+        // Знаки разные: из большего модуля вычитается меньший, знак — у большего
+        match cmp_magnitude(&self.magnitude, &other.magnitude) {
+            Ordering::Less => BigInt::from_parts(
+                other.negative,
+                sub_magnitude(&other.magnitude, &self.magnitude),
+            ),
+            _ => BigInt::from_parts(
+                self.negative,
+                sub_magnitude(&self.magnitude, &other.magnitude),
+            ),
+        }
+    }
+}
+
+impl Sub for &BigInt {
+    type Output = BigInt;
+
+    fn sub(self, other: &BigInt) -> BigInt {
+        self + &-other.clone()
+    }
+}
+
+impl Mul for &BigInt {
+    type Output = BigInt;
+
+    fn mul(self, other: &BigInt) -> BigInt {
+        let mut product = vec![0u32; self.magnitude.len() + other.magnitude.len()];
+        for (i, &a) in self.magnitude.iter().enumerate() {
+            let mut carry = 0u64;
+            for (j, &b) in other.magnitude.iter().enumerate() {
+                let cell = product[i + j] as u64 + a as u64 * b as u64 + carry;
+                product[i + j] = cell as u32;
+                carry = cell >> 32;
+            }
+            product[i + other.magnitude.len()] = carry as u32;
+        }
+        BigInt::from_parts(self.negative != other.negative, product)
+    }
+}
+
+impl fmt::Display for BigInt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_string_radix(10))
+    }
+}
+
+fn cmp_magnitude(a: &[u32], b: &[u32]) -> Ordering {
+    a.len()
+        .cmp(&b.len())
+        .then_with(|| a.iter().rev().cmp(b.iter().rev()))
+}
+
+fn add_magnitude(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut sum = Vec::with_capacity(a.len().max(b.len()) + 1);
+    let mut carry = 0u64;
+    for i in 0..a.len().max(b.len()) {
+        let cell = *a.get(i).unwrap_or(&0) as u64 + *b.get(i).unwrap_or(&0) as u64 + carry;
+        sum.push(cell as u32);
+        carry = cell >> 32;
+    }
+    sum.push(carry as u32);
+    sum
+}
+
+/// Разность модулей при `a >= b`.
+fn sub_magnitude(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut difference = Vec::with_capacity(a.len());
+    let mut borrow = 0i64;
+    for (i, &limb) in a.iter().enumerate() {
+        let mut cell = limb as i64 - *b.get(i).unwrap_or(&0) as i64 - borrow;
+        borrow = (cell < 0) as i64;
+        if cell < 0 {
+            cell += LIMB as i64;
+        }
+        difference.push(cell as u32);
+    }
+    difference
+}
+
+/// Деление модулей «в столбик» по одному биту; делитель не ноль.
+fn div_rem_magnitude(dividend: &[u32], divisor: &[u32]) -> (Vec<u32>, Vec<u32>) {
+    if let [divisor] = divisor {
+        let mut quotient = dividend.to_vec();
+        let remainder = div_rem_small(&mut quotient, *divisor);
+        return (quotient, vec![remainder]);
+    }
+    if cmp_magnitude(dividend, divisor) == Ordering::Less {
+        return (Vec::new(), dividend.to_vec());
+    }
+    let mut quotient = vec![0u32; dividend.len()];
+    let mut remainder = Vec::with_capacity(divisor.len() + 1);
+    for bit in (0..dividend.len() * 32).rev() {
+        // remainder = remainder * 2 + очередной бит делимого
+        let mut carry = (dividend[bit / 32] >> (bit % 32)) & 1;
+        for limb in remainder.iter_mut() {
+            let shifted = (*limb << 1) | carry;
+            carry = *limb >> 31;
+            *limb = shifted;
+        }
+        if carry > 0 {
+            remainder.push(carry);
+        }
+        if cmp_magnitude(&remainder, divisor) != Ordering::Less {
+            remainder = sub_magnitude(&remainder, divisor);
+            while remainder.last() == Some(&0) {
+                remainder.pop();
+            }
+            quotient[bit / 32] |= 1 << (bit % 32);
+        }
+    }
+    (quotient, remainder)
+}
+
+/// `magnitude = magnitude * factor + addend`.
+fn mul_add_small(magnitude: &mut Vec<u32>, factor: u32, addend: u32) {
+    let mut carry = addend as u64;
+    for limb in magnitude.iter_mut() {
+        let cell = *limb as u64 * factor as u64 + carry;
+        *limb = cell as u32;
+        carry = cell >> 32;
+    }
+    if carry > 0 {
+        magnitude.push(carry as u32);
+    }
+}
+
+/// Делит модуль на `divisor` на месте и возвращает остаток.
+fn div_rem_small(magnitude: &mut Vec<u32>, divisor: u32) -> u32 {
+    let mut remainder = 0u64;
+    for limb in magnitude.iter_mut().rev() {
+        let cell = (remainder << 32) | *limb as u64;
+        *limb = (cell / divisor as u64) as u32;
+        remainder = cell % divisor as u64;
+    }
+    while magnitude.last() == Some(&0) {
+        magnitude.pop();
+    }
+    remainder as u32
+}
+
+/// Целое, не поместившееся в `i64`. Помнит систему счисления литерала, как и `RadixValue`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BigIntegerValue {
+    pub value: BigInt,
+    pub radix: Radix,
+}
+
+impl ObjectValue for BigIntegerValue {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn clone_value(&self) -> Box<dyn ObjectValue> {
+        Box::new(self.clone())
+    }
+}
+
+impl fmt::Display for BigIntegerValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.value.is_negative() {
+            write!(f, "-")?;
+        }
+        let magnitude = match self.value.is_negative() {
+            true => -self.value.clone(),
+            false => self.value.clone(),
+        };
+        let digits: String = magnitude
+            .to_string_radix(self.radix.base())
+            .chars()
+            .map(|ch| self.radix.from_standard(ch))
+            .collect();
+        write!(f, "{}{}", self.radix.prefix(), digits)
+    }
+}
+
+pub struct BigInteger;
+impl BigInteger {
+    pub fn new_instance(proto: PrototypeRef, value: BigInt, radix: Radix) -> ObjectRef {
+        Object::new(proto, Some(Box::new(BigIntegerValue { value, radix })))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_big_integer_arithmetic() {
+        let max = BigInt::from(i64::MAX);
+        let sum = &max + &BigInt::from(1);
+        assert_eq!(sum.to_string(), "9223372036854775808");
+        assert_eq!(sum.to_i64(), None);
+        assert_eq!((&sum - &BigInt::from(1)).to_i64(), Some(i64::MAX));
+        assert_eq!(BigInt::from(i64::MIN).to_i64(), Some(i64::MIN));
+
+        let power = BigInt::from(2).pow(100);
+        assert_eq!(power.to_string(), "1267650600228229401496703205376");
+        assert_eq!(
+            (&power * &BigInt::from(-3)).to_string(),
+            "-3802951800684688204490109616128"
+        );
+        assert_eq!(
+            BigInt::parse_radix("-ffffffffffffffffff", 16)
+                .unwrap()
+                .to_string_radix(16),
+            "-FFFFFFFFFFFFFFFFFF"
+        );
+        assert!(BigInt::from(-5) < BigInt::from(3));
+        assert!((&BigInt::from(7) - &BigInt::from(7)).is_zero());
+
+        let (quotient, remainder) = power.div_rem(&BigInt::from(10).pow(20)).unwrap();
+        assert_eq!(quotient.to_string(), "12676506002");
+        assert_eq!(remainder.to_string(), "28229401496703205376");
+        let (quotient, remainder) = BigInt::from(-7).div_mod_floor(&BigInt::from(2)).unwrap();
+        assert_eq!((quotient.to_i64(), remainder.to_i64()), (Some(-4), Some(1)));
+        assert_eq!(
+            BigInt::from(2).pow(80).gcd(&BigInt::from(-48)).to_i64(),
+            Some(16)
+        );
+        assert_eq!(BigInt::from(1).div_rem(&BigInt::zero()), None);
+        assert_eq!(BigInt::parse_radix("12G", 16), None);
+    }
+}
@@ -0,0 +1,45 @@
+use crate::interpretator::prototypes::types::object::object::{Object, ObjectRef, ObjectValue};
+use crate::interpretator::prototypes::types::prototype::PrototypeRef;
+use std::any::Any;
+use std::fmt;
+
+#[derive(Debug, Clone)]
+pub struct FloatValue(pub f64);
+
+impl ObjectValue for FloatValue {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn clone_value(&self) -> Box<dyn ObjectValue> {
+        Box::new(self.clone())
+    }
+}
+
+impl fmt::Display for FloatValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Целое значение всё равно печатается как дробное: 10.0, а не 10
+        if self.0.is_finite() && self.0.fract() == 0.0 {
+            write!(f, "{:.1}", self.0)
+        } else {
+            write_real(f, self.0)
+        }
+    }
+}
+
+/// Дробное в синтаксисе LaCoN: бесконечности и `NaN` записываются так же,
+/// как их читает документ — `Infinity`, `-Infinity`, `NaN`, а не `inf`.
+pub fn write_real(f: &mut fmt::Formatter<'_>, value: f64) -> fmt::Result {
+    match value {
+        _ if value.is_nan() => write!(f, "NaN"),
+        f64::INFINITY => write!(f, "Infinity"),
+        f64::NEG_INFINITY => write!(f, "-Infinity"),
+        _ => write!(f, "{}", value),
+    }
+}
+
+pub struct Float;
+impl Float {
+    pub fn new_instance(proto: PrototypeRef, value: f64) -> ObjectRef {
+        Object::new(proto, Some(Box::new(FloatValue(value))))
+    }
+}
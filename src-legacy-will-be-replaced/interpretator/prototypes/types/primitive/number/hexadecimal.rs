@@ -0,0 +1,139 @@
+use crate::interpretator::prototypes::types::object::object::{Object, ObjectRef, ObjectValue};
+use crate::interpretator::prototypes::types::prototype::PrototypeRef;
+use std::any::Any;
+use std::fmt;
+
+/// Система счисления, в которой записан целый литерал.
+///
+/// Значение её запоминает и печатается так же, как было записано: `0x1E + 1 = 0x1F`.
+/// `0t` — base32 с цифрами `0–9A–V`, `0c` — base32 Крокфорда: `0–9` и латинские буквы
+/// без `I`, `L`, `O`, `U`. Оба варианта — тип `Base32`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Radix {
+    Decimal,
+    Binary,
+    Octal,
+    Hex,
+    Base32,
+    Crockford,
+}
+
+/// Цифры base32 Крокфорда по порядку значений.
+const CROCKFORD_DIGITS: &str = "0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+impl Radix {
+    /// Система по букве после `0`: `0b`, `0o`, `0x`, `0t`, `0c`, без учёта регистра.
+    pub fn from_prefix(letter: char) -> Option<Self> {
+        match letter.to_ascii_lowercase() {
+            'b' => Some(Radix::Binary),
+            'o' => Some(Radix::Octal),
+            'x' => Some(Radix::Hex),
+            't' => Some(Radix::Base32),
+            'c' => Some(Radix::Crockford),
+            _ => None,
+        }
+    }
+
+    pub fn base(&self) -> u32 {
+        match self {
+            Radix::Decimal => 10,
+            Radix::Binary => 2,
+            Radix::Octal => 8,
+            Radix::Hex => 16,
+            Radix::Base32 | Radix::Crockford => 32,
+        }
+    }
+
+    /// Цифра ли `ch` в этой системе. Буквы-цифры `0t` и `0c` только заглавные,
+    /// иначе `0cm` и `0cd` перестали бы быть нулём с единицей.
+    pub fn is_digit(&self, ch: char) -> bool {
+        match self {
+            Radix::Crockford => CROCKFORD_DIGITS.contains(ch),
+            Radix::Base32 if ch.is_ascii_lowercase() => false,
+            _ => ch.is_digit(self.base()),
+        }
+    }
+
+    /// Цифра в обычной записи `0–9A–Z`, которую понимают `to_digit` и `from_digit`:
+    /// у Крокфорда `Z` — тридцать первая цифра, то есть `V`.
+    pub fn to_standard(&self, ch: char) -> Option<char> {
+        match self {
+            Radix::Crockford => {
+                let digit = CROCKFORD_DIGITS.find(ch)?;
+                char::from_digit(digit as u32, 32).map(|ch| ch.to_ascii_uppercase())
+            }
+            _ => self.is_digit(ch).then_some(ch),
+        }
+    }
+
+    /// Обратное к [`Radix::to_standard`]: цифра `0–9A–Z` в записи этой системы.
+    pub fn from_standard(&self, ch: char) -> char {
+        match (self, ch.to_digit(32)) {
+            (Radix::Crockford, Some(digit)) => CROCKFORD_DIGITS.as_bytes()[digit as usize] as char,
+            _ => ch,
+        }
+    }
+
+    pub fn prefix(&self) -> &'static str {
+        match self {
+            Radix::Decimal => "",
+            Radix::Binary => "0b",
+            Radix::Octal => "0o",
+            Radix::Hex => "0x",
+            Radix::Base32 => "0t",
+            Radix::Crockford => "0c",
+        }
+    }
+}
+
+/// Целое, записанное не в десятичной системе: `0b1011110011`, `0o071`, `0x348FABD1`, `0tL1FF`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RadixValue {
+    pub value: i64,
+    pub radix: Radix,
+}
+
+impl ObjectValue for RadixValue {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn clone_value(&self) -> Box<dyn ObjectValue> {
+        Box::new(self.clone())
+    }
+}
+
+impl fmt::Display for RadixValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.value < 0 {
+            write!(f, "-")?;
+        }
+        let digits = radix_digits(self.value.unsigned_abs(), self.radix.base());
+        let digits: String = digits
+            .chars()
+            .map(|ch| self.radix.from_standard(ch))
+            .collect();
+        write!(f, "{}{}", self.radix.prefix(), digits)
+    }
+}
+
+/// Цифры числа в системе `base` заглавными буквами.
+fn radix_digits(mut value: u64, base: u32) -> String {
+    let mut digits = Vec::new();
+    loop {
+        let digit = (value % base as u64) as u32;
+        let ch = char::from_digit(digit, base).expect("цифра меньше основания");
+        digits.push(ch.to_ascii_uppercase());
+        value /= base as u64;
+        if value == 0 {
+            break;
+        }
+    }
+    digits.iter().rev().collect()
+}
+
+pub struct RadixInteger;
+impl RadixInteger {
+    pub fn new_instance(proto: PrototypeRef, value: i64, radix: Radix) -> ObjectRef {
+        Object::new(proto, Some(Box::new(RadixValue { value, radix })))
+    }
+}
@@ -0,0 +1,10 @@
+use crate::interpretator::prototypes::types::object::object::{IntegerValue, Object, ObjectRef};
+use crate::interpretator::prototypes::types::prototype::PrototypeRef;
+
+pub struct Integer;
+impl Integer {
+    /// Значение хранится в `IntegerValue` из `object.rs`.
+    pub fn new_instance(proto: PrototypeRef, value: i64) -> ObjectRef {
+        Object::new(proto, Some(Box::new(IntegerValue(value))))
+    }
+}
@@ -0,0 +1,370 @@
+use crate::interpretator::prototypes::types::object::object::{Object, ObjectRef, ObjectValue};
+use crate::interpretator::prototypes::types::prototype::PrototypeRef;
+use std::any::Any;
+use std::fmt;
+use thiserror::Error;
+
+/// Значение строки: `"text"`, `'text'`, `` `text` `` или текст без кавычек в объявлении.
+#[derive(Debug, Clone)]
+pub struct StringValue(pub std::string::String);
+
+impl ObjectValue for StringValue {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn clone_value(&self) -> Box<dyn ObjectValue> {
+        Box::new(self.clone())
+    }
+}
+
+impl fmt::Display for StringValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+pub struct String;
+impl String {
+    pub fn new_instance(proto: PrototypeRef, value: std::string::String) -> ObjectRef {
+        Object::new(proto, Some(Box::new(StringValue(value))))
+    }
+}
+
+/// Ошибка шаблона `format()`.
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum FormatError {
+    #[error("незакрытая «{{» в шаблоне «{0}»")]
+    Unclosed(std::string::String),
+    #[error("одиночная «}}» в шаблоне «{0}»: закрывающая скобка записывается как «}}}}»")]
+    UnmatchedBrace(std::string::String),
+    #[error("некорректный формат «{{{0}}}»")]
+    InvalidSpec(std::string::String),
+    #[error("нет аргумента {index} для шаблона: передано {count}")]
+    MissingArgument { index: usize, count: usize },
+    #[error("формат «{spec}» не применим к {found}")]
+    Mismatch {
+        spec: std::string::String,
+        found: &'static str,
+    },
+    #[error("переполнение при сдвиге на «({0})»")]
+    Overflow(std::string::String),
+}
+
+/// Аргумент шаблона: числа форматируются по спецификации, остальное — как текст.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FormatArg {
+    Integer(i64),
+    Float(f64),
+    Text(std::string::String),
+}
+
+impl FormatArg {
+    fn kind(&self) -> &'static str {
+        match self {
+            FormatArg::Integer(_) => "целому числу",
+            FormatArg::Float(_) => "дробному числу",
+            FormatArg::Text(_) => "тексту",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Align {
+    Left,
+    Center,
+    Right,
+}
+
+/// Спецификация после двоеточия: `{:>8}`, `{:+.2}`, `{:#010b}`, `{:04X(0x1F031)}`.
+#[derive(Debug, Clone, Default, PartialEq)]
+struct Spec {
+    fill: Option<char>,
+    align: Option<Align>,
+    plus: bool,
+    /// `#` — префикс системы счисления: `0x`, `0o`, `0b`
+    alternate: bool,
+    zero: bool,
+    width: usize,
+    precision: Option<usize>,
+    /// `x`, `X`, `o`, `b`, `e` или `E`
+    radix: Option<char>,
+    /// `(0x1F031)` — число, прибавляемое к целому аргументу перед выводом
+    offset: Option<i64>,
+}
+
+/// Подставляет аргументы в шаблон в духе `format!` из Rust.
+///
+/// `{}` берёт следующий аргумент, `{1}` — аргумент по номеру, `{{` и `}}` — сами скобки.
+/// После двоеточия — заполнитель и выравнивание (`<`, `^`, `>`), `+`, `#`, `0`, ширина,
+/// `.точность` и основание (`x`, `X`, `o`, `b`, `e`, `E`). Целое можно сдвинуть на
+/// смещение в скобках: `{:04X(0x1F031)}` с аргументом `2` даёт `1F033`.
+pub fn format_template(
+    template: &str,
+    arguments: &[FormatArg],
+) -> Result<std::string::String, FormatError> {
+    let mut output = std::string::String::new();
+    let mut next = 0;
+    let mut chars = template.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.as_str().starts_with('{') => {
+                chars.next();
+                output.push('{');
+            }
+            '{' => {
+                let rest = chars.as_str();
+                let end = rest
+                    .find('}')
+                    .ok_or_else(|| FormatError::Unclosed(template.to_string()))?;
+                let placeholder = &rest[..end];
+                chars = rest[end + 1..].chars();
+                let (position, spec) = placeholder.split_once(':').unwrap_or((placeholder, ""));
+                let index = match position.trim() {
+                    "" => {
+                        next += 1;
+                        next - 1
+                    }
+                    position => position
+                        .parse()
+                        .map_err(|_| FormatError::InvalidSpec(placeholder.to_string()))?,
+                };
+                let argument = arguments.get(index).ok_or(FormatError::MissingArgument {
+                    index,
+                    count: arguments.len(),
+                })?;
+                let parsed = parse_spec(spec)
+                    .ok_or_else(|| FormatError::InvalidSpec(placeholder.to_string()))?;
+                output.push_str(&apply_spec(&parsed, argument, spec)?);
+            }
+            '}' if chars.as_str().starts_with('}') => {
+                chars.next();
+                output.push('}');
+            }
+            '}' => return Err(FormatError::UnmatchedBrace(template.to_string())),
+            c => output.push(c),
+        }
+    }
+    Ok(output)
+}
+
+fn parse_spec(text: &str) -> Option<Spec> {
+    let mut spec = Spec::default();
+    let mut rest = text;
+    let align = |c: char| match c {
+        '<' => Some(Align::Left),
+        '^' => Some(Align::Center),
+        '>' => Some(Align::Right),
+        _ => None,
+    };
+    let mut leading = rest.chars();
+    match (leading.next(), leading.next()) {
+        (Some(fill), Some(c)) if align(c).is_some() => {
+            spec.fill = Some(fill);
+            spec.align = align(c);
+            rest = leading.as_str();
+        }
+        (Some(c), _) if align(c).is_some() => {
+            spec.align = align(c);
+            rest = &rest[1..];
+        }
+        _ => {}
+    }
+    if let Some(after) = rest.strip_prefix('+') {
+        spec.plus = true;
+        rest = after;
+    }
+    if let Some(after) = rest.strip_prefix('#') {
+        spec.alternate = true;
+        rest = after;
+    }
+    if let Some(after) = rest.strip_prefix('0') {
+        spec.zero = true;
+        rest = after;
+    }
+    let digits = rest.len() - rest.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+    if digits > 0 {
+        spec.width = rest[..digits].parse().ok()?;
+        rest = &rest[digits..];
+    }
+    if let Some(after) = rest.strip_prefix('.') {
+        let digits = after.len() - after.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+        spec.precision = Some(after[..digits].parse().ok()?);
+        rest = &after[digits..];
+    }
+    if let Some(c) = rest.chars().next().filter(|c| "xXobeE".contains(*c)) {
+        spec.radix = Some(c);
+        rest = &rest[1..];
+    }
+    if let Some(offset) = rest.strip_prefix('(') {
+        spec.offset = Some(parse_offset(offset.strip_suffix(')')?)?);
+        rest = "";
+    }
+    rest.is_empty().then_some(spec)
+}
+
+/// Смещение — целый литерал: `0x1F031`, `-16`, `0b1010`, `1_000`.
+fn parse_offset(text: &str) -> Option<i64> {
+    let digits = text.trim().replace('_', "");
+    let (negative, digits) = match digits.strip_prefix('-') {
+        Some(digits) => (true, digits.to_string()),
+        None => (false, digits),
+    };
+    let (radix, digits) = match digits.get(..2) {
+        Some("0x") => (16, &digits[2..]),
+        Some("0o") => (8, &digits[2..]),
+        Some("0b") => (2, &digits[2..]),
+        _ => (10, digits.as_str()),
+    };
+    let value = i64::from_str_radix(digits, radix).ok()?;
+    Some(if negative { -value } else { value })
+}
+
+fn apply_spec(
+    spec: &Spec,
+    argument: &FormatArg,
+    text: &str,
+) -> Result<std::string::String, FormatError> {
+    let mismatch = || FormatError::Mismatch {
+        spec: format!("{{:{}}}", text),
+        found: argument.kind(),
+    };
+    let argument = match (argument, spec.offset) {
+        (FormatArg::Integer(value), Some(offset)) => FormatArg::Integer(
+            value
+                .checked_add(offset)
+                .ok_or_else(|| FormatError::Overflow(text.to_string()))?,
+        ),
+        (_, Some(_)) => return Err(mismatch()),
+        (argument, None) => argument.clone(),
+    };
+    let (negative, prefix, digits) = match (&argument, spec.radix) {
+        (FormatArg::Text(text), None) => {
+            let text = match spec.precision {
+                Some(precision) => text.chars().take(precision).collect(),
+                None => text.clone(),
+            };
+            return Ok(pad(spec, "", &text, Align::Left));
+        }
+        (FormatArg::Text(_), Some(_)) => return Err(mismatch()),
+        (FormatArg::Integer(value), Some(radix @ ('x' | 'X' | 'o' | 'b'))) => {
+            let magnitude = value.unsigned_abs();
+            let (prefix, digits) = match radix {
+                'x' => ("0x", format!("{:x}", magnitude)),
+                'X' => ("0x", format!("{:X}", magnitude)),
+                'o' => ("0o", format!("{:o}", magnitude)),
+                _ => ("0b", format!("{:b}", magnitude)),
+            };
+            (*value < 0, prefix, digits)
+        }
+        (FormatArg::Float(_), Some('x' | 'X' | 'o' | 'b')) => return Err(mismatch()),
+        (FormatArg::Integer(value), None) => (*value < 0, "", value.unsigned_abs().to_string()),
+        (argument, radix) => {
+            let value = match argument {
+                FormatArg::Integer(value) => *value as f64,
+                FormatArg::Float(value) => *value,
+                FormatArg::Text(_) => unreachable!("текст разобран выше"),
+            };
+            let magnitude = value.abs();
+            let digits = match (radix, spec.precision) {
+                (Some('e'), Some(precision)) => format!("{:.*e}", precision, magnitude),
+                (Some('e'), None) => format!("{:e}", magnitude),
+                (Some(_), Some(precision)) => format!("{:.*E}", precision, magnitude),
+                (Some(_), None) => format!("{:E}", magnitude),
+                (None, Some(precision)) => format!("{:.*}", precision, magnitude),
+                (None, None) => magnitude.to_string(),
+            };
+            (value.is_sign_negative() && !value.is_nan(), "", digits)
+        }
+    };
+    let sign = match (negative, spec.plus) {
+        (true, _) => "-",
+        (false, true) => "+",
+        (false, false) => "",
+    };
+    let prefix = format!("{}{}", sign, if spec.alternate { prefix } else { "" });
+    Ok(pad(spec, &prefix, &digits, Align::Right))
+}
+
+/// Дополняет до ширины: с `0` — нулями после знака и префикса, иначе заполнителем.
+fn pad(spec: &Spec, prefix: &str, digits: &str, default: Align) -> std::string::String {
+    let length = prefix.chars().count() + digits.chars().count();
+    let missing = spec.width.saturating_sub(length);
+    if spec.zero && spec.align.is_none() && default == Align::Right {
+        return format!("{}{}{}", prefix, "0".repeat(missing), digits);
+    }
+    let fill = spec.fill.unwrap_or(' ').to_string();
+    let (before, after) = match spec.align.unwrap_or(default) {
+        Align::Left => (0, missing),
+        Align::Center => (missing / 2, missing - missing / 2),
+        Align::Right => (missing, 0),
+    };
+    format!(
+        "{}{}{}{}",
+        fill.repeat(before),
+        prefix,
+        digits,
+        fill.repeat(after)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interpretator::engine::engine::tests::entry;
+    use crate::interpretator::engine::engine::Engine;
+
+    #[test]
+    fn test_format_template() {
+        let format =
+            |template: &str, arguments: &[FormatArg]| format_template(template, arguments).unwrap();
+        let integer = FormatArg::Integer;
+        assert_eq!(format("{:04X(0x1F031)}", &[integer(2)]), "1F033");
+        assert_eq!(format("{:08.3}", &[FormatArg::Float(-1.23456)]), "-001.235");
+        assert_eq!(format("{:#010b}", &[integer(5)]), "0b00000101");
+        assert_eq!(format("{:*^7}", &[FormatArg::Text("ab".into())]), "**ab***");
+        assert_eq!(
+            format("{1}-{0} {{}} {:+}", &[integer(1), integer(2)]),
+            "2-1 {} +1"
+        );
+        assert_eq!(format("{:x(-16)}", &[integer(20)]), "4");
+        assert_eq!(format("{:.2e}", &[FormatArg::Float(1500.0)]), "1.50e3");
+        assert!(matches!(
+            format_template("{:x}", &[FormatArg::Text("a".into())]),
+            Err(FormatError::Mismatch { .. })
+        ));
+        assert!(matches!(
+            format_template("{} {}", &[integer(1)]),
+            Err(FormatError::MissingArgument { index: 1, count: 1 })
+        ));
+    }
+
+    #[test]
+    fn test_string_templates() {
+        let source = r#"
+const done = true
+const base = 0x1F600
+status "${if (done) "готово" else "в работе"}: ${done ? 1 : 0}"
+price "\$${2 + 3}"
+smile "\u{1F600}\u{${base + 3}}"
+letter \u{41}
+glyph format("U+{:04X(0x1F031)}", 2)
+table format("{:>6.2}|{:<4}|{1:*^6}", 3.14159, "ab")
+"#;
+        let root = Engine::new().evaluate_document(source).unwrap();
+        assert_eq!(entry(&root, "status"), "готово: 1");
+        assert_eq!(entry(&root, "price"), "$5");
+        assert_eq!(entry(&root, "smile"), "😀😃");
+        assert_eq!(entry(&root, "letter"), "A");
+        assert_eq!(entry(&root, "glyph"), "U+1F033");
+        assert_eq!(entry(&root, "table"), "  3.14|ab  |**ab**");
+
+        let errors = Engine::new()
+            .evaluate_document("bad \"\\u{110000}\"\nhex format(\"{:x}\", \"text\")\n")
+            .unwrap_err();
+        let codes: Vec<_> = errors
+            .iter()
+            .filter_map(|error| error.code.as_deref())
+            .collect();
+        assert_eq!(codes, ["E0006", "E0200"]);
+    }
+}
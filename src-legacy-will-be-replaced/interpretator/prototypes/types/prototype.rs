@@ -1,6 +1,6 @@
 use crate::interpretator::prototypes::types::object::object::ObjectRef;
+use indexmap::IndexMap;
 use std::cell::RefCell;
-use std::collections::HashMap;
 use std::rc::Rc;
 
 /// Умный указатель для прототипа, чтобы несколько объектов могли ссылаться на один чертеж.
@@ -11,7 +11,7 @@ pub struct Prototype {
     /// Ссылка на родительский прототип для реализации цепочки наследования.
     pub parent: Option<PrototypeRef>,
     /// Таблица методов или констант, принадлежащих прототипу.
-    pub properties: HashMap<String, ObjectRef>,
+    pub properties: IndexMap<String, ObjectRef>,
 }
 
 impl Prototype {
@@ -19,7 +19,7 @@ impl Prototype {
         Rc::new(RefCell::new(Prototype {
             name: name.to_string(),
             parent,
-            properties: HashMap::new(),
+            properties: IndexMap::new(),
         }))
     }
 
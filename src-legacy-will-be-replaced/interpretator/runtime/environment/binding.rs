@@ -0,0 +1,24 @@
+use crate::interpretator::prototypes::types::object::object::ObjectRef;
+
+/// Имя, связанное со значением: переменная (`let`) или постоянная (`const`).
+#[derive(Debug, Clone)]
+pub struct Binding {
+    pub value: ObjectRef,
+    pub mutable: bool,
+}
+
+impl Binding {
+    pub fn constant(value: ObjectRef) -> Self {
+        Binding {
+            value,
+            mutable: false,
+        }
+    }
+
+    pub fn variable(value: ObjectRef) -> Self {
+        Binding {
+            value,
+            mutable: true,
+        }
+    }
+}
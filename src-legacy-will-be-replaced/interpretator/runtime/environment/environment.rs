@@ -0,0 +1,77 @@
+use crate::interpretator::prototypes::types::object::object::ObjectRef;
+use crate::interpretator::runtime::environment::binding::Binding;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+/// Умный указатель на область видимости: вложенные блоки держат ссылку на родителя.
+pub type EnvironmentRef = Rc<RefCell<Environment>>;
+
+/// Почему не удалось изменить связывание.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AssignError {
+    /// Имя не объявлено ни в одной из областей.
+    Undefined,
+    /// Имя объявлено через `const`.
+    Constant,
+}
+
+/// Область видимости: таблица имён текущего блока и ссылка на объемлющую.
+pub struct Environment {
+    pub parent: Option<EnvironmentRef>,
+    bindings: HashMap<String, Binding>,
+}
+
+impl Environment {
+    pub fn new(parent: Option<EnvironmentRef>) -> EnvironmentRef {
+        Rc::new(RefCell::new(Environment {
+            parent,
+            bindings: HashMap::new(),
+        }))
+    }
+
+    /// Самая внешняя область — та, куда попадают `global`-объявления.
+    pub fn global(env: &EnvironmentRef) -> EnvironmentRef {
+        match env.borrow().parent {
+            Some(ref parent) => Environment::global(parent),
+            None => Rc::clone(env),
+        }
+    }
+
+    /// Объявляет имя в текущей области. Возвращает `false`, если оно уже объявлено здесь же.
+    pub fn define(&mut self, name: &str, binding: Binding) -> bool {
+        if self.bindings.contains_key(name) {
+            return false;
+        }
+        self.bindings.insert(name.to_string(), binding);
+        true
+    }
+
+    /// Поиск имени вверх по цепочке областей.
+    pub fn lookup(&self, name: &str) -> Option<Binding> {
+        if let Some(binding) = self.bindings.get(name) {
+            return Some(binding.clone());
+        }
+        self.parent.as_ref()?.borrow().lookup(name)
+    }
+
+    /// Только в текущей области, без подъёма к родителю (`local.name`).
+    pub fn lookup_local(&self, name: &str) -> Option<Binding> {
+        self.bindings.get(name).cloned()
+    }
+
+    /// Присваивает новое значение ближайшему связыванию с этим именем.
+    pub fn assign(&mut self, name: &str, value: ObjectRef) -> Result<(), AssignError> {
+        match self.bindings.get_mut(name) {
+            Some(binding) if !binding.mutable => Err(AssignError::Constant),
+            Some(binding) => {
+                binding.value = value;
+                Ok(())
+            }
+            None => match self.parent {
+                Some(ref parent) => parent.borrow_mut().assign(name, value),
+                None => Err(AssignError::Undefined),
+            },
+        }
+    }
+}
@@ -0,0 +1,3 @@
+pub mod binding;
+#[allow(clippy::module_inception, reason = "окружение живёт в «environment/environment.rs» по раскладке каталогов")]
+pub mod environment;
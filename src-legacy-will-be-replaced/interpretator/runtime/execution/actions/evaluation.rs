@@ -0,0 +1,2267 @@
+use crate::extensions::json::interpretate_types::parse_json;
+use crate::interpretator::analysis::schema::compiler::compile_schema;
+use crate::interpretator::analysis::schema::validator::{validate, SchemaUse, Written};
+use crate::interpretator::engine::dialect::{check_list, Dialect};
+use crate::interpretator::frontend::ast::expression::{BinaryOp, Expr, ExprKind, StringSegment};
+use crate::interpretator::frontend::ast::node::{TypeExpr, TypeExprKind};
+use crate::interpretator::frontend::ast::statement::{
+    AssignOp, Block, DeclKind, Entry, EntryKey, Expansion, Modifier, Pattern, Stmt, StmtKind,
+    VarDecl,
+};
+use crate::interpretator::frontend::diagnostics::error::Diagnostic;
+use crate::interpretator::frontend::diagnostics::reporter::Reporter;
+use crate::interpretator::frontend::diagnostics::span::Span;
+use crate::interpretator::frontend::parser::parser::parse_source;
+use crate::interpretator::modules::loader::file_loader::{FileLoader, SourceFile, SourceFormat};
+use crate::interpretator::modules::resolver::path_resolution::resolve_include;
+use crate::interpretator::prototypes::types::formal::formal::{kind_dimension, Unit, UnitError};
+use crate::interpretator::prototypes::types::formal::fraction::{FractionValue, Rational};
+use crate::interpretator::prototypes::types::formal::temperature::{
+    TemperatureScale, TemperatureValue,
+};
+use crate::interpretator::prototypes::types::object::array::ArrayValue;
+use crate::interpretator::prototypes::types::object::date_time::{parse_offset, DateTimeValue};
+use crate::interpretator::prototypes::types::object::dictionary::DictionaryValue;
+use crate::interpretator::prototypes::types::object::object::{Object, ObjectRef, ObjectValue};
+use crate::interpretator::prototypes::types::object::routine::routine::{
+    RoutineContent, RoutineValue,
+};
+use crate::interpretator::prototypes::types::primitive::color::{ColorSpace, ColorValue};
+use crate::interpretator::prototypes::types::primitive::number::big_integer::BigInt;
+use crate::interpretator::prototypes::types::primitive::number::hexadecimal::Radix;
+use crate::interpretator::prototypes::types::primitive::operator::{OperatorType, OperatorValue};
+use crate::interpretator::runtime::environment::binding::Binding;
+use crate::interpretator::runtime::environment::environment::{
+    AssignError, Environment, EnvironmentRef,
+};
+use crate::interpretator::stdlib::core::ops::{self, OpError, MAX_ITERATIONS};
+use crate::interpretator::stdlib::core::prelude::Prelude;
+use crate::interpretator::stdlib::core::primitives::{
+    as_number, as_quantity, as_temperature, as_text, is_empty_value, is_instance_of, is_truthy,
+    type_name, Number,
+};
+use crate::interpretator::stdlib::core::{builtins, colors, dates, quantities, temperatures};
+use indexmap::IndexMap;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::rc::Rc;
+use thiserror::Error;
+
+/// Ошибка вычисления документа.
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum EvalError {
+    #[error("{error}")]
+    Operation { error: OpError, span: Span },
+    #[error("некорректное число «{literal}»")]
+    InvalidNumber { literal: String, span: Span },
+    #[error("«{name}» уже объявлено в этой области")]
+    AlreadyDefined { name: String, span: Span },
+    #[error("«{name}» — постоянная, её нельзя изменить")]
+    ConstantAssignment { name: String, span: Span },
+    #[error("«{name}» не объявлено")]
+    Undefined { name: String, span: Span },
+    #[error("ожидалось значение типа {expected}, получено {found}")]
+    TypeMismatch {
+        expected: String,
+        found: String,
+        span: Span,
+    },
+    #[error("нет элемента «{key}»")]
+    MissingKey { key: String, span: Span },
+    #[error("значение типа {found} нельзя вызвать")]
+    NotCallable { found: String, span: Span },
+    #[error("{what} не поддерживается в режиме данных")]
+    Unsupported { what: String, span: Span },
+    #[error("{message}")]
+    Invalid { message: String, span: Span },
+    #[error("не удалось вставить «{path}»: {message}")]
+    Include {
+        path: String,
+        message: String,
+        span: Span,
+    },
+    #[error("{error}")]
+    Unit { error: UnitError, span: Span },
+    #[error("не удалось подключить схему «{path}»: {message}")]
+    Schema {
+        path: String,
+        message: String,
+        span: Span,
+    },
+    #[error("ключ «{key}» есть и в документе, и во вставленном «{path}»")]
+    Collision {
+        key: String,
+        path: String,
+        span: Span,
+    },
+}
+
+impl EvalError {
+    pub fn span(&self) -> Span {
+        match self {
+            EvalError::Operation { span, .. }
+            | EvalError::InvalidNumber { span, .. }
+            | EvalError::AlreadyDefined { span, .. }
+            | EvalError::ConstantAssignment { span, .. }
+            | EvalError::Undefined { span, .. }
+            | EvalError::TypeMismatch { span, .. }
+            | EvalError::MissingKey { span, .. }
+            | EvalError::NotCallable { span, .. }
+            | EvalError::Unsupported { span, .. }
+            | EvalError::Invalid { span, .. }
+            | EvalError::Include { span, .. }
+            | EvalError::Unit { span, .. }
+            | EvalError::Schema { span, .. }
+            | EvalError::Collision { span, .. } => *span,
+        }
+    }
+
+    /// Код ошибки для диагностик.
+    pub fn code(&self) -> &'static str {
+        match self {
+            EvalError::Operation { .. } => "E0200",
+            EvalError::InvalidNumber { .. } => "E0201",
+            EvalError::AlreadyDefined { .. } => "E0202",
+            EvalError::ConstantAssignment { .. } => "E0203",
+            EvalError::Undefined { .. } => "E0204",
+            EvalError::TypeMismatch { .. } => "E0205",
+            EvalError::MissingKey { .. } => "E0206",
+            EvalError::NotCallable { .. } => "E0207",
+            EvalError::Unsupported { .. } => "E0208",
+            EvalError::Invalid { .. } => "E0209",
+            EvalError::Include { .. } => "E0212",
+            EvalError::Unit { .. } => "E0213",
+            EvalError::Collision { .. } => "E0216",
+            EvalError::Schema { .. } => "E0217",
+        }
+    }
+
+    fn unsupported(what: impl Into<String>, span: Span) -> Self {
+        EvalError::Unsupported {
+            what: what.into(),
+            span,
+        }
+    }
+
+    fn invalid(message: impl Into<String>, span: Span) -> Self {
+        EvalError::Invalid {
+            message: message.into(),
+            span,
+        }
+    }
+}
+
+impl From<EvalError> for Diagnostic {
+    fn from(error: EvalError) -> Self {
+        let diagnostic = Diagnostic::error(error.to_string(), error.span()).with_code(error.code());
+        match error {
+            EvalError::ConstantAssignment { .. } => {
+                diagnostic.with_help("объявите имя через «let», чтобы его можно было менять")
+            }
+            EvalError::Undefined { .. } => {
+                diagnostic.with_help("объявите имя через «let» или «const» до использования")
+            }
+            EvalError::Collision { .. } => diagnostic
+                .with_help("переименуйте ключ или уберите его из «provide» вставляемого файла"),
+            _ => diagnostic,
+        }
+    }
+}
+
+type EvalResult<T> = Result<T, EvalError>;
+
+/// Куда попадают элементы данных при выполнении блока.
+#[derive(Debug)]
+pub enum Target {
+    Dictionary(IndexMap<String, ObjectRef>),
+    Array(Vec<ObjectRef>),
+}
+
+/// Что отдал `yield`: значение или пару `ключ, значение`.
+#[derive(Debug)]
+enum Yielded {
+    Value(ObjectRef, Span),
+    Pair(String, ObjectRef, Span),
+}
+
+/// Чем закончилось выполнение инструкции.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Flow {
+    Normal,
+    Break,
+    Continue,
+    /// `cancel`: остаток текущего блока пропускается, выполнение идёт после блока
+    Cancel,
+}
+
+/// Вычислитель режима данных: выполняет инструкции документа, собирая элементы
+/// в `Target`, и вычисляет выражения в объекты `ObjectRef`.
+///
+/// Ошибка в инструкции сообщается в `reporter`, а выполнение продолжается со следующей,
+/// поэтому за один прогон видны все ошибки документа.
+pub struct Evaluator<'a> {
+    prelude: &'a Prelude,
+    reporter: &'a mut Reporter,
+    env: EnvironmentRef,
+    /// Каталог, от которого отсчитываются пути в `include`
+    base_dir: PathBuf,
+    loader: FileLoader,
+    /// Ключи из `provide`: только они видны файлу, который включает этот документ
+    provided: Vec<String>,
+    /// Ключи текущего словаря, вставленные `include`, и пути их файлов
+    included: HashMap<String, String>,
+    /// Схема из `use schema`; по ней проверяется корень документа
+    schema: Option<SchemaUse>,
+    /// Значения `yield` текущего `generate`, `spread` или `filter`
+    yields: Option<Vec<Yielded>>,
+}
+
+impl<'a> Evaluator<'a> {
+    pub fn new(prelude: &'a Prelude, reporter: &'a mut Reporter) -> Self {
+        Evaluator {
+            prelude,
+            reporter,
+            env: Environment::new(None),
+            base_dir: PathBuf::from("."),
+            loader: FileLoader::new(),
+            provided: Vec::new(),
+            included: HashMap::new(),
+            schema: None,
+            yields: None,
+        }
+    }
+
+    pub fn with_base_dir(mut self, base_dir: impl Into<PathBuf>) -> Self {
+        self.base_dir = base_dir.into();
+        self
+    }
+
+    /// Загрузчик вставляемых файлов; помнит цепочку включений для поиска циклов.
+    pub fn with_loader(mut self, loader: FileLoader) -> Self {
+        self.loader = loader;
+        self
+    }
+
+    /// Выполняет инструкции блока, складывая элементы данных в `target`.
+    /// Если документ подключил схему, собранные элементы проверяются по ней.
+    pub fn execute_block(&mut self, statements: &[Stmt], target: &mut Target) {
+        self.execute_root(statements, target);
+        if let Some(usage) = self.schema.take() {
+            self.validate_schema(&usage, statements, target);
+        }
+    }
+
+    pub fn prelude(&self) -> &'a Prelude {
+        self.prelude
+    }
+
+    /// Значение подходит под тип `<Type>`; неизвестные движку типы подходят всегда.
+    pub fn matches_type(&self, value: &ObjectRef, ty: &TypeExpr) -> bool {
+        self.check_type(Some(ty), false, value, ty.span).is_ok()
+    }
+
+    /// Правило `@[value in [...]]`: `value` в нём — проверяемое значение.
+    pub fn rule_holds(&mut self, rule: &Expr, value: &ObjectRef) -> Result<bool, EvalError> {
+        self.scoped(|this| {
+            this.env
+                .borrow_mut()
+                .define("value", Binding::constant(value.clone()));
+            this.truthy(rule)
+        })
+    }
+
+    fn execute_statements(&mut self, statements: &[Stmt], target: &mut Target) -> Flow {
+        for statement in statements {
+            match self.execute(statement, target) {
+                Ok(Flow::Normal) => {}
+                Ok(Flow::Cancel) => break,
+                Ok(flow) => return flow,
+                Err(error) => self.reporter.report(error),
+            }
+        }
+        Flow::Normal
+    }
+
+    /// Инструкции корня документа, секции или словаря: выйти из них `break` и `continue`
+    /// не могут, поэтому такой выход — ошибка, а выполнение идёт дальше. `cancel`
+    /// заканчивает их, как любой другой блок.
+    fn execute_root(&mut self, statements: &[Stmt], target: &mut Target) {
+        for statement in statements {
+            let keyword = match self.execute(statement, target) {
+                Ok(Flow::Normal) => continue,
+                Ok(Flow::Cancel) => return,
+                Ok(Flow::Break) => "break",
+                Ok(Flow::Continue) => "continue",
+                Err(error) => {
+                    self.reporter.report(error);
+                    continue;
+                }
+            };
+            self.reporter.report(EvalError::invalid(
+                format!("«{}» вне цикла", keyword),
+                statement.span,
+            ));
+        }
+    }
+
+    /// Выполняет блок в новой вложенной области видимости.
+    fn execute_scoped(&mut self, block: &Block, target: &mut Target) -> Flow {
+        self.scoped(|this| this.execute_statements(&block.kind, target))
+    }
+
+    fn scoped<T>(&mut self, run: impl FnOnce(&mut Self) -> T) -> T {
+        let child = Environment::new(Some(Rc::clone(&self.env)));
+        let parent = std::mem::replace(&mut self.env, child);
+        let result = run(self);
+        self.env = parent;
+        result
+    }
+
+    // --- Инструкции ---
+
+    fn execute(&mut self, statement: &Stmt, target: &mut Target) -> EvalResult<Flow> {
+        let span = statement.span;
+        match &statement.kind {
+            // Заголовки разбирает движок до выполнения; об ошибках разбора уже сообщено
+            StmtKind::Header { .. } | StmtKind::Error => {}
+            StmtKind::VarDecl(decl) => self.declare(decl, span)?,
+            StmtKind::Entry(entry) => self.entry(entry, span, target)?,
+            StmtKind::Assign {
+                target: place,
+                op,
+                value,
+            } => self.assign(place, *op, value.as_ref(), span, target)?,
+            StmtKind::Section { name, body, .. } => {
+                let value = self.scoped(|this| this.collect_dictionary(&body.kind));
+                self.define(&format!("§{}", name), Binding::constant(value), false, span)?;
+            }
+            StmtKind::Include { path } => self.include(path, span, target)?,
+            StmtKind::UseSchema { path } => self.use_schema(path, span)?,
+            StmtKind::Provide { keys } => self.provided.extend(keys.iter().cloned()),
+            StmtKind::If {
+                branches,
+                otherwise,
+            } => {
+                for (condition, block) in branches {
+                    if self.truthy(condition)? {
+                        return Ok(self.execute_scoped(block, target));
+                    }
+                }
+                if let Some(block) = otherwise {
+                    return Ok(self.execute_scoped(block, target));
+                }
+            }
+            StmtKind::For {
+                pattern,
+                iterable,
+                body,
+            } => {
+                let items = self.iteration_items(iterable, pattern)?;
+                for item in items {
+                    let flow = self.scoped(|this| -> EvalResult<Flow> {
+                        this.bind_pattern(pattern, item, iterable.span)?;
+                        Ok(this.execute_statements(&body.kind, target))
+                    })?;
+                    if flow == Flow::Break {
+                        break;
+                    }
+                }
+            }
+            StmtKind::Loop { count, body } => {
+                let count = match count {
+                    Some(count) => Some(self.count(count)?),
+                    None => None,
+                };
+                let mut index = 0;
+                while count.is_none_or(|count| index < count) {
+                    index += 1;
+                    self.check_iterations(index as usize, span)?;
+                    // `index` — неявный номер итерации, с единицы
+                    let flow = self.scoped(|this| {
+                        let value = this.prelude.integer_value(index);
+                        this.env
+                            .borrow_mut()
+                            .define("index", Binding::constant(value));
+                        this.execute_statements(&body.kind, target)
+                    });
+                    if flow == Flow::Break {
+                        break;
+                    }
+                }
+            }
+            StmtKind::While {
+                condition,
+                body,
+                do_while,
+            } => {
+                let mut iterations = 0;
+                let mut first = *do_while;
+                while first || self.truthy(condition)? {
+                    first = false;
+                    iterations += 1;
+                    self.check_iterations(iterations, span)?;
+                    if self.execute_scoped(body, target) == Flow::Break {
+                        break;
+                    }
+                }
+            }
+            StmtKind::Yield { key, value } => {
+                let value = self.evaluate(value)?;
+                let yielded = match key {
+                    Some(key) => {
+                        let key = self.evaluate(key)?.borrow().to_string();
+                        Yielded::Pair(key, value, span)
+                    }
+                    None => Yielded::Value(value, span),
+                };
+                match self.yields.as_mut() {
+                    Some(yields) => yields.push(yielded),
+                    None => {
+                        return Err(EvalError::invalid(
+                            "«yield» бывает только внутри generate, spread и filter",
+                            span,
+                        ))
+                    }
+                }
+            }
+            StmtKind::Break(None) => return Ok(Flow::Break),
+            StmtKind::Continue(None) => return Ok(Flow::Continue),
+            StmtKind::Cancel(None) => return Ok(Flow::Cancel),
+            StmtKind::Block(block) => return Ok(self.execute_scoped(block, target)),
+            StmtKind::Expression(expr) => self.bare_value(expr, target)?,
+            StmtKind::Routine(_) => return Err(EvalError::unsupported("объявление функций", span)),
+            StmtKind::Class(_) => return Err(EvalError::unsupported("объявление классов", span)),
+            StmtKind::Match { .. } => return Err(EvalError::unsupported("«match»", span)),
+            StmtKind::Try { .. } => return Err(EvalError::unsupported("«try»", span)),
+            StmtKind::Break(Some(_)) | StmtKind::Continue(Some(_)) => {
+                return Err(EvalError::unsupported("выход из нескольких циклов", span))
+            }
+            StmtKind::Cancel(Some(_)) => {
+                return Err(EvalError::unsupported("выход из нескольких блоков", span))
+            }
+            StmtKind::Return(_) | StmtKind::Throw(_) => {
+                return Err(EvalError::unsupported("«return» и «throw»", span))
+            }
+        }
+        Ok(Flow::Normal)
+    }
+
+    fn declare(&mut self, decl: &VarDecl, span: Span) -> EvalResult<()> {
+        if decl.lazy {
+            return Err(EvalError::unsupported("ленивое значение «=>»", span));
+        }
+        let value = match &decl.value {
+            Some(value) => self.evaluate(value)?,
+            None => self.prelude.none_value(),
+        };
+        self.check_type(decl.ty.as_ref(), decl.optional, &value, span)?;
+
+        let global = decl.modifiers.contains(&Modifier::Global);
+        let make = |value| match decl.kind {
+            DeclKind::Const => Binding::constant(value),
+            DeclKind::Let => Binding::variable(value),
+        };
+        match &decl.pattern {
+            Pattern::Name(name) => self.define(name, make(value), global, span),
+            Pattern::Tuple(names) => {
+                let elements = self.destructure(&value, names.len(), span)?;
+                for (name, value) in names.iter().zip(elements) {
+                    self.define(name, make(value), global, span)?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    fn define(&mut self, name: &str, binding: Binding, global: bool, span: Span) -> EvalResult<()> {
+        let env = if global {
+            Environment::global(&self.env)
+        } else {
+            Rc::clone(&self.env)
+        };
+        let defined = env.borrow_mut().define(name, binding);
+        if defined {
+            Ok(())
+        } else {
+            Err(EvalError::AlreadyDefined {
+                name: name.to_string(),
+                span,
+            })
+        }
+    }
+
+    fn entry(&mut self, entry: &Entry, span: Span, target: &mut Target) -> EvalResult<()> {
+        if entry.lazy {
+            return Err(EvalError::unsupported("ленивое значение «=>»", span));
+        }
+        let value = self.evaluate(&entry.value)?;
+        self.check_type(entry.ty.as_ref(), entry.optional, &value, entry.value.span)?;
+        // `$num 2` и `$num = 2` для объявленного имени — присваивание, а не элемент данных
+        if let EntryKey::Name(name) = &entry.key {
+            let result = self.env.borrow_mut().assign(name, value.clone());
+            match result {
+                Ok(()) => return Ok(()),
+                Err(AssignError::Constant) => {
+                    return Err(EvalError::ConstantAssignment {
+                        name: name.clone(),
+                        span: entry.key_span,
+                    })
+                }
+                Err(AssignError::Undefined) => {}
+            }
+        }
+        self.insert(&entry.key, value, entry.key_span, target)
+    }
+
+    /// Записывает значение под ключом; повторный ключ заменяет прежнее значение.
+    fn insert(
+        &mut self,
+        key: &EntryKey,
+        value: ObjectRef,
+        span: Span,
+        target: &mut Target,
+    ) -> EvalResult<()> {
+        let entries = match target {
+            Target::Dictionary(entries) => entries,
+            Target::Array(_) => {
+                return Err(EvalError::invalid(
+                    "у элементов документа-массива не бывает ключей",
+                    span,
+                ))
+            }
+        };
+        match key {
+            EntryKey::Name(name) => {
+                self.check_collision(name, span)?;
+                entries.insert(name.clone(), value);
+            }
+            EntryKey::String(expr) => {
+                let key = self.evaluate(expr)?;
+                let key = key.borrow().to_string();
+                self.check_collision(&key, span)?;
+                entries.insert(key, value);
+            }
+            EntryKey::Multiple(keys) => {
+                let values = self.destructure(&value, keys.len(), span)?;
+                for (key, value) in keys.iter().zip(values) {
+                    self.insert(key, value, span, target)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn assign(
+        &mut self,
+        place: &Expr,
+        op: AssignOp,
+        value: Option<&Expr>,
+        span: Span,
+        target: &mut Target,
+    ) -> EvalResult<()> {
+        let (env, name) = match &place.kind {
+            ExprKind::Identifier(name) => (Rc::clone(&self.env), name.clone()),
+            // `global.flag = false`, `local.value += 1`
+            ExprKind::Member {
+                object, property, ..
+            } => match &object.kind {
+                ExprKind::Identifier(scope) if scope == "global" => {
+                    (Environment::global(&self.env), property.clone())
+                }
+                ExprKind::Identifier(scope) if scope == "local" => {
+                    (Rc::clone(&self.env), property.clone())
+                }
+                _ => {
+                    let key = self.prelude.string_value(property.clone());
+                    return self.assign_element(object, key, op, value, place.span, span);
+                }
+            },
+            ExprKind::Index { object, index, .. } => {
+                let key = self.evaluate(index)?;
+                return self.assign_element(object, key, op, value, place.span, span);
+            }
+            _ => {
+                return Err(EvalError::invalid(
+                    "присвоить можно только имени, полю или элементу",
+                    place.span,
+                ))
+            }
+        };
+
+        let current = env.borrow().lookup(&name).map(|binding| binding.value);
+        let current = match (current, &mut *target) {
+            (Some(value), _) => Some(value),
+            // Элемент данных текущего блока, а не переменная: `key += 1`
+            (None, Target::Dictionary(entries)) => entries.get(&name).cloned(),
+            (None, Target::Array(_)) => None,
+        };
+        let new_value = self.updated_value(op, current.as_ref(), value, &name, place.span, span)?;
+
+        let result = env.borrow_mut().assign(&name, new_value.clone());
+        match result {
+            Ok(()) => Ok(()),
+            Err(AssignError::Constant) => Err(EvalError::ConstantAssignment {
+                name,
+                span: place.span,
+            }),
+            Err(AssignError::Undefined) => match target {
+                Target::Dictionary(entries) if matches!(place.kind, ExprKind::Identifier(_)) => {
+                    self.check_collision(&name, place.span)?;
+                    entries.insert(name, new_value);
+                    Ok(())
+                }
+                _ => Err(EvalError::Undefined {
+                    name,
+                    span: place.span,
+                }),
+            },
+        }
+    }
+
+    /// `merged[k] = v`, `data.key { ... }`, `counts[1] += 1`: элемент словаря или массива
+    /// меняется на месте, и это видно через любое имя, которое ссылается на тот же объект.
+    /// `const` запрещает перепривязать имя, а не менять содержимое.
+    fn assign_element(
+        &mut self,
+        object: &Expr,
+        key: ObjectRef,
+        op: AssignOp,
+        value: Option<&Expr>,
+        place: Span,
+        span: Span,
+    ) -> EvalResult<()> {
+        let container = self.evaluate(object)?;
+        let current = element(&container, &key);
+        let name = key.borrow().to_string();
+        let new_value = self.updated_value(op, current.as_ref(), value, &name, place, span)?;
+        store_element(&container, &key, new_value, place)
+    }
+
+    /// Новое значение для `=`, составного присваивания, `++` и `--`.
+    fn updated_value(
+        &mut self,
+        op: AssignOp,
+        current: Option<&ObjectRef>,
+        value: Option<&Expr>,
+        name: &str,
+        place: Span,
+        span: Span,
+    ) -> EvalResult<ObjectRef> {
+        Ok(match (op, current) {
+            (AssignOp::Set, _) => self.evaluate(value.expect("у «=» всегда есть значение"))?,
+            (_, None) => {
+                return Err(EvalError::Undefined {
+                    name: name.to_string(),
+                    span: place,
+                })
+            }
+            (AssignOp::Increment, Some(current)) => {
+                self.apply(BinaryOp::Add, current, &self.prelude.integer_value(1), span)?
+            }
+            (AssignOp::Decrement, Some(current)) => {
+                self.apply(BinaryOp::Sub, current, &self.prelude.integer_value(1), span)?
+            }
+            (op, Some(current)) => {
+                let operand =
+                    self.evaluate(value.expect("у составного присваивания есть значение"))?;
+                match op {
+                    AssignOp::Concat => {
+                        let text = format!("{}{}", current.borrow(), operand.borrow());
+                        self.prelude.string_value(text)
+                    }
+                    AssignOp::And => {
+                        let result = is_truthy(&current.borrow()) && is_truthy(&operand.borrow());
+                        self.prelude.boolean_value(result)
+                    }
+                    _ => self.apply(compound_operator(op), current, &operand, span)?,
+                }
+            }
+        })
+    }
+
+    /// `include "./data.json"`, `include "./keys.llacon"`: данные файла встают на место
+    /// директивы — ключи словаря в текущий словарь, элементы массива в текущий массив.
+    /// Путь отсчитывается от каталога включающего файла; от документа LaCoN
+    /// вставляются только ключи из его `provide`, если он есть.
+    fn include(&mut self, path: &str, span: Span, target: &mut Target) -> EvalResult<()> {
+        let failed = |message: String| EvalError::Include {
+            path: path.to_string(),
+            message,
+            span,
+        };
+        let resolved = resolve_include(&self.base_dir, path);
+        let file = self
+            .loader
+            .load(&resolved)
+            .map_err(|error| failed(error.to_string()))?;
+        let value = match file.format {
+            SourceFormat::Json => {
+                parse_json(self.prelude, &file.source).map_err(|error| failed(error.to_string()))?
+            }
+            SourceFormat::Lacon(dialect) => {
+                self.include_document(&file, dialect).map_err(failed)?
+            }
+            SourceFormat::Schema => {
+                return Err(failed(
+                    "схема не вставляется, её подключают через «use schema»".to_string(),
+                ))
+            }
+        };
+
+        let object = value.borrow();
+        match target {
+            Target::Dictionary(entries) => match object.downcast::<DictionaryValue>() {
+                Some(dictionary) => {
+                    // Совпавший ключ остаётся за документом, остальные вставляются
+                    for (key, value) in &dictionary.entries {
+                        if entries.contains_key(key) {
+                            self.reporter.report(EvalError::Collision {
+                                key: key.clone(),
+                                path: path.to_string(),
+                                span,
+                            });
+                        } else {
+                            entries.insert(key.clone(), value.clone());
+                        }
+                        self.included.insert(key.clone(), path.to_string());
+                    }
+                    Ok(())
+                }
+                None => Err(failed(format!(
+                    "в словарь можно вставить только словарь, а в файле {}",
+                    type_name(&object)
+                ))),
+            },
+            Target::Array(elements) => {
+                match object.downcast::<ArrayValue>() {
+                    Some(array) => elements.extend(array.elements.iter().cloned()),
+                    None => elements.push(value.clone()),
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Вычисляет вставляемый документ LaCoN отдельно от текущего: у него свои имена,
+    /// а наружу выходят только данные. `Err` — первая ошибка документа.
+    fn include_document(&self, file: &SourceFile, dialect: Dialect) -> Result<ObjectRef, String> {
+        let first_error = |reporter: &Reporter| {
+            let error = reporter
+                .diagnostics()
+                .iter()
+                .find(|error| error.is_error())?;
+            Some(format!("{}: {}", error.span(), error.message))
+        };
+        let mut reporter = Reporter::new();
+        let program = parse_source(&file.source, &mut reporter);
+        if dialect == Dialect::List || Dialect::declared(&program) == Dialect::List {
+            check_list(&program, &mut reporter);
+        }
+        if let Some(error) = first_error(&reporter) {
+            return Err(error);
+        }
+
+        let mut target = Target::Dictionary(IndexMap::new());
+        let provided = {
+            let mut nested = Evaluator::new(self.prelude, &mut reporter)
+                .with_base_dir(file.base_dir())
+                .with_loader(self.loader.nested(&file.path));
+            nested.execute_block(&program.statements, &mut target);
+            nested.provided
+        };
+        if let Some(error) = first_error(&reporter) {
+            return Err(error);
+        }
+        let Target::Dictionary(mut entries) = target else {
+            unreachable!("документ вычислялся в словарь")
+        };
+        if !provided.is_empty() {
+            let mut exposed = IndexMap::new();
+            for key in provided {
+                match entries.shift_remove(&key) {
+                    Some(value) => exposed.insert(key, value),
+                    None => return Err(format!("в документе нет ключа «{}» из provide", key)),
+                };
+            }
+            entries = exposed;
+        }
+        Ok(self.prelude.dictionary_value(entries))
+    }
+
+    /// `use schema "./schema.slacon"`: схема компилируется сразу, а документ проверяется
+    /// по ней, когда все его элементы собраны.
+    fn use_schema(&mut self, path: &str, span: Span) -> EvalResult<()> {
+        let failed = |message: String| EvalError::Schema {
+            path: path.to_string(),
+            message,
+            span,
+        };
+        if self.schema.is_some() {
+            return Err(failed("документ уже подключил схему".to_string()));
+        }
+        let resolved = resolve_include(&self.base_dir, path);
+        let file = self
+            .loader
+            .load(&resolved)
+            .map_err(|error| failed(error.to_string()))?;
+        if file.format != SourceFormat::Schema {
+            return Err(failed("схема — это файл «.slacon»".to_string()));
+        }
+        let mut reporter = Reporter::new();
+        let schema = compile_schema(&file.source, &mut reporter);
+        if let Some(error) = reporter.diagnostics().iter().find(|error| error.is_error()) {
+            return Err(failed(format!("{}: {}", error.span(), error.message)));
+        }
+        self.schema = Some(SchemaUse {
+            schema,
+            path: path.to_string(),
+            file: resolved.display().to_string(),
+            span,
+        });
+        Ok(())
+    }
+
+    fn validate_schema(&mut self, usage: &SchemaUse, statements: &[Stmt], target: &mut Target) {
+        let entries = match target {
+            Target::Dictionary(entries) => entries,
+            Target::Array(_) => {
+                self.reporter.report(
+                    Diagnostic::error("схема описывает словарь, а документ — массив", usage.span)
+                        .with_code("E0218"),
+                );
+                return;
+            }
+        };
+        // Ключи, записанные в документе явно; остальные указывают на `use schema`
+        let written = statements
+            .iter()
+            .filter_map(|statement| match &statement.kind {
+                StmtKind::Entry(Entry {
+                    key: EntryKey::Name(name),
+                    key_span,
+                    value,
+                    ..
+                }) => Some((
+                    name.clone(),
+                    Written {
+                        span: *key_span,
+                        value,
+                    },
+                )),
+                _ => None,
+            })
+            .collect();
+        for violation in validate(self, usage, entries, &written) {
+            self.reporter.report(violation);
+        }
+    }
+
+    /// Ключ, уже вставленный `include`, нельзя задать повторно.
+    fn check_collision(&self, key: &str, span: Span) -> EvalResult<()> {
+        match self.included.get(key) {
+            Some(path) => Err(EvalError::Collision {
+                key: key.to_string(),
+                path: path.clone(),
+                span,
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// Вливает словарь в целевой: ключи из `include` защищены так же, как от обычных элементов.
+    fn merge_entries(
+        &self,
+        entries: &mut IndexMap<String, ObjectRef>,
+        dictionary: &DictionaryValue,
+        span: Span,
+    ) -> EvalResult<()> {
+        for (key, value) in &dictionary.entries {
+            self.check_collision(key, span)?;
+            entries.insert(key.clone(), value.clone());
+        }
+        Ok(())
+    }
+
+    /// Значение без ключа: элемент документа-массива или содержимое, вливаемое в словарь.
+    fn bare_value(&mut self, expr: &Expr, target: &mut Target) -> EvalResult<()> {
+        if let ExprKind::Expansion(expansion) = &expr.kind {
+            let yields = self.expand(expansion, expr.span)?;
+            return self.place_yields(yields, target);
+        }
+        let spread = match &expr.kind {
+            ExprKind::Spread(inner) => Some(inner.as_ref()),
+            _ => None,
+        };
+        let value = self.evaluate(spread.unwrap_or(expr))?;
+        match target {
+            Target::Array(elements) => {
+                if spread.is_some() {
+                    let items = self.spread_items(&value, expr.span)?;
+                    elements.extend(items);
+                } else {
+                    elements.push(value);
+                }
+                Ok(())
+            }
+            Target::Dictionary(entries) => {
+                let value = value.borrow();
+                if let Some(dictionary) = value.downcast::<DictionaryValue>() {
+                    // `{ key-1 value }` в корне равносильно `key-1 value`
+                    return self.merge_entries(entries, dictionary, expr.span);
+                }
+                // Вызовы выполняются ради побочных эффектов
+                if matches!(expr.kind, ExprKind::Call { .. }) {
+                    return Ok(());
+                }
+                Err(EvalError::invalid(
+                    "у значения в словаре должен быть ключ",
+                    expr.span,
+                ))
+            }
+        }
+    }
+
+    // --- Выражения ---
+
+    pub fn evaluate(&mut self, expr: &Expr) -> EvalResult<ObjectRef> {
+        let span = expr.span;
+        let prelude = self.prelude;
+        match &expr.kind {
+            ExprKind::Number { literal, unit } => {
+                parse_number(prelude, literal, unit.as_deref(), span)
+            }
+            ExprKind::String { segments, .. } => {
+                let mut text = String::new();
+                for segment in segments {
+                    match segment {
+                        StringSegment::Text(part) => text.push_str(part),
+                        StringSegment::Interpolation(expr) => {
+                            let value = self.evaluate(expr)?;
+                            text.push_str(&value.borrow().to_string());
+                        }
+                        StringSegment::CodePoint(expr) => {
+                            let value = self.evaluate(expr)?;
+                            text.push(code_point(&value.borrow(), expr.span)?);
+                        }
+                    }
+                }
+                Ok(prelude.string_value(text))
+            }
+            ExprKind::Boolean(value) => Ok(prelude.boolean_value(*value)),
+            ExprKind::None => Ok(prelude.none_value()),
+            ExprKind::Nil => Ok(prelude.nil_value()),
+            ExprKind::Undefined | ExprKind::Error => Ok(prelude.undefined_value()),
+            ExprKind::Auto => Ok(prelude.auto_value()),
+            // Необъявленное имя в данных — это текст без кавычек: `key value`
+            ExprKind::Identifier(name) => Ok(self
+                .lookup(name)
+                .or_else(|| special_number(prelude, name))
+                .unwrap_or_else(|| prelude.string_value(name.clone()))),
+            ExprKind::DateTime(text) => DateTimeValue::parse_iso(text)
+                .map(|moment| prelude.date_time_value(moment))
+                .ok_or_else(|| EvalError::invalid(format!("некорректная дата «{}»", text), span)),
+            ExprKind::Color(digits) => ColorValue::parse_hex(digits)
+                .map(|color| prelude.color_value(color))
+                .ok_or_else(|| {
+                    EvalError::invalid(format!("некорректный цвет «#{}»", digits), span)
+                }),
+            ExprKind::Section(name) => {
+                self.lookup(&format!("§{}", name))
+                    .ok_or_else(|| EvalError::Undefined {
+                        name: format!("§{}", name),
+                        span,
+                    })
+            }
+            ExprKind::Operator(op) => match operator_type(*op) {
+                Some(op_type) => Ok(prelude.operator_value(op_type)),
+                None => Err(EvalError::unsupported(
+                    format!("оператор «{}» как значение", op.as_str()),
+                    span,
+                )),
+            },
+            ExprKind::Array(items) => {
+                let mut elements = Vec::new();
+                for item in items {
+                    match &item.kind {
+                        ExprKind::Spread(inner) => {
+                            let value = self.evaluate(inner)?;
+                            elements.extend(self.spread_items(&value, item.span)?);
+                        }
+                        ExprKind::Expansion(expansion) => {
+                            let yields = self.expand(expansion, item.span)?;
+                            let mut target = Target::Array(elements);
+                            self.place_yields(yields, &mut target)?;
+                            let Target::Array(expanded) = target else {
+                                unreachable!("цель осталась массивом")
+                            };
+                            elements = expanded;
+                        }
+                        _ => elements.push(self.evaluate(item)?),
+                    }
+                }
+                Ok(prelude.array_value(elements))
+            }
+            ExprKind::Tuple(items) => {
+                let elements = items
+                    .iter()
+                    .map(|item| self.evaluate(item))
+                    .collect::<EvalResult<Vec<_>>>()?;
+                Ok(prelude.array_value(elements))
+            }
+            ExprKind::Dictionary(statements) => {
+                Ok(self.scoped(|this| this.collect_dictionary(statements)))
+            }
+            ExprKind::Unary { op, operand } => {
+                let operand = self.evaluate(operand)?;
+                ops::unary(prelude, *op, &operand)
+                    .map_err(|error| EvalError::Operation { error, span })
+            }
+            ExprKind::Binary { op, left, right } => self.binary(*op, left, right, span),
+            ExprKind::Ternary {
+                condition,
+                then_branch,
+                else_branch,
+            } => {
+                if self.truthy(condition)? {
+                    self.evaluate(then_branch)
+                } else {
+                    self.evaluate(else_branch)
+                }
+            }
+            ExprKind::Member {
+                object,
+                property,
+                optional,
+            } => {
+                if let ExprKind::Identifier(scope) = &object.kind {
+                    if let Some(value) = self.scope_member(scope, property) {
+                        return Ok(value);
+                    }
+                }
+                let object = self.evaluate(object)?;
+                let found = member(&object, property)
+                    .or_else(|| builtins::property(prelude, &object, property))
+                    .or_else(|| builtins::method(prelude, &object, property));
+                self.found_or_missing(found, property, *optional, span)
+            }
+            ExprKind::Index {
+                object,
+                index,
+                optional,
+            } => {
+                let object = self.evaluate(object)?;
+                let index = self.evaluate(index)?;
+                let found = element(&object, &index);
+                let key = index.borrow().to_string();
+                self.found_or_missing(found, &key, *optional, span)
+            }
+            ExprKind::Call {
+                callee, arguments, ..
+            } => {
+                let callee = self.callee(callee)?;
+                let arguments = arguments
+                    .iter()
+                    .map(|argument| self.evaluate(argument))
+                    .collect::<EvalResult<Vec<_>>>()?;
+                self.call(&callee, arguments, span)
+            }
+            ExprKind::Floor(inner) | ExprKind::Ceil(inner) => {
+                let value = self.evaluate(inner)?;
+                let floor = matches!(expr.kind, ExprKind::Floor(_));
+                // Величина и температура округляются в своей единице: `⌊2.7m⌋ = 2m`
+                if let Some(quantity) = as_quantity(&value.borrow()) {
+                    let rounded = match floor {
+                        true => quantity.value.floor(),
+                        false => quantity.value.ceil(),
+                    };
+                    return Ok(prelude.quantity_value(rounded, quantity.unit.clone()));
+                }
+                if let Some(temperature) = as_temperature(&value.borrow()) {
+                    let rounded = match floor {
+                        true => temperature.value.floor(),
+                        false => temperature.value.ceil(),
+                    };
+                    let rounded = TemperatureValue {
+                        value: rounded,
+                        ..temperature.clone()
+                    };
+                    return temperatures::checked(prelude, rounded)
+                        .map_err(|error| EvalError::Operation { error, span });
+                }
+                let number = as_number(&value.borrow());
+                let rounded = match number {
+                    Some(Number::Integer(_) | Number::Big(_)) => return Ok(value),
+                    Some(Number::Fraction(exact)) if floor => exact.floor(),
+                    Some(Number::Fraction(exact)) => -(-exact).floor(),
+                    // У бесконечностей и `NaN` целой части нет, они остаются как есть
+                    Some(Number::Float(float)) if !float.is_finite() => return Ok(value),
+                    Some(Number::Float(float)) => {
+                        let rounded = if floor { float.floor() } else { float.ceil() };
+                        Rational::from_f64(rounded).expect("число конечно").floor()
+                    }
+                    None => {
+                        let found = type_name(&value.borrow());
+                        return Err(EvalError::TypeMismatch {
+                            expected: "Number".to_string(),
+                            found,
+                            span,
+                        });
+                    }
+                };
+                Ok(prelude.big_integer_value(rounded, Radix::Decimal))
+            }
+            ExprKind::Spread(_) => Err(EvalError::invalid(
+                "деконструкция «...» допустима только внутри массива или словаря",
+                span,
+            )),
+            // Вне массива и словаря: пары дают словарь, значения — массив
+            ExprKind::Expansion(expansion) => {
+                let yields = self.expand(expansion, span)?;
+                let mut target = match yields.first() {
+                    Some(Yielded::Pair(..)) => Target::Dictionary(IndexMap::new()),
+                    _ => Target::Array(Vec::new()),
+                };
+                self.place_yields(yields, &mut target)?;
+                Ok(match target {
+                    Target::Dictionary(entries) => prelude.dictionary_value(entries),
+                    Target::Array(elements) => prelude.array_value(elements),
+                })
+            }
+            ExprKind::Type(_) => Err(EvalError::unsupported("тип как значение", span)),
+            ExprKind::Lambda { .. } | ExprKind::Routine(_) => {
+                Err(EvalError::unsupported("объявление функций", span))
+            }
+            ExprKind::New { .. } => Err(EvalError::unsupported("«new»", span)),
+        }
+    }
+
+    fn binary(
+        &mut self,
+        op: BinaryOp,
+        left: &Expr,
+        right: &Expr,
+        span: Span,
+    ) -> EvalResult<ObjectRef> {
+        match op {
+            BinaryOp::And => {
+                let result = self.truthy(left)? && self.truthy(right)?;
+                Ok(self.prelude.boolean_value(result))
+            }
+            BinaryOp::Or => {
+                let result = self.truthy(left)? || self.truthy(right)?;
+                Ok(self.prelude.boolean_value(result))
+            }
+            BinaryOp::Is => {
+                let value = self.evaluate(left)?;
+                let result = self.is_of_type(&value.borrow(), type_operand(right));
+                let result = result.ok_or_else(|| {
+                    EvalError::invalid("справа от «is» ожидалось имя типа", right.span)
+                })?;
+                Ok(self.prelude.boolean_value(result))
+            }
+            // `value |> name(_, x)`: значение подставляется вместо `_` или первым аргументом
+            BinaryOp::Pipeline => {
+                let value = self.evaluate(left)?;
+                match &right.kind {
+                    ExprKind::Call {
+                        callee, arguments, ..
+                    } => {
+                        let callee = self.callee(callee)?;
+                        let mut values = Vec::new();
+                        let mut placed = false;
+                        for argument in arguments {
+                            if matches!(&argument.kind, ExprKind::Identifier(name) if name == "_") {
+                                values.push(value.clone());
+                                placed = true;
+                            } else {
+                                values.push(self.evaluate(argument)?);
+                            }
+                        }
+                        if !placed {
+                            values.insert(0, value);
+                        }
+                        self.call(&callee, values, span)
+                    }
+                    _ => {
+                        let callee = self.callee(right)?;
+                        self.call(&callee, vec![value], span)
+                    }
+                }
+            }
+            // `25m3 -> nL`, `25°C -> n°F`: справа — значение, чья единица берётся целиком,
+            // или имя единицы; составная `-> nkm/h` разбирается как единица, а не как деление
+            BinaryOp::Convert => {
+                let value = self.evaluate(left)?;
+                let target = match self.unit_text(right) {
+                    Some(text) => text_conversion_target(self.prelude, &text, right.span)?,
+                    None => {
+                        let target = self.evaluate(right)?;
+                        let target = target.borrow();
+                        conversion_target(self.prelude, &target, right.span)?
+                    }
+                };
+                let converted = match target {
+                    ConversionTarget::Unit(unit) => {
+                        quantities::convert(self.prelude, &value, &unit)
+                    }
+                    ConversionTarget::Temperature(scale) => {
+                        temperatures::convert(self.prelude, &value, scale)
+                    }
+                    ConversionTarget::Fraction => ops::fraction(self.prelude, &value),
+                    ConversionTarget::Color(space) => colors::convert(self.prelude, &value, space),
+                    ConversionTarget::Zone(offset) => {
+                        dates::convert_zone(self.prelude, &value, offset)
+                    }
+                };
+                converted.map_err(|error| EvalError::Operation { error, span })
+            }
+            BinaryOp::CartesianSum => Err(EvalError::unsupported("декартова сумма «∔»", span)),
+            _ => {
+                let left = self.evaluate(left)?;
+                let right = self.evaluate(right)?;
+                self.apply(op, &left, &right, span)
+            }
+        }
+    }
+
+    fn apply(
+        &self,
+        op: BinaryOp,
+        left: &ObjectRef,
+        right: &ObjectRef,
+        span: Span,
+    ) -> EvalResult<ObjectRef> {
+        ops::binary(self.prelude, op, left, right)
+            .map_err(|error| EvalError::Operation { error, span })
+    }
+
+    fn call(
+        &mut self,
+        callee: &ObjectRef,
+        arguments: Vec<ObjectRef>,
+        span: Span,
+    ) -> EvalResult<ObjectRef> {
+        let op_type = callee
+            .borrow()
+            .downcast::<OperatorValue>()
+            .map(|operator| operator.op_type.clone());
+        if let Some(op_type) = op_type {
+            let [left, right] = <[ObjectRef; 2]>::try_from(arguments).map_err(|_| {
+                EvalError::invalid(
+                    format!("оператор «{}» принимает два операнда", op_type.as_str()),
+                    span,
+                )
+            })?;
+            return ops::operator(self.prelude, &op_type, &left, &right)
+                .map_err(|error| EvalError::Operation { error, span });
+        }
+        let routine = callee
+            .borrow()
+            .downcast::<RoutineValue>()
+            .map(|routine| (routine.name.clone(), routine.content.clone()));
+        let Some((name, content)) = routine else {
+            return Err(EvalError::NotCallable {
+                found: type_name(&callee.borrow()),
+                span,
+            });
+        };
+        match content {
+            RoutineContent::NativeFunction(function) => Ok(function(arguments)),
+            RoutineContent::NativeProcedure(procedure) => {
+                procedure(arguments);
+                Ok(self.prelude.none_value())
+            }
+            RoutineContent::Builtin { receiver } => {
+                builtins::call(self.prelude, &name, receiver.as_ref(), &arguments)
+                    .map_err(|error| EvalError::Operation { error, span })
+            }
+            RoutineContent::Defined(_) => Err(EvalError::unsupported(
+                "вызов пользовательских функций",
+                span,
+            )),
+        }
+    }
+
+    // --- Вспомогательное ---
+
+    /// Вызываемое значение; необъявленное имя здесь — ошибка, а не текст,
+    /// если это не встроенная функция вроде `RGBA`.
+    fn callee(&mut self, callee: &Expr) -> EvalResult<ObjectRef> {
+        if let ExprKind::Identifier(name) = &callee.kind {
+            let found = self
+                .lookup(name)
+                .or_else(|| builtins::function(self.prelude, name));
+            return found.ok_or_else(|| EvalError::Undefined {
+                name: name.clone(),
+                span: callee.span,
+            });
+        }
+        self.evaluate(callee)
+    }
+
+    /// Запись составной единицы справа от `->`: `nkm/h`, `nkg*m/s**2`. Парсер видит в ней
+    /// деление и умножение имён; единицей она считается, только если ни одно имя не объявлено.
+    fn unit_text(&self, expr: &Expr) -> Option<String> {
+        fn write(evaluator: &Evaluator, expr: &Expr, text: &mut String) -> Option<()> {
+            match &expr.kind {
+                ExprKind::Identifier(name) if evaluator.lookup(name).is_none() => {
+                    text.push_str(name)
+                }
+                ExprKind::Binary { op, left, right } => {
+                    write(evaluator, left, text)?;
+                    match (op, &right.kind) {
+                        (BinaryOp::Mul, _) => text.push('*'),
+                        (BinaryOp::Div, _) => text.push('/'),
+                        (
+                            BinaryOp::Pow,
+                            ExprKind::Number {
+                                literal,
+                                unit: None,
+                            },
+                        ) => {
+                            text.push_str(literal);
+                            return Some(());
+                        }
+                        _ => return None,
+                    }
+                    write(evaluator, right, text)?;
+                }
+                _ => return None,
+            }
+            Some(())
+        }
+        if !matches!(expr.kind, ExprKind::Binary { .. }) {
+            return None;
+        }
+        let mut text = String::new();
+        write(self, expr, &mut text)?;
+        Some(text)
+    }
+
+    fn lookup(&self, name: &str) -> Option<ObjectRef> {
+        self.env.borrow().lookup(name).map(|binding| binding.value)
+    }
+
+    /// `global.name` и `local.name` — обращение к имени в конкретной области.
+    fn scope_member(&self, scope: &str, name: &str) -> Option<ObjectRef> {
+        let binding = match scope {
+            "global" => Environment::global(&self.env).borrow().lookup_local(name),
+            "local" => self.env.borrow().lookup_local(name),
+            _ => None,
+        };
+        binding.map(|binding| binding.value)
+    }
+
+    fn truthy(&mut self, condition: &Expr) -> EvalResult<bool> {
+        let value = self.evaluate(condition)?;
+        let truthy = is_truthy(&value.borrow());
+        Ok(truthy)
+    }
+
+    fn count(&mut self, count: &Expr) -> EvalResult<i64> {
+        let value = self.evaluate(count)?;
+        let number = as_number(&value.borrow());
+        match number {
+            Some(Number::Integer(count)) => Ok(count),
+            _ => Err(EvalError::TypeMismatch {
+                expected: "Int".to_string(),
+                found: type_name(&value.borrow()),
+                span: count.span,
+            }),
+        }
+    }
+
+    fn check_iterations(&self, iterations: usize, span: Span) -> EvalResult<()> {
+        if iterations > MAX_ITERATIONS {
+            return Err(EvalError::invalid(
+                format!("цикл превысил {} итераций", MAX_ITERATIONS),
+                span,
+            ));
+        }
+        Ok(())
+    }
+
+    /// Выполняет тело `generate`, `spread` или `filter` для каждого элемента источника
+    /// и возвращает всё, что отдали `yield`. Тело без `yield` работает ради побочных эффектов.
+    ///
+    /// В теле видны имена из `as` и `index` — номер элемента с единицы,
+    /// как у `loop`, `for` и индексов массивов.
+    fn expand(&mut self, expansion: &Expansion, span: Span) -> EvalResult<Vec<Yielded>> {
+        let items = self.expansion_items(expansion)?;
+        let outer = self.yields.replace(Vec::new());
+        let result = self.expand_items(expansion, items, span);
+        let yields = std::mem::replace(&mut self.yields, outer).unwrap_or_default();
+        result.map(|_| yields)
+    }
+
+    fn expand_items(
+        &mut self,
+        expansion: &Expansion,
+        items: Vec<Vec<ObjectRef>>,
+        span: Span,
+    ) -> EvalResult<()> {
+        for (index, parts) in items.into_iter().enumerate() {
+            self.check_iterations(index + 1, span)?;
+            let flow = self.scoped(|this| -> EvalResult<Flow> {
+                let value = this.prelude.integer_value(index as i64 + 1);
+                this.env
+                    .borrow_mut()
+                    .define("index", Binding::constant(value));
+                this.bind_expansion(&expansion.pattern, parts, expansion.source.span)?;
+                // Элементы данных в теле никуда не попадают: наружу идут только `yield`
+                let mut scratch = Target::Dictionary(IndexMap::new());
+                Ok(this.execute_statements(&expansion.body.kind, &mut scratch))
+            })?;
+            if flow == Flow::Break {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    /// Элементы источников по шагам. Несколько массивов идут параллельно и должны быть
+    /// одной длины; несколько словарей идут друг за другом парами `[ключ, значение]`.
+    fn expansion_items(&mut self, expansion: &Expansion) -> EvalResult<Vec<Vec<ObjectRef>>> {
+        let sources = match &expansion.source.kind {
+            ExprKind::Tuple(sources) => sources.iter().collect(),
+            _ => vec![&expansion.source],
+        };
+        let mut columns = Vec::with_capacity(sources.len());
+        let mut dictionaries = true;
+        for source in &sources {
+            let value = self.evaluate(source)?;
+            let value = value.borrow();
+            dictionaries &= value.downcast::<DictionaryValue>().is_some();
+            columns.push(self.items_of(&value, source.span)?);
+        }
+        if dictionaries || columns.len() == 1 {
+            return Ok(columns
+                .into_iter()
+                .flatten()
+                .map(|item| vec![item])
+                .collect());
+        }
+        let length = columns[0].len();
+        if let Some((source, column)) = sources
+            .iter()
+            .zip(&columns)
+            .find(|(_, column)| column.len() != length)
+        {
+            return Err(EvalError::invalid(
+                format!(
+                    "источники {} идут параллельно и должны быть одной длины, а здесь {} и {}",
+                    expansion.kind.as_str(),
+                    length,
+                    column.len()
+                ),
+                source.span,
+            ));
+        }
+        let mut columns: Vec<_> = columns.into_iter().map(Vec::into_iter).collect();
+        Ok((0..length)
+            .map(|_| columns.iter_mut().filter_map(Iterator::next).collect())
+            .collect())
+    }
+
+    /// `as (letter, suffix)` при нескольких источниках получает по значению из каждого,
+    /// `as (n)` — сам элемент, остальные образцы раскладывают элемент как в `for`.
+    fn bind_expansion(
+        &mut self,
+        pattern: &Pattern,
+        mut parts: Vec<ObjectRef>,
+        span: Span,
+    ) -> EvalResult<()> {
+        match pattern {
+            Pattern::Tuple(names) if parts.len() > 1 => {
+                if names.len() != parts.len() {
+                    return Err(EvalError::invalid(
+                        format!("источников {}, а имён {}", parts.len(), names.len()),
+                        span,
+                    ));
+                }
+                for (name, value) in names.iter().zip(parts) {
+                    self.define(name, Binding::variable(value), false, span)?;
+                }
+                Ok(())
+            }
+            Pattern::Tuple(names) if names.len() == 1 => {
+                let value = parts.pop().expect("у шага всегда есть значение");
+                self.define(&names[0], Binding::variable(value), false, span)
+            }
+            _ => {
+                let value = match parts.len() {
+                    1 => parts.pop().expect("у шага всегда есть значение"),
+                    _ => self.prelude.array_value(parts),
+                };
+                self.bind_pattern(pattern, value, span)
+            }
+        }
+    }
+
+    /// Раскрывает отданное `yield` в массив или словарь: словарь, отданный без ключа,
+    /// вливается в словарь поэлементно.
+    fn place_yields(&mut self, yields: Vec<Yielded>, target: &mut Target) -> EvalResult<()> {
+        for yielded in yields {
+            match (yielded, &mut *target) {
+                (Yielded::Value(value, _), Target::Array(elements)) => elements.push(value),
+                (Yielded::Pair(.., span), Target::Array(_)) => {
+                    return Err(EvalError::invalid(
+                        "в массив «yield» отдаёт значения без ключей",
+                        span,
+                    ))
+                }
+                (Yielded::Pair(key, value, span), Target::Dictionary(entries)) => {
+                    self.check_collision(&key, span)?;
+                    entries.insert(key, value);
+                }
+                (Yielded::Value(value, span), Target::Dictionary(entries)) => {
+                    let value = value.borrow();
+                    let Some(dictionary) = value.downcast::<DictionaryValue>() else {
+                        return Err(EvalError::invalid(
+                            format!(
+                                "в словарь «yield» отдаёт пары «key, value» или словари, а не {}",
+                                type_name(&value)
+                            ),
+                            span,
+                        ));
+                    };
+                    self.merge_entries(entries, dictionary, span)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn collect_dictionary(&mut self, statements: &[Stmt]) -> ObjectRef {
+        let mut target = Target::Dictionary(IndexMap::new());
+        let included = std::mem::take(&mut self.included);
+        self.execute_root(statements, &mut target);
+        self.included = included;
+        match target {
+            Target::Dictionary(entries) => self.prelude.dictionary_value(entries),
+            Target::Array(elements) => self.prelude.array_value(elements),
+        }
+    }
+
+    fn found_or_missing(
+        &self,
+        found: Option<ObjectRef>,
+        key: &str,
+        optional: bool,
+        span: Span,
+    ) -> EvalResult<ObjectRef> {
+        match found {
+            Some(value) => Ok(value),
+            None if optional => Ok(self.prelude.none_value()),
+            None => Err(EvalError::MissingKey {
+                key: key.to_string(),
+                span,
+            }),
+        }
+    }
+
+    /// Элементы для `...value`: элементы массива.
+    fn spread_items(&self, value: &ObjectRef, span: Span) -> EvalResult<Vec<ObjectRef>> {
+        let value = value.borrow();
+        match value.downcast::<ArrayValue>() {
+            Some(array) => Ok(array.elements.clone()),
+            None => Err(EvalError::TypeMismatch {
+                expected: "Array".to_string(),
+                found: type_name(&value),
+                span,
+            }),
+        }
+    }
+
+    /// Раскладывает массив или кортеж на `count` значений.
+    fn destructure(
+        &self,
+        value: &ObjectRef,
+        count: usize,
+        span: Span,
+    ) -> EvalResult<Vec<ObjectRef>> {
+        let elements = self.spread_items(value, span)?;
+        if elements.len() != count {
+            return Err(EvalError::invalid(
+                format!("ожидалось {} значений, получено {}", count, elements.len()),
+                span,
+            ));
+        }
+        Ok(elements)
+    }
+
+    /// Значения, которые по очереди получает переменная `for`:
+    /// элементы массива, пары `[ключ, значение]` словаря, символы строки.
+    /// `for (index, value) in array` получает номер элемента с единицы.
+    fn iteration_items(
+        &mut self,
+        iterable: &Expr,
+        pattern: &Pattern,
+    ) -> EvalResult<Vec<ObjectRef>> {
+        let value = self.evaluate(iterable)?;
+        let value = value.borrow();
+        let prelude = self.prelude;
+        if let Some(array) = value.downcast::<ArrayValue>() {
+            let with_index = matches!(pattern, Pattern::Tuple(names) if names.len() == 2)
+                && array
+                    .elements
+                    .iter()
+                    .all(|element| element.borrow().downcast::<ArrayValue>().is_none());
+            if with_index {
+                return Ok(array
+                    .elements
+                    .iter()
+                    .enumerate()
+                    .map(|(i, element)| {
+                        prelude
+                            .array_value(vec![prelude.integer_value(i as i64 + 1), element.clone()])
+                    })
+                    .collect());
+            }
+        }
+        self.items_of(&value, iterable.span)
+    }
+
+    /// Элементы массива, пары `[ключ, значение]` словаря или символы строки.
+    fn items_of(&self, value: &Object, span: Span) -> EvalResult<Vec<ObjectRef>> {
+        let prelude = self.prelude;
+        if let Some(array) = value.downcast::<ArrayValue>() {
+            return Ok(array.elements.clone());
+        }
+        if let Some(dictionary) = value.downcast::<DictionaryValue>() {
+            return Ok(dictionary
+                .entries
+                .iter()
+                .map(|(key, value)| {
+                    prelude.array_value(vec![prelude.string_value(key.clone()), value.clone()])
+                })
+                .collect());
+        }
+        if let Some(text) = as_text(value) {
+            return Ok(text
+                .chars()
+                .map(|ch| prelude.string_value(ch.to_string()))
+                .collect());
+        }
+        Err(EvalError::TypeMismatch {
+            expected: "Array, Dictionary или String".to_string(),
+            found: type_name(value),
+            span,
+        })
+    }
+
+    fn bind_pattern(&mut self, pattern: &Pattern, value: ObjectRef, span: Span) -> EvalResult<()> {
+        match pattern {
+            Pattern::Name(name) => self.define(name, Binding::variable(value), false, span),
+            Pattern::Tuple(names) => {
+                let values = self.destructure(&value, names.len(), span)?;
+                for (name, value) in names.iter().zip(values) {
+                    self.define(name, Binding::variable(value), false, span)?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Относится ли значение к типу с указанным именем: к прототипу из прелюдии
+    /// или, для величин, к виду по размерности (`Energy`, `Velocity`).
+    /// `None`, если тип неизвестен.
+    fn is_of_type(&self, value: &Object, name: &str) -> Option<bool> {
+        let dimension = kind_dimension(name);
+        let proto = self.prelude.lookup_type(name);
+        if dimension.is_none() && proto.is_none() {
+            return None;
+        }
+        let by_kind = dimension.is_some_and(|dimension| {
+            as_quantity(value).is_some_and(|quantity| quantity.dimension() == dimension)
+        });
+        let by_proto = proto.is_some_and(|proto| is_instance_of(value, &proto));
+        Some(by_kind || by_proto)
+    }
+
+    /// Проверяет значение по аннотации `<Type>`. Типы, которых движок ещё не знает,
+    /// не проверяются.
+    fn check_type(
+        &self,
+        ty: Option<&TypeExpr>,
+        optional: bool,
+        value: &ObjectRef,
+        span: Span,
+    ) -> EvalResult<()> {
+        let ty = match ty {
+            Some(ty) => ty,
+            None => return Ok(()),
+        };
+        let value = value.borrow();
+        if optional && is_empty_value(&value) {
+            return Ok(());
+        }
+        let (proto, expected) = match &ty.kind {
+            TypeExprKind::Named { .. } => {
+                let name = ty.kind.simple_name().unwrap_or_default();
+                if name.eq_ignore_ascii_case("any") {
+                    return Ok(());
+                }
+                return match self.is_of_type(&value, name) {
+                    Some(false) => Err(EvalError::TypeMismatch {
+                        expected: name.to_string(),
+                        found: type_name(&value),
+                        span,
+                    }),
+                    _ => Ok(()),
+                };
+            }
+            TypeExprKind::Array(_) | TypeExprKind::Tuple(_) => {
+                (self.prelude.array.clone(), "Array".to_string())
+            }
+            TypeExprKind::Dictionary(_) => {
+                (self.prelude.dictionary.clone(), "Dictionary".to_string())
+            }
+            TypeExprKind::Power(..) => return Ok(()),
+        };
+        if is_instance_of(&value, &proto) {
+            return Ok(());
+        }
+        Err(EvalError::TypeMismatch {
+            expected,
+            found: type_name(&value),
+            span,
+        })
+    }
+}
+
+/// Свойство объекта: элемент словаря или свойство из цепочки прототипов.
+fn member(object: &ObjectRef, property: &str) -> Option<ObjectRef> {
+    let object = object.borrow();
+    if let Some(dictionary) = object.downcast::<DictionaryValue>() {
+        if let Some(value) = dictionary.entries.get(property) {
+            return Some(value.clone());
+        }
+    }
+    object.get(property)
+}
+
+/// `array[1]` — нумерация с единицы, `dictionary["key"]` — по ключу,
+/// `dictionary[1]` — по месту в порядке объявления.
+fn element(object: &ObjectRef, index: &ObjectRef) -> Option<ObjectRef> {
+    let (object, index) = (object.borrow(), index.borrow());
+    if let Some(array) = object.downcast::<ArrayValue>() {
+        return match as_number(&index) {
+            Some(Number::Integer(position)) if position >= 1 => {
+                array.elements.get(position as usize - 1).cloned()
+            }
+            _ => None,
+        };
+    }
+    if let Some(dictionary) = object.downcast::<DictionaryValue>() {
+        if let Some(Number::Integer(position)) = as_number(&index) {
+            let position = usize::try_from(position).ok()?;
+            return dictionary
+                .entry_at(position)
+                .map(|(_, value)| value.clone());
+        }
+        return dictionary.entries.get(as_text(&index)?).cloned();
+    }
+    None
+}
+
+/// Записывает `value` в словарь или массив. Значение объекта заменяется целиком, но сам
+/// объект остаётся тем же, поэтому изменение видно всем, кто на него ссылается.
+fn store_element(
+    object: &ObjectRef,
+    key: &ObjectRef,
+    value: ObjectRef,
+    span: Span,
+) -> EvalResult<()> {
+    let mut object = object.borrow_mut();
+    let key = key.borrow();
+    let missing = || EvalError::MissingKey {
+        key: key.to_string(),
+        span,
+    };
+    let position = match as_number(&key) {
+        Some(Number::Integer(position)) => usize::try_from(position)
+            .ok()
+            .and_then(|p| p.checked_sub(1)),
+        _ => None,
+    };
+    let replaced: Box<dyn ObjectValue> =
+        if let Some(dictionary) = object.downcast::<DictionaryValue>() {
+            let mut dictionary = dictionary.clone();
+            match (as_text(&key), position) {
+                (Some(name), _) => {
+                    dictionary.entries.insert(name.to_string(), value);
+                }
+                (None, Some(index)) => {
+                    *dictionary
+                        .entries
+                        .get_index_mut(index)
+                        .ok_or_else(missing)?
+                        .1 = value;
+                }
+                (None, None) => {
+                    return Err(EvalError::TypeMismatch {
+                        expected: "String или Int".to_string(),
+                        found: type_name(&key),
+                        span,
+                    })
+                }
+            }
+            Box::new(dictionary)
+        } else if let Some(array) = object.downcast::<ArrayValue>() {
+            let mut array = array.clone();
+            let slot = position.and_then(|index| array.elements.get_mut(index));
+            *slot.ok_or_else(missing)? = value;
+            Box::new(array)
+        } else {
+            return Err(EvalError::TypeMismatch {
+                expected: "Dictionary или Array".to_string(),
+                found: type_name(&object),
+                span,
+            });
+        };
+    object.value = Some(replaced);
+    Ok(())
+}
+
+/// Во что преобразует `->`.
+enum ConversionTarget {
+    Unit(Unit),
+    Temperature(TemperatureScale),
+    /// `-> nfr`: точная дробь
+    Fraction,
+    /// `-> hsl`: цвет в другой записи
+    Color(ColorSpace),
+    /// `-> "+03:00"`: дата по часам другой зоны
+    Zone(i32),
+}
+
+fn conversion_target(
+    prelude: &Prelude,
+    target: &Object,
+    span: Span,
+) -> EvalResult<ConversionTarget> {
+    if let Some(quantity) = as_quantity(target) {
+        return Ok(ConversionTarget::Unit(quantity.unit.clone()));
+    }
+    if let Some(temperature) = as_temperature(target) {
+        return Ok(ConversionTarget::Temperature(temperature.scale));
+    }
+    if target.downcast::<FractionValue>().is_some() {
+        return Ok(ConversionTarget::Fraction);
+    }
+    let text = as_text(target).ok_or_else(|| EvalError::TypeMismatch {
+        expected: "единица измерения".to_string(),
+        found: type_name(target),
+        span,
+    })?;
+    text_conversion_target(prelude, text, span)
+}
+
+/// Цель `->`, записанная текстом: `n°F`, `nfr`, `hsl`, `"+03:00"`, `nkm/h`.
+fn text_conversion_target(
+    prelude: &Prelude,
+    text: &str,
+    span: Span,
+) -> EvalResult<ConversionTarget> {
+    if let Some(scale) = TemperatureScale::parse_target(text) {
+        return Ok(ConversionTarget::Temperature(scale));
+    }
+    if matches!(text, "nfr" | "fr") {
+        return Ok(ConversionTarget::Fraction);
+    }
+    if let Some(space) = ColorSpace::parse(text) {
+        return Ok(ConversionTarget::Color(space));
+    }
+    if let Some(offset) = parse_offset(text) {
+        return Ok(ConversionTarget::Zone(offset));
+    }
+    Unit::parse_target(text, &prelude.units)
+        .map(ConversionTarget::Unit)
+        .map_err(|error| EvalError::Unit { error, span })
+}
+
+/// Имя типа в правой части `is`: `value is Int`, `power is Energy`.
+fn type_operand(expr: &Expr) -> &str {
+    match &expr.kind {
+        ExprKind::Identifier(name) => name.as_str(),
+        ExprKind::Type(ty) => ty.kind.simple_name().unwrap_or_default(),
+        _ => "",
+    }
+}
+
+/// Числовой литерал: `10`, `20_000`, `0x1E`, `10.5`, `1e5`, `15kW`, `9.8m/s2`, `25°C`.
+fn parse_number(
+    prelude: &Prelude,
+    literal: &str,
+    unit: Option<&str>,
+    span: Span,
+) -> EvalResult<ObjectRef> {
+    let digits = literal.replace('_', "");
+    let invalid = || EvalError::InvalidNumber {
+        literal: literal.to_string(),
+        span,
+    };
+    if unit == Some("fr") {
+        return Rational::parse_decimal(&digits)
+            .map(|value| prelude.fraction_value(value))
+            .ok_or_else(invalid);
+    }
+    if let Some(unit) = unit {
+        let value = digits.parse::<f64>().map_err(|_| invalid())?;
+        // Отдельно стоящие `K`, `°C`, `°F` — абсолютная температура; внутри составной
+        // единицы (`J/K`) кельвин остаётся обычным множителем
+        if let Some(scale) = TemperatureScale::parse(unit) {
+            return Ok(prelude.temperature_value(TemperatureValue::absolute(value, scale)));
+        }
+        let unit = Unit::parse_in(unit, &prelude.units)
+            .map_err(|error| EvalError::Unit { error, span })?;
+        return Ok(prelude.quantity_value(value, unit));
+    }
+    // `0b`, `0o`, `0x`, `0t`, `0c`: значение помнит систему счисления литерала
+    let mut chars = digits.chars();
+    let (radix, digits) = match (chars.next(), chars.next().and_then(Radix::from_prefix)) {
+        (Some('0'), Some(radix)) => (radix, &digits[2..]),
+        _ => (Radix::Decimal, digits.as_str()),
+    };
+    // В `0x1E` буква `E` — цифра, порядок бывает только у десятичных литералов
+    if radix == Radix::Decimal && digits.contains(['.', 'e', 'E']) {
+        return digits
+            .parse::<f64>()
+            .map(|value| prelude.float_value(value))
+            .map_err(|_| invalid());
+    }
+    let digits: String = digits
+        .chars()
+        .map(|ch| radix.to_standard(ch))
+        .collect::<Option<_>>()
+        .ok_or_else(invalid)?;
+    // Не поместившееся в `i64` целое становится `BigInteger`
+    BigInt::parse_radix(&digits, radix.base())
+        .map(|value| prelude.big_integer_value(value, radix))
+        .ok_or_else(invalid)
+}
+
+/// `Infinity` и `NaN`, в том числе с единицей: `Infinitykg`, `NaN°C`.
+/// Знак — обычный унарный минус: `-Infinitykg`. Объявленное имя их перекрывает.
+fn special_number(prelude: &Prelude, name: &str) -> Option<ObjectRef> {
+    let (value, unit) = [("Infinity", f64::INFINITY), ("NaN", f64::NAN)]
+        .into_iter()
+        .find_map(|(word, value)| Some((value, name.strip_prefix(word)?)))?;
+    if unit.is_empty() {
+        return Some(prelude.float_value(value));
+    }
+    if let Some(scale) = TemperatureScale::parse(unit) {
+        return Some(prelude.temperature_value(TemperatureValue::absolute(value, scale)));
+    }
+    let unit = Unit::parse_in(unit, &prelude.units).ok()?;
+    Some(prelude.quantity_value(value, unit))
+}
+
+/// Символ `\u{${code}}`: код — целое число или шестнадцатеричная строка `"1F031"`.
+fn code_point(value: &Object, span: Span) -> EvalResult<char> {
+    let code = match (as_number(value), as_text(value)) {
+        (Some(Number::Integer(code)), _) => u32::try_from(code).ok(),
+        (_, Some(text)) => u32::from_str_radix(text, 16).ok(),
+        _ => {
+            return Err(EvalError::invalid(
+                format!(
+                    "код символа должен быть целым числом, а не {}",
+                    type_name(value)
+                ),
+                span,
+            ))
+        }
+    };
+    code.and_then(char::from_u32)
+        .ok_or_else(|| EvalError::invalid(format!("«{}» не код символа Unicode", value), span))
+}
+
+fn compound_operator(op: AssignOp) -> BinaryOp {
+    match op {
+        AssignOp::Add => BinaryOp::Add,
+        AssignOp::Sub => BinaryOp::Sub,
+        AssignOp::Mul => BinaryOp::Mul,
+        AssignOp::Div => BinaryOp::Div,
+        AssignOp::Mod => BinaryOp::Mod,
+        other => unreachable!("{:?} не является арифметическим присваиванием", other),
+    }
+}
+
+/// Оператор-значение `[+, -, *]` в терминах `OperatorType`.
+fn operator_type(op: BinaryOp) -> Option<OperatorType> {
+    Some(match op {
+        BinaryOp::Add => OperatorType::Add,
+        BinaryOp::Sub => OperatorType::Sub,
+        BinaryOp::Mul => OperatorType::Mul,
+        BinaryOp::Div => OperatorType::Div,
+        BinaryOp::IDiv => OperatorType::IDiv,
+        BinaryOp::Mod => OperatorType::Mod,
+        BinaryOp::Pow => OperatorType::Pow,
+        BinaryOp::Less => OperatorType::Less,
+        BinaryOp::Greater => OperatorType::Greater,
+        BinaryOp::LessEq => OperatorType::LessEq,
+        BinaryOp::GreaterEq => OperatorType::GreaterEq,
+        BinaryOp::Equal => OperatorType::Equal,
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interpretator::engine::engine::tests::entry;
+    use crate::interpretator::engine::engine::Engine;
+
+    const EXAMPLE: &str = include_str!("../../../../../example.lacon");
+
+    /// Блок из example.lacon: от строки `first` до первой `}` в начале строки.
+    fn example_block(first: &str) -> String {
+        let lines: Vec<&str> = EXAMPLE.lines().collect();
+        let start = lines
+            .iter()
+            .position(|line| *line == first)
+            .expect("блок есть в example.lacon");
+        let end = start
+            + lines[start..]
+                .iter()
+                .position(|line| *line == "}")
+                .expect("блок закрыт");
+        lines[start..=end].join("\n") + "\n"
+    }
+
+    #[test]
+    fn test_evaluate_data_document() {
+        let source = r#"
+[Mode:Data]
+const base = 10
+let count = 0
+const name = "мир"
+key-1 value
+total = base * 2 + 1
+ratio 7 / 2
+greeting "Привет, ${name}!"
+loop 3 {
+    count++
+}
+counter count
+nested {
+    zeta true
+    alpha [1, 2, ...[3, 4]]
+    mid 0
+}
+"#;
+        let root = Engine::new().evaluate_document(source).unwrap();
+        assert_eq!(entry(&root, "key-1"), "value");
+        assert_eq!(entry(&root, "total"), "21");
+        assert_eq!(entry(&root, "ratio"), "3.5");
+        assert_eq!(entry(&root, "greeting"), "Привет, мир!");
+        assert_eq!(entry(&root, "counter"), "3");
+        assert_eq!(
+            entry(&root, "nested"),
+            r#"{"zeta": true, "alpha": [1, 2, 3, 4], "mid": 0}"#
+        );
+        assert!(!root
+            .borrow()
+            .downcast::<DictionaryValue>()
+            .unwrap()
+            .entries
+            .contains_key("base"));
+
+        let root = Engine::new()
+            .evaluate_document("[Document:Array]\n1\n\"два\"\n")
+            .unwrap();
+        assert_eq!(
+            root.borrow()
+                .downcast::<ArrayValue>()
+                .unwrap()
+                .elements
+                .len(),
+            2
+        );
+
+        let errors = Engine::new()
+            .evaluate_document("const x = 1\nx = 2\ny = missing.key\n")
+            .unwrap_err();
+        let codes: Vec<_> = errors
+            .iter()
+            .map(|error| error.code.as_deref().unwrap())
+            .collect();
+        assert_eq!(codes, ["E0203", "E0206"]);
+
+        // Выход из корня документа — ошибка, а не молчаливый конец документа
+        let errors = Engine::new()
+            .evaluate_document("a 1\nif (true) { break }\nb 2\nsettings { continue }\n")
+            .unwrap_err();
+        let messages: Vec<_> = errors.iter().map(|error| error.message.as_str()).collect();
+        assert_eq!(messages, ["«break» вне цикла", "«continue» вне цикла"]);
+    }
+
+    #[test]
+    fn test_exact_numbers() {
+        let source = r#"
+a = 9223372036854775807 + 1
+b = 0x1E + 1
+b32 = [0cZYX + 1, 0tL1FF, 0cZYX is Base32]
+c = 1fr / 7
+d = (2/3fr) ** 2 + 1
+e = 0.75 -> nfr
+f = (2 ** 100) // (2 ** 90)
+g = -Infinitykg
+h = [NaN == NaN, NaN < 1, Infinity > 10 ** 100]
+i = [1.0 / 0.0, -1.0 / 0.0, 0.0 / 0.0, 5kg / 0.0]
+j = [1e5, 2.5E-3, 6e+2kW, 0x1E]
+"#;
+        let root = Engine::new().evaluate_document(source).unwrap();
+        assert_eq!(entry(&root, "a"), "9223372036854775808");
+        assert_eq!(entry(&root, "b"), "0x1F");
+        assert_eq!(entry(&root, "b32"), "[0cZYY, 0tL1FF, true]");
+        assert_eq!(entry(&root, "c"), "1/7fr");
+        assert_eq!(entry(&root, "d"), "13/9fr");
+        assert_eq!(entry(&root, "e"), "3/4fr");
+        assert_eq!(entry(&root, "f"), "1024");
+        assert_eq!(entry(&root, "g"), "-Infinitykg");
+        assert_eq!(entry(&root, "h"), "[false, false, true]");
+        assert_eq!(entry(&root, "i"), "[Infinity, -Infinity, NaN, Infinitykg]");
+        assert_eq!(entry(&root, "j"), "[100000.0, 0.0025, 600kW, 0x1E]");
+
+        for division in ["1 / 0", "1 // 0", "(2 ** 100) / 0", "1fr / 0"] {
+            let errors = Engine::new()
+                .evaluate_document(&format!("a = {}\n", division))
+                .unwrap_err();
+            assert_eq!(errors[0].message, "деление на ноль", "{}", division);
+        }
+    }
+
+    #[test]
+    fn test_expansion_blocks() {
+        let source = r#"
+const numbers = [1, 2, 3, 4, 5, 6]
+let total = 0
+countdown [
+    generate (0x3 .. -1) as local let i {
+        yield i
+    }
+]
+spread (["c", "s"], ["pro", "ypo"]) as let (letter-case, suffix) {
+    yield [letter-case + "_" + suffix], index
+}
+evens [
+    filter (numbers) as let (n) {
+        if (n % 2 == 0) yield n
+    },
+    100
+]
+spread({a: 1, b: 2}, {c: 3}) as let (k, v) {
+    total += v
+}
+sum total
+"#;
+        let root = Engine::new().evaluate_document(source).unwrap();
+        assert_eq!(entry(&root, "countdown"), "[3, 2, 1, 0, -1]");
+        assert_eq!(entry(&root, "c_pro"), "1");
+        assert_eq!(entry(&root, "s_ypo"), "2");
+        assert_eq!(entry(&root, "evens"), "[2, 4, 6, 100]");
+        assert_eq!(entry(&root, "sum"), "6");
+
+        let errors = Engine::new()
+            .evaluate_document(concat!(
+                "yield 1\n",
+                "spread ([1, 2], [3]) as (a, b) { yield a }\n",
+                "huge [generate (0 .. 10 ** 12) as i { yield i }]\n",
+            ))
+            .unwrap_err();
+        assert_eq!(
+            errors[1].message,
+            "источники spread идут параллельно и должны быть одной длины, а здесь 2 и 1"
+        );
+        assert_eq!(
+            errors[2].message,
+            "диапазон 0 .. 1000000000000 длиннее 1000000 элементов"
+        );
+    }
+
+    #[test]
+    fn test_example_expansion_blocks() {
+        let evaluate = |first: &str, shown: &str| {
+            let source = format!("{}shown {}\n", example_block(first), shown);
+            let root = Engine::new().evaluate_document(&source).unwrap();
+            entry(&root, "shown")
+        };
+
+        let root = Engine::new()
+            .evaluate_document(&example_block(
+                "spread ['one', 'two', 'three', 'four', 'five'] as name {",
+            ))
+            .unwrap();
+        assert_eq!(entry(&root, "digit-one"), r#"{"number-value": 1}"#);
+        assert_eq!(entry(&root, "digit-five"), r#"{"number-value": 5}"#);
+
+        let letters = concat!(
+            r#"{"hel_c_let_a_alpha__prosgegrammeni": "#,
+            r#"{"unicode": 1FBC, "symbol": {"letter": hel_c_let_a_alpha}}, "#,
+            r#""hel_s_let_a_alpha__ypogegrammeni": "#,
+            r#"{"unicode": 1FB3, "symbol": {"letter": hel_s_let_a_alpha}}}"#,
+        );
+        assert_eq!(evaluate("const data = {", "data"), letters);
+        assert_eq!(evaluate("const data = {}", "data"), letters);
+
+        assert_eq!(
+            evaluate("const merged = {};", "merged"),
+            r#"{"a": 1, "b": 2, "c": 3, "d": 4}"#
+        );
+        assert_eq!(evaluate("const obj = {", "obj"), r#"{"a": 1, "b": 2}"#);
+        assert_eq!(
+            evaluate("const result = {};", "result"),
+            r#"{"even_b": 2, "even_d": 4}"#
+        );
+
+        let numbers = EXAMPLE
+            .lines()
+            .find(|line| line.starts_with("const numbers = "))
+            .unwrap();
+        let source = format!(
+            "{}\n{}shown evenNumbers\n",
+            numbers,
+            example_block("const evenNumbers = {")
+        );
+        let root = Engine::new().evaluate_document(&source).unwrap();
+        let even = entry(&root, "shown");
+        assert!(
+            even.starts_with(r#"{"number_2": 2, "number_4": 4,"#),
+            "{}",
+            even
+        );
+        assert!(even.ends_with(r#""number_22": 22}"#), "{}", even);
+
+        // Диапазон в примере заходит в отрицательные коды — это ошибка, а не пустые символы
+        let errors = Engine::new()
+            .evaluate_document(&example_block(
+                "generate (0x4E3 .. -10) as local let code-point {",
+            ))
+            .unwrap_err();
+        assert_eq!(errors.len(), 10);
+        assert_eq!(errors[0].message, "«-1» не код символа Unicode");
+
+        // Поля и элементы меняются на месте; `const` не даёт перепривязать имя
+        let root = Engine::new()
+            .evaluate_document(concat!(
+                "const counts = [1, 2]\n",
+                "const alias = counts\n",
+                "counts[2] += 5\n",
+                "const settings = {mode: \"a\"}\n",
+                "settings.mode = \"b\"\n",
+                "shown [alias, settings]\n",
+            ))
+            .unwrap();
+        assert_eq!(entry(&root, "shown"), r#"[[1, 7], {"mode": b}]"#);
+        let errors = Engine::new()
+            .evaluate_document("const counts = [1]\ncounts[3] = 1\nconst n = 1\nn.x = 2\n")
+            .unwrap_err();
+        let codes: Vec<_> = errors
+            .iter()
+            .filter_map(|error| error.code.as_deref())
+            .collect();
+        assert_eq!(codes, ["E0206", "E0205"]);
+    }
+}
@@ -0,0 +1,80 @@
+use crate::interpretator::prototypes::types::object::date_time::DateTimeValue;
+use crate::interpretator::prototypes::types::object::object::ObjectRef;
+use crate::interpretator::prototypes::types::primitive::color::ColorValue;
+use crate::interpretator::stdlib::core::ops::OpError;
+use crate::interpretator::stdlib::core::prelude::Prelude;
+use crate::interpretator::stdlib::core::primitives::type_name;
+use crate::interpretator::stdlib::core::{colors, dates, strings};
+
+/// Встроенная функция по имени: `RGBA`, `HSL`, `now`, `DateTime`, `format`.
+/// Объявленное в документе имя её перекрывает.
+pub fn function(prelude: &Prelude, name: &str) -> Option<ObjectRef> {
+    let parameters = colors::constructor_parameters(name)
+        .or_else(|| dates::function_parameters(name))
+        .or_else(|| strings::function_parameters(name))?;
+    Some(prelude.builtin_value(name, parameters, None))
+}
+
+/// Встроенная функция без побочных эффектов: только такие вызывает List LaCoN.
+pub fn is_function(name: &str) -> bool {
+    colors::constructor_parameters(name).is_some()
+        || dates::function_parameters(name).is_some()
+        || strings::function_parameters(name).is_some()
+}
+
+/// Метод встроенного типа: `color.lighten`, `moment.format`. Они не меняют получателя.
+pub fn is_method(name: &str) -> bool {
+    colors::method_parameters(name).is_some() || dates::method_parameters(name).is_some()
+}
+
+/// Метод встроенного значения, привязанный к нему: `color.lighten`, `moment.format`.
+pub fn method(prelude: &Prelude, receiver: &ObjectRef, name: &str) -> Option<ObjectRef> {
+    let parameters = {
+        let object = receiver.borrow();
+        if object.downcast::<ColorValue>().is_some() {
+            colors::method_parameters(name)
+        } else if object.downcast::<DateTimeValue>().is_some() {
+            dates::method_parameters(name)
+        } else {
+            None
+        }
+    }?;
+    Some(prelude.builtin_value(name, parameters, Some(receiver.clone())))
+}
+
+/// Свойство встроенного значения: `color.red`, `moment.year`.
+pub fn property(prelude: &Prelude, object: &ObjectRef, name: &str) -> Option<ObjectRef> {
+    let object = object.borrow();
+    match object.downcast::<ColorValue>() {
+        Some(color) => colors::property(prelude, color, name),
+        None => dates::property(prelude, &object, name),
+    }
+}
+
+/// Вызов встроенной функции (`receiver` нет) или метода.
+pub fn call(
+    prelude: &Prelude,
+    name: &str,
+    receiver: Option<&ObjectRef>,
+    arguments: &[ObjectRef],
+) -> Result<ObjectRef, OpError> {
+    let Some(receiver) = receiver else {
+        if colors::constructor_parameters(name).is_some() {
+            return colors::construct(prelude, name, arguments);
+        }
+        return match strings::function_parameters(name) {
+            Some(_) => strings::call(prelude, name, arguments),
+            None => dates::call(prelude, name, arguments),
+        };
+    };
+    if let Some(color) = colors::as_color(receiver) {
+        return colors::method(prelude, &color, name, arguments);
+    }
+    if let Some(moment) = dates::as_date_time(receiver) {
+        return dates::method(prelude, &moment, name, arguments);
+    }
+    Err(OpError::InvalidOperand {
+        op: ".",
+        operand: type_name(&receiver.borrow()),
+    })
+}
@@ -0,0 +1,189 @@
+use crate::interpretator::frontend::ast::expression::BinaryOp;
+use crate::interpretator::prototypes::types::formal::formal::{Dimension, Unit};
+use crate::interpretator::prototypes::types::object::object::ObjectRef;
+use crate::interpretator::prototypes::types::primitive::color::{ColorSpace, ColorValue};
+use crate::interpretator::stdlib::core::ops::OpError;
+use crate::interpretator::stdlib::core::prelude::Prelude;
+use crate::interpretator::stdlib::core::primitives::{as_number, as_quantity, type_name};
+
+/// Конструкторы цвета и их параметры. Имена не зависят от регистра: `RGBA` и `rgba` равнозначны.
+const CONSTRUCTORS: [(&str, &[&str]); 4] = [
+    ("RGB", &["red", "green", "blue"]),
+    ("RGBA", &["red", "green", "blue", "alpha"]),
+    ("HSL", &["hue", "saturation", "lightness"]),
+    ("HSLA", &["hue", "saturation", "lightness", "alpha"]),
+];
+
+/// Методы цвета и их параметры; `weight` у `mix` необязателен.
+const METHODS: [(&str, &[&str]); 4] = [
+    ("lighten", &["amount"]),
+    ("darken", &["amount"]),
+    ("mix", &["other", "weight"]),
+    ("contrast", &["other"]),
+];
+
+pub fn constructor_parameters(name: &str) -> Option<&'static [&'static str]> {
+    CONSTRUCTORS
+        .iter()
+        .find(|(constructor, _)| constructor.eq_ignore_ascii_case(name))
+        .map(|(_, parameters)| *parameters)
+}
+
+pub fn method_parameters(name: &str) -> Option<&'static [&'static str]> {
+    METHODS
+        .iter()
+        .find(|(method, _)| *method == name)
+        .map(|(_, parameters)| *parameters)
+}
+
+/// `RGBA(250, 130, 70, 0.8)`, `HSL(20, 95%, 63%)`, `HSLA(20deg, 95, 63, 80%)`.
+///
+/// Как в CSS, каналы — числа 0–255 или проценты, тон — число градусов или угол,
+/// насыщенность и светлота — проценты (число без `%` считается процентами),
+/// прозрачность — число 0–1 или проценты. Прозрачность можно передать и `RGB`/`HSL`.
+pub fn construct(
+    prelude: &Prelude,
+    name: &str,
+    arguments: &[ObjectRef],
+) -> Result<ObjectRef, OpError> {
+    let name = name.to_ascii_uppercase();
+    let invalid = || arguments_error(&name, constructor_parameters(&name).unwrap_or_default());
+    if !matches!(arguments.len(), 3 | 4) {
+        return Err(invalid());
+    }
+    let alpha = match arguments.get(3) {
+        Some(alpha) => share(alpha, 1.0).ok_or_else(invalid)?,
+        None => 1.0,
+    };
+    let color = match name.as_str() {
+        "RGB" | "RGBA" => {
+            let channels = arguments[..3]
+                .iter()
+                .map(channel)
+                .collect::<Option<Vec<_>>>()
+                .ok_or_else(invalid)?;
+            ColorValue::rgba(channels[0], channels[1], channels[2], alpha)
+        }
+        _ => {
+            let hue = hue(&arguments[0]).ok_or_else(invalid)?;
+            let saturation = share(&arguments[1], 100.0).ok_or_else(invalid)?;
+            let lightness = share(&arguments[2], 100.0).ok_or_else(invalid)?;
+            ColorValue::hsla(hue, saturation, lightness, alpha)
+        }
+    };
+    Ok(prelude.color_value(color))
+}
+
+/// `color.lighten(10%)`, `color.darken(0.1)`, `color.mix(other, 25%)`, `color.contrast(other)`.
+/// Доля — проценты или число от 0 до 1; `mix` без веса смешивает поровну.
+pub fn method(
+    prelude: &Prelude,
+    color: &ColorValue,
+    name: &str,
+    arguments: &[ObjectRef],
+) -> Result<ObjectRef, OpError> {
+    let invalid = || arguments_error(name, method_parameters(name).unwrap_or_default());
+    match (name, arguments) {
+        ("lighten", [amount]) => {
+            let amount = share(amount, 1.0).ok_or_else(invalid)?;
+            Ok(prelude.color_value(color.lighten(amount)))
+        }
+        ("darken", [amount]) => {
+            let amount = share(amount, 1.0).ok_or_else(invalid)?;
+            Ok(prelude.color_value(color.lighten(-amount)))
+        }
+        ("mix", [other]) => {
+            let other = as_color(other).ok_or_else(invalid)?;
+            Ok(prelude.color_value(color.mix(&other, 0.5)))
+        }
+        ("mix", [other, weight]) => {
+            let other = as_color(other).ok_or_else(invalid)?;
+            let weight = share(weight, 1.0).ok_or_else(invalid)?;
+            Ok(prelude.color_value(color.mix(&other, weight)))
+        }
+        ("contrast", [other]) => {
+            let other = as_color(other).ok_or_else(invalid)?;
+            Ok(prelude.float_value(color.contrast(&other)))
+        }
+        _ => Err(invalid()),
+    }
+}
+
+/// Каналы цвета: `red`, `green`, `blue` — целые 0–255, `alpha` — 0–1,
+/// `hue` — градусы, `saturation` и `lightness` — проценты.
+pub fn property(prelude: &Prelude, color: &ColorValue, name: &str) -> Option<ObjectRef> {
+    let rounded = |value: f64| (value * 100.0).round() / 100.0;
+    let percent = |value: f64| {
+        let unit = Unit::parse("%").expect("процент — известная единица");
+        prelude.quantity_value(rounded(value * 100.0), unit)
+    };
+    let (hue, saturation, lightness) = color.to_hsl();
+    Some(match name {
+        "red" => prelude.integer_value(color.red.round() as i64),
+        "green" => prelude.integer_value(color.green.round() as i64),
+        "blue" => prelude.integer_value(color.blue.round() as i64),
+        "alpha" => prelude.float_value(rounded(color.alpha)),
+        "hue" => prelude.float_value(rounded(hue)),
+        "saturation" => percent(saturation),
+        "lightness" => percent(lightness),
+        _ => return None,
+    })
+}
+
+/// `color -> hsl`: тот же цвет в другой записи.
+pub fn convert(
+    prelude: &Prelude,
+    value: &ObjectRef,
+    space: ColorSpace,
+) -> Result<ObjectRef, OpError> {
+    match as_color(value) {
+        Some(color) => Ok(prelude.color_value(color.in_space(space))),
+        None => Err(OpError::InvalidOperand {
+            op: BinaryOp::Convert.as_str(),
+            operand: type_name(&value.borrow()),
+        }),
+    }
+}
+
+pub fn as_color(object: &ObjectRef) -> Option<ColorValue> {
+    object.borrow().downcast::<ColorValue>().cloned()
+}
+
+fn arguments_error(function: &str, parameters: &[&str]) -> OpError {
+    OpError::Arguments {
+        function: function.to_string(),
+        parameters: parameters.join(", "),
+    }
+}
+
+/// Канал RGB: число 0–255 или доля от 255 в процентах.
+fn channel(object: &ObjectRef) -> Option<f64> {
+    let object = object.borrow();
+    match as_quantity(&object) {
+        Some(quantity) if quantity.unit.is_fraction() => Some(quantity.si_value() * 255.0),
+        Some(_) => None,
+        None => as_number(&object).map(|number| number.as_f64()),
+    }
+}
+
+/// Тон в градусах: число или угол в любых единицах.
+fn hue(object: &ObjectRef) -> Option<f64> {
+    let object = object.borrow();
+    match as_quantity(&object) {
+        Some(quantity) if quantity.dimension() == Dimension::ANGLE => {
+            Some(quantity.si_value().to_degrees())
+        }
+        Some(_) => None,
+        None => as_number(&object).map(|number| number.as_f64()),
+    }
+}
+
+/// Доля от 0 до 1: проценты или число, где `scale` — число, соответствующее целому.
+fn share(object: &ObjectRef, scale: f64) -> Option<f64> {
+    let object = object.borrow();
+    match as_quantity(&object) {
+        Some(quantity) if quantity.unit.is_fraction() => Some(quantity.si_value()),
+        Some(_) => None,
+        None => as_number(&object).map(|number| number.as_f64() / scale),
+    }
+}
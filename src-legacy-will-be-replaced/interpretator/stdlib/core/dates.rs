@@ -0,0 +1,382 @@
+use crate::interpretator::frontend::ast::expression::{BinaryOp, UnaryOp};
+use crate::interpretator::prototypes::types::formal::formal::{QuantityValue, Unit};
+use crate::interpretator::prototypes::types::formal::time::calendar_months;
+use crate::interpretator::prototypes::types::object::date_time::{
+    format_offset, parse_offset, DateTimeError, DateTimeValue, DurationValue,
+};
+use crate::interpretator::prototypes::types::object::object::{Object, ObjectRef};
+use crate::interpretator::stdlib::core::ops::OpError;
+use crate::interpretator::stdlib::core::prelude::Prelude;
+use crate::interpretator::stdlib::core::primitives::{
+    as_duration, as_number, as_quantity, as_text, type_name,
+};
+use std::cmp::Ordering;
+
+/// Встроенные функции времени и их параметры; `pattern` у `DateTime` необязателен.
+const FUNCTIONS: [(&str, &[&str]); 4] = [
+    ("now", &[]),
+    ("today", &[]),
+    ("DateTime", &["value", "pattern"]),
+    ("Duration", &["value"]),
+];
+
+const METHODS: [(&str, &[&str]); 2] = [("format", &["pattern"]), ("in_zone", &["offset"])];
+
+/// Операнд арифметики над датами.
+enum Operand {
+    DateTime(DateTimeValue),
+    Duration(DurationValue),
+    /// Величина времени: `10s`, `25μs`
+    Time(QuantityValue),
+    Number(f64),
+    Other(String),
+}
+
+fn operand(object: &ObjectRef) -> Operand {
+    let object = object.borrow();
+    if let Some(moment) = object.downcast::<DateTimeValue>() {
+        return Operand::DateTime(moment.clone());
+    }
+    if let Some(duration) = object.downcast::<DurationValue>() {
+        return Operand::Duration(*duration);
+    }
+    if let (Some(quantity), Some(_)) = (as_quantity(&object), as_duration(&object)) {
+        return Operand::Time(quantity.clone());
+    }
+    match as_number(&object) {
+        Some(number) => Operand::Number(number.as_f64()),
+        None => Operand::Other(type_name(&object)),
+    }
+}
+
+/// Промежуток, который задаёт операнд: `Duration` или величина времени.
+fn span_of(operand: &Operand) -> Option<DurationValue> {
+    match operand {
+        Operand::Duration(duration) => Some(*duration),
+        Operand::Time(quantity) => DurationValue::from_seconds(quantity.si_value()),
+        _ => None,
+    }
+}
+
+/// Число календарных месяцев в величине из годов или месяцев: `Some(Some(18))` для `1.5y`,
+/// `Some(None)`, если месяцев не целое число, и `None` для других единиц времени.
+fn months_of(quantity: &QuantityValue) -> Option<Option<i64>> {
+    let [term] = quantity.unit.terms.as_slice() else {
+        return None;
+    };
+    if term.power != 1 {
+        return None;
+    }
+    let months = quantity.value * calendar_months(&term.symbol)? as f64;
+    let whole = months.round();
+    match (months - whole).abs() < 1e-9 && whole.abs() < i64::MAX as f64 {
+        true => Some(Some(whole as i64)),
+        false => Some(None),
+    }
+}
+
+/// Бинарная операция с датой или промежутком. `None` — среди операндов их нет
+/// или оператор не арифметический.
+///
+/// - дата ± промежуток = дата, дата − дата = промежуток;
+/// - промежутки складываются с величинами времени, тип берётся у левого операнда:
+///   `Duration(1h) + 30min = PT1H30M`, `30min + Duration(1h) = 90min`;
+/// - промежуток умножается и делится на число, а промежуток на промежуток — число.
+pub fn binary(
+    prelude: &Prelude,
+    op: BinaryOp,
+    left: &ObjectRef,
+    right: &ObjectRef,
+) -> Option<Result<ObjectRef, OpError>> {
+    if !matches!(
+        op,
+        BinaryOp::Add | BinaryOp::Sub | BinaryOp::Mul | BinaryOp::Div
+    ) {
+        return None;
+    }
+    let (left, right) = (operand(left), operand(right));
+    let involved =
+        |operand: &Operand| matches!(operand, Operand::DateTime(_) | Operand::Duration(_));
+    if !involved(&left) && !involved(&right) {
+        return None;
+    }
+    let overflow = || OpError::Overflow { op: op.as_str() };
+    let duration = |nanos: Option<i128>| {
+        nanos
+            .map(|nanos| prelude.duration_value(DurationValue { nanos }))
+            .ok_or_else(overflow)
+    };
+    let shifted = |moment: &DateTimeValue, nanos: i128| {
+        moment
+            .shifted(nanos)
+            .map(|moment| prelude.date_time_value(moment))
+            .ok_or_else(overflow)
+    };
+
+    let months = |moment: &DateTimeValue, quantity: &QuantityValue, sign: i64| {
+        let count = months_of(quantity).flatten().ok_or_else(|| {
+            OpError::DateTime(DateTimeError::FractionalMonths(quantity.to_string()))
+        })?;
+        moment
+            .add_months(sign * count)
+            .map(|moment| prelude.date_time_value(moment))
+            .ok_or_else(overflow)
+    };
+
+    let result = match (op, &left, &right) {
+        // Годы и месяцы идут по календарю: `2024-03-15T10:30:00Z + 1y` — то же время
+        // через год, а не через 365.25 суток
+        (BinaryOp::Add, Operand::DateTime(moment), Operand::Time(quantity))
+        | (BinaryOp::Add, Operand::Time(quantity), Operand::DateTime(moment))
+            if months_of(quantity).is_some() =>
+        {
+            months(moment, quantity, 1)
+        }
+        (BinaryOp::Sub, Operand::DateTime(moment), Operand::Time(quantity))
+            if months_of(quantity).is_some() =>
+        {
+            months(moment, quantity, -1)
+        }
+        (BinaryOp::Sub, Operand::DateTime(a), Operand::DateTime(b)) => {
+            duration(a.timestamp_nanos().checked_sub(b.timestamp_nanos()))
+        }
+        (BinaryOp::Add, Operand::DateTime(moment), other)
+        | (BinaryOp::Add, other, Operand::DateTime(moment))
+            if span_of(other).is_some() =>
+        {
+            shifted(moment, span_of(other).map_or(0, |span| span.nanos))
+        }
+        (BinaryOp::Sub, Operand::DateTime(moment), other) if span_of(other).is_some() => {
+            shifted(moment, -span_of(other).map_or(0, |span| span.nanos))
+        }
+        (BinaryOp::Add | BinaryOp::Sub, Operand::Time(quantity), Operand::Duration(b)) => {
+            let seconds = QuantityValue::new(b.seconds(), Unit::parse("s").expect("секунда"));
+            let b = seconds.value_in(&quantity.unit).unwrap_or_default();
+            let value = match op {
+                BinaryOp::Add => quantity.value + b,
+                _ => quantity.value - b,
+            };
+            Ok(prelude.quantity_value(value, quantity.unit.clone()))
+        }
+        (BinaryOp::Add | BinaryOp::Sub, Operand::Duration(a), other)
+            if span_of(other).is_some() =>
+        {
+            let b = span_of(other).map_or(0, |span| span.nanos);
+            duration(match op {
+                BinaryOp::Add => a.nanos.checked_add(b),
+                _ => a.nanos.checked_sub(b),
+            })
+        }
+        (BinaryOp::Mul, Operand::Duration(a), Operand::Number(b))
+        | (BinaryOp::Mul, Operand::Number(b), Operand::Duration(a)) => {
+            DurationValue::from_seconds(a.seconds() * b)
+                .map(|duration| prelude.duration_value(duration))
+                .ok_or_else(overflow)
+        }
+        (BinaryOp::Div, Operand::Duration(_), Operand::Number(b)) if *b == 0.0 => {
+            Err(OpError::DivisionByZero)
+        }
+        (BinaryOp::Div, Operand::Duration(a), Operand::Number(b)) => {
+            DurationValue::from_seconds(a.seconds() / b)
+                .map(|duration| prelude.duration_value(duration))
+                .ok_or_else(overflow)
+        }
+        (BinaryOp::Div, Operand::Duration(a), other) if span_of(other).is_some() => {
+            match span_of(other).map_or(0, |span| span.nanos) {
+                0 => Err(OpError::DivisionByZero),
+                b => Ok(prelude.float_value(a.nanos as f64 / b as f64)),
+            }
+        }
+        _ => Err(OpError::InvalidOperands {
+            op: op.as_str(),
+            left: describe(&left),
+            right: describe(&right),
+        }),
+    };
+    Some(result)
+}
+
+/// `-` меняет знак промежутка.
+pub fn unary(prelude: &Prelude, op: UnaryOp, operand: &ObjectRef) -> Option<ObjectRef> {
+    let duration = *operand.borrow().downcast::<DurationValue>()?;
+    match op {
+        UnaryOp::Negate => Some(prelude.duration_value(DurationValue {
+            nanos: -duration.nanos,
+        })),
+        UnaryOp::Plus => Some(operand.clone()),
+        _ => None,
+    }
+}
+
+/// Даты сравниваются по моменту, промежутки — между собой и с величинами времени.
+pub fn compare(left: &ObjectRef, right: &ObjectRef) -> Option<Ordering> {
+    match (operand(left), operand(right)) {
+        (Operand::DateTime(a), Operand::DateTime(b)) => {
+            Some(a.timestamp_nanos().cmp(&b.timestamp_nanos()))
+        }
+        (left, right)
+            if matches!(left, Operand::Duration(_)) || matches!(right, Operand::Duration(_)) =>
+        {
+            Some(span_of(&left)?.cmp(&span_of(&right)?))
+        }
+        _ => None,
+    }
+}
+
+/// `moment -> "+03:00"`, `moment -> UTC`: тот же момент по часам другой зоны.
+pub fn convert_zone(
+    prelude: &Prelude,
+    value: &ObjectRef,
+    offset: i32,
+) -> Result<ObjectRef, OpError> {
+    match as_date_time(value) {
+        Some(moment) => Ok(prelude.date_time_value(moment.in_zone(offset))),
+        None => Err(OpError::InvalidOperand {
+            op: BinaryOp::Convert.as_str(),
+            operand: type_name(&value.borrow()),
+        }),
+    }
+}
+
+pub fn as_date_time(object: &ObjectRef) -> Option<DateTimeValue> {
+    object.borrow().downcast::<DateTimeValue>().cloned()
+}
+
+pub fn function_parameters(name: &str) -> Option<&'static [&'static str]> {
+    FUNCTIONS
+        .iter()
+        .find(|(function, _)| *function == name)
+        .map(|(_, parameters)| *parameters)
+}
+
+pub fn method_parameters(name: &str) -> Option<&'static [&'static str]> {
+    METHODS
+        .iter()
+        .find(|(method, _)| *method == name)
+        .map(|(_, parameters)| *parameters)
+}
+
+/// `now()` и `today()` по часам хоста, `DateTime("15.03.2024", "dd.MM.yyyy")`,
+/// `DateTime(0)` — момент по секундам эпохи Unix, `Duration(90min)`, `Duration("PT1H30M")`.
+pub fn call(prelude: &Prelude, name: &str, arguments: &[ObjectRef]) -> Result<ObjectRef, OpError> {
+    let invalid = || arguments_error(name, function_parameters(name).unwrap_or_default());
+    match (name, arguments) {
+        ("now", []) => Ok(prelude.date_time_value(prelude.clock.now())),
+        ("today", []) => Ok(prelude.date_time_value(prelude.clock.now().date())),
+        ("DateTime", [value]) => {
+            let value = value.borrow();
+            if let Some(number) = as_number(&value) {
+                let nanos = DurationValue::from_seconds(number.as_f64()).ok_or_else(invalid)?;
+                let epoch = DateTimeValue::from_timestamp(0, 0, Some(0));
+                return epoch
+                    .shifted(nanos.nanos)
+                    .map(|moment| prelude.date_time_value(moment))
+                    .ok_or(OpError::DateTime(DateTimeError::OutOfRange));
+            }
+            let text = as_text(&value).ok_or_else(invalid)?;
+            DateTimeValue::parse_iso(text)
+                .map(|moment| prelude.date_time_value(moment))
+                .ok_or_else(|| {
+                    OpError::DateTime(DateTimeError::Mismatch {
+                        text: text.to_string(),
+                        pattern: "ISO 8601".to_string(),
+                    })
+                })
+        }
+        ("DateTime", [text, pattern]) => {
+            let (text, pattern) = (text.borrow(), pattern.borrow());
+            let (text, pattern) = (as_text(&text), as_text(&pattern));
+            let (text, pattern) = text.zip(pattern).ok_or_else(invalid)?;
+            DateTimeValue::parse(text, pattern)
+                .map(|moment| prelude.date_time_value(moment))
+                .map_err(OpError::DateTime)
+        }
+        ("Duration", [value]) => {
+            let value = value.borrow();
+            let duration = match (as_duration(&value), as_number(&value), as_text(&value)) {
+                (Some(duration), _, _) => Some(duration),
+                (_, Some(seconds), _) => DurationValue::from_seconds(seconds.as_f64()),
+                (_, _, Some(text)) => DurationValue::parse_iso(text),
+                _ => None,
+            };
+            duration
+                .map(|duration| prelude.duration_value(duration))
+                .ok_or_else(invalid)
+        }
+        _ => Err(invalid()),
+    }
+}
+
+/// `moment.format("dd.MM.yyyy")`, `moment.in_zone("+03:00")`.
+pub fn method(
+    prelude: &Prelude,
+    moment: &DateTimeValue,
+    name: &str,
+    arguments: &[ObjectRef],
+) -> Result<ObjectRef, OpError> {
+    let invalid = || arguments_error(name, method_parameters(name).unwrap_or_default());
+    let [argument] = arguments else {
+        return Err(invalid());
+    };
+    let argument = argument.borrow();
+    let text = as_text(&argument).ok_or_else(invalid)?;
+    match name {
+        "format" => moment
+            .format(text)
+            .map(|text| prelude.string_value(text))
+            .map_err(OpError::DateTime),
+        "in_zone" => parse_offset(text)
+            .map(|offset| prelude.date_time_value(moment.in_zone(offset)))
+            .ok_or_else(|| OpError::DateTime(DateTimeError::InvalidOffset(text.to_string()))),
+        _ => Err(invalid()),
+    }
+}
+
+/// Поля даты по её местным часам (`year`, `month`, `day`, `hour`, `minute`, `second`,
+/// `nanosecond`, `weekday` — 1 для понедельника), `offset`, `timestamp` — секунды эпохи Unix,
+/// `date` — дата без времени; у промежутка — `seconds`.
+pub fn property(prelude: &Prelude, object: &Object, name: &str) -> Option<ObjectRef> {
+    if let Some(duration) = object.downcast::<DurationValue>() {
+        return match name {
+            "seconds" => Some(prelude.float_value(duration.seconds())),
+            _ => None,
+        };
+    }
+    let moment = object.downcast::<DateTimeValue>()?;
+    let fields = moment.fields();
+    let integer = |value: i64| Some(prelude.integer_value(value));
+    match name {
+        "year" => integer(fields.year),
+        "month" => integer(fields.month as i64),
+        "day" => integer(fields.day as i64),
+        "hour" => integer(fields.hour as i64),
+        "minute" => integer(fields.minute as i64),
+        "second" => integer(fields.second as i64),
+        "nanosecond" => integer(fields.nanosecond as i64),
+        "weekday" => integer(moment.weekday() as i64),
+        "timestamp" => integer(moment.seconds),
+        "offset" => Some(match moment.offset {
+            Some(offset) => prelude.string_value(format_offset(offset)),
+            None => prelude.none_value(),
+        }),
+        "date" => Some(prelude.date_time_value(moment.date())),
+        _ => None,
+    }
+}
+
+fn arguments_error(function: &str, parameters: &[&str]) -> OpError {
+    OpError::Arguments {
+        function: function.to_string(),
+        parameters: parameters.join(", "),
+    }
+}
+
+fn describe(operand: &Operand) -> String {
+    match operand {
+        Operand::DateTime(_) => "DateTime".to_string(),
+        Operand::Duration(_) => "Duration".to_string(),
+        Operand::Time(quantity) => quantity.dimension().describe(),
+        Operand::Number(_) => "Number".to_string(),
+        Operand::Other(type_name) => type_name.clone(),
+    }
+}
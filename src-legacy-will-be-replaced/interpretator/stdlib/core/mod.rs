@@ -0,0 +1,9 @@
+pub mod builtins;
+pub mod colors;
+pub mod dates;
+pub mod ops;
+pub mod prelude;
+pub mod primitives;
+pub mod quantities;
+pub mod strings;
+pub mod temperatures;
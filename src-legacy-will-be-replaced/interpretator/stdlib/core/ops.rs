@@ -0,0 +1,478 @@
+use crate::interpretator::frontend::ast::expression::{BinaryOp, UnaryOp};
+use crate::interpretator::prototypes::types::formal::fraction::Rational;
+use crate::interpretator::prototypes::types::object::array::ArrayValue;
+use crate::interpretator::prototypes::types::object::date_time::DateTimeError;
+use crate::interpretator::prototypes::types::object::dictionary::DictionaryValue;
+use crate::interpretator::prototypes::types::object::object::ObjectRef;
+use crate::interpretator::prototypes::types::primitive::number::big_integer::BigInt;
+use crate::interpretator::prototypes::types::primitive::number::hexadecimal::Radix;
+use crate::interpretator::prototypes::types::primitive::operator::OperatorType;
+use crate::interpretator::prototypes::types::primitive::string::FormatError;
+use crate::interpretator::stdlib::core::prelude::Prelude;
+use crate::interpretator::stdlib::core::primitives::{
+    as_number, as_quantity, as_text, is_truthy, radix_of, soft_equals, strict_equals, type_name,
+    Number,
+};
+use crate::interpretator::stdlib::core::{dates, quantities, temperatures};
+use std::cmp::Ordering;
+use thiserror::Error;
+
+/// Предел итераций `while`/`loop` без счётчика и длины диапазона `a .. b`:
+/// документ данных не должен зависать или исчерпывать память.
+pub const MAX_ITERATIONS: usize = 1_000_000;
+
+/// Ошибка применения оператора к значениям. Место в исходнике добавляет вычислитель.
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum OpError {
+    #[error("оператор «{op}» не применим к {left} и {right}")]
+    InvalidOperands {
+        op: &'static str,
+        left: String,
+        right: String,
+    },
+    #[error("оператор «{op}» не применим к {operand}")]
+    InvalidOperand { op: &'static str, operand: String },
+    #[error("деление на ноль")]
+    DivisionByZero,
+    #[error("переполнение целого числа в «{op}»")]
+    Overflow { op: &'static str },
+    #[error("оператор «{op}» требует одинаковой размерности, а получены {left} и {right}")]
+    DimensionMismatch {
+        op: &'static str,
+        left: String,
+        right: String,
+    },
+    #[error("оператор «{op}» не применим к {left} и {right}: абсолютную температуру можно только сдвинуть на разность или вычесть из неё другую")]
+    AffineTemperature {
+        op: &'static str,
+        left: String,
+        right: String,
+    },
+    #[error("температура «{value}» ниже абсолютного нуля")]
+    BelowAbsoluteZero { value: String },
+    #[error("«{absolute}» нельзя выразить в относительной единице «{relative}»")]
+    RelativeUnit { relative: String, absolute: String },
+    #[error("нельзя преобразовать {from} в {to} «{unit}»")]
+    IncompatibleUnits {
+        from: String,
+        to: String,
+        unit: String,
+    },
+    #[error("«{function}» ожидает аргументы ({parameters})")]
+    Arguments {
+        function: String,
+        parameters: String,
+    },
+    #[error("{0}")]
+    DateTime(DateTimeError),
+    #[error("{value} нельзя записать точной дробью")]
+    Inexact { value: String },
+    #[error("некорректное регулярное выражение: {0}")]
+    InvalidPattern(String),
+    #[error("{0}")]
+    Format(FormatError),
+    #[error("диапазон {from} .. {to} длиннее {limit} элементов")]
+    RangeTooLong { from: i64, to: i64, limit: usize },
+}
+
+/// Бинарные операции над готовыми значениями.
+/// `&&`, `||` и `|>` здесь не обрабатываются: им нужен ленивый правый операнд.
+pub fn binary(
+    prelude: &Prelude,
+    op: BinaryOp,
+    left: &ObjectRef,
+    right: &ObjectRef,
+) -> Result<ObjectRef, OpError> {
+    let invalid = || OpError::InvalidOperands {
+        op: op.as_str(),
+        left: type_name(&left.borrow()),
+        right: type_name(&right.borrow()),
+    };
+    if let Some(result) = temperatures::binary(prelude, op, left, right) {
+        return result;
+    }
+    if let Some(result) = dates::binary(prelude, op, left, right) {
+        return result;
+    }
+    if let Some(result) = quantities::binary(prelude, op, left, right) {
+        return result;
+    }
+
+    match op {
+        BinaryOp::Equal => Ok(prelude.boolean_value(soft_equals(left, right))),
+        BinaryOp::NotEqual => Ok(prelude.boolean_value(!soft_equals(left, right))),
+        BinaryOp::StrictEqual | BinaryOp::Identical => {
+            Ok(prelude.boolean_value(strict_equals(left, right)))
+        }
+        BinaryOp::StrictNotEqual => Ok(prelude.boolean_value(!strict_equals(left, right))),
+        BinaryOp::Less | BinaryOp::Greater | BinaryOp::LessEq | BinaryOp::GreaterEq => {
+            let ordering = match compare(left, right) {
+                Some(ordering) => ordering,
+                // С `NaN` любое сравнение ложно, как в IEEE 754
+                None if is_nan(left) || is_nan(right) => return Ok(prelude.boolean_value(false)),
+                None => return Err(invalid()),
+            };
+            let result = match op {
+                BinaryOp::Less => ordering == Ordering::Less,
+                BinaryOp::Greater => ordering == Ordering::Greater,
+                BinaryOp::LessEq => ordering != Ordering::Greater,
+                _ => ordering != Ordering::Less,
+            };
+            Ok(prelude.boolean_value(result))
+        }
+        BinaryOp::Add => add(prelude, left, right).ok_or_else(invalid)?,
+        BinaryOp::Sub
+        | BinaryOp::Mul
+        | BinaryOp::Div
+        | BinaryOp::IDiv
+        | BinaryOp::Mod
+        | BinaryOp::Pow
+        | BinaryOp::Monus => {
+            let (a, b) = numbers(left, right).ok_or_else(invalid)?;
+            arithmetic(prelude, op, a, b, radix_of(&left.borrow()))
+        }
+        BinaryOp::Matches => {
+            let (text, pattern) = {
+                let (left, right) = (left.borrow(), right.borrow());
+                match (as_text(&left), as_text(&right)) {
+                    (Some(text), Some(pattern)) => (text.to_string(), pattern.to_string()),
+                    _ => return Err(invalid()),
+                }
+            };
+            let regex = regex::Regex::new(&pattern)
+                .map_err(|error| OpError::InvalidPattern(error.to_string()))?;
+            Ok(prelude.boolean_value(regex.is_match(&text)))
+        }
+        BinaryOp::In => contains(right, left)
+            .map(|found| prelude.boolean_value(found))
+            .ok_or_else(invalid),
+        BinaryOp::Range => {
+            let (from, to) = match numbers(left, right) {
+                Some((Number::Integer(from), Number::Integer(to))) => (from, to),
+                _ => return Err(invalid()),
+            };
+            if from.abs_diff(to) >= MAX_ITERATIONS as u64 {
+                return Err(OpError::RangeTooLong {
+                    from,
+                    to,
+                    limit: MAX_ITERATIONS,
+                });
+            }
+            // `0x4E3 .. -10` идёт вниз
+            let elements = match from <= to {
+                true => (from..=to).map(|i| prelude.integer_value(i)).collect(),
+                false => (to..=from)
+                    .rev()
+                    .map(|i| prelude.integer_value(i))
+                    .collect(),
+            };
+            Ok(prelude.array_value(elements))
+        }
+        _ => Err(invalid()),
+    }
+}
+
+/// Применяет оператор-значение к двум операндам: `(+)(10em, 5)` — то же, что `10em + 5`,
+/// со всеми правилами величин и температур.
+pub fn operator(
+    prelude: &Prelude,
+    op_type: &OperatorType,
+    left: &ObjectRef,
+    right: &ObjectRef,
+) -> Result<ObjectRef, OpError> {
+    let op = match op_type {
+        OperatorType::Add => BinaryOp::Add,
+        OperatorType::Sub => BinaryOp::Sub,
+        OperatorType::Mul => BinaryOp::Mul,
+        OperatorType::Div => BinaryOp::Div,
+        OperatorType::IDiv => BinaryOp::IDiv,
+        OperatorType::Mod => BinaryOp::Mod,
+        OperatorType::Pow => BinaryOp::Pow,
+        OperatorType::Less => BinaryOp::Less,
+        OperatorType::Greater => BinaryOp::Greater,
+        OperatorType::LessEq => BinaryOp::LessEq,
+        OperatorType::GreaterEq => BinaryOp::GreaterEq,
+        OperatorType::Equal => BinaryOp::Equal,
+    };
+    binary(prelude, op, left, right)
+}
+
+pub fn unary(prelude: &Prelude, op: UnaryOp, operand: &ObjectRef) -> Result<ObjectRef, OpError> {
+    let number = as_number(&operand.borrow());
+    let radix = radix_of(&operand.borrow());
+    let invalid = || OpError::InvalidOperand {
+        op: match op {
+            UnaryOp::Negate => "-",
+            UnaryOp::Plus => "+",
+            UnaryOp::Not => "!",
+            UnaryOp::Delta => "Δ",
+        },
+        operand: type_name(&operand.borrow()),
+    };
+    if let Some(result) = temperatures::unary(prelude, op, operand) {
+        return result;
+    }
+    if let Some(result) = dates::unary(prelude, op, operand) {
+        return Ok(result);
+    }
+    if let Some(result) = quantities::unary(prelude, op, operand) {
+        return Ok(result);
+    }
+    match (op, number) {
+        (UnaryOp::Not, _) => Ok(prelude.boolean_value(!is_truthy(&operand.borrow()))),
+        (UnaryOp::Plus, Some(_)) => Ok(operand.clone()),
+        (UnaryOp::Negate, Some(Number::Integer(value))) => Ok(match value.checked_neg() {
+            Some(value) => prelude.radix_integer_value(value, radix),
+            None => prelude.big_integer_value(-BigInt::from(value), radix),
+        }),
+        (UnaryOp::Negate, Some(Number::Big(value))) => Ok(prelude.big_integer_value(-value, radix)),
+        (UnaryOp::Negate, Some(Number::Fraction(value))) => Ok(prelude.fraction_value(-value)),
+        (UnaryOp::Negate, Some(Number::Float(value))) => Ok(prelude.float_value(-value)),
+        _ => Err(invalid()),
+    }
+}
+
+/// `value -> nfr`: целое или дробное как точная дробь. У `Float` это его точное
+/// двоичное значение: `0.75 -> nfr = 3/4fr`.
+pub fn fraction(prelude: &Prelude, value: &ObjectRef) -> Result<ObjectRef, OpError> {
+    let number = as_number(&value.borrow());
+    let exact = match &number {
+        Some(Number::Float(float)) => Rational::from_f64(*float),
+        Some(number) => number.as_rational(),
+        None => {
+            return Err(OpError::InvalidOperand {
+                op: BinaryOp::Convert.as_str(),
+                operand: type_name(&value.borrow()),
+            })
+        }
+    };
+    exact
+        .map(|exact| prelude.fraction_value(exact))
+        .ok_or_else(|| OpError::Inexact {
+            value: value.borrow().to_string(),
+        })
+}
+
+fn numbers(left: &ObjectRef, right: &ObjectRef) -> Option<(Number, Number)> {
+    Some((as_number(&left.borrow())?, as_number(&right.borrow())?))
+}
+
+fn compare(left: &ObjectRef, right: &ObjectRef) -> Option<Ordering> {
+    if let Some((a, b)) = numbers(left, right) {
+        return a.compare(&b);
+    }
+    if let Some(ordering) = quantities::compare(left, right) {
+        return Some(ordering);
+    }
+    if let Some(ordering) = temperatures::compare(left, right) {
+        return Some(ordering);
+    }
+    if let Some(ordering) = dates::compare(left, right) {
+        return Some(ordering);
+    }
+    let (left, right) = (left.borrow(), right.borrow());
+    Some(as_text(&left)?.cmp(as_text(&right)?))
+}
+
+/// `+`: числа складываются, строка склеивается с любым значением,
+/// к массиву добавляется элемент, словари объединяются (правый перекрывает левый).
+fn add(
+    prelude: &Prelude,
+    left: &ObjectRef,
+    right: &ObjectRef,
+) -> Option<Result<ObjectRef, OpError>> {
+    if let Some((a, b)) = numbers(left, right) {
+        let radix = radix_of(&left.borrow());
+        return Some(arithmetic(prelude, BinaryOp::Add, a, b, radix));
+    }
+    let (left_ref, right_ref) = (left.borrow(), right.borrow());
+    if let Some(array) = left_ref.downcast::<ArrayValue>() {
+        let mut elements = array.elements.clone();
+        elements.push(right.clone());
+        return Some(Ok(prelude.array_value(elements)));
+    }
+    if as_text(&left_ref).is_some() || as_text(&right_ref).is_some() {
+        return Some(Ok(
+            prelude.string_value(format!("{}{}", left_ref, right_ref))
+        ));
+    }
+    if let (Some(a), Some(b)) = (
+        left_ref.downcast::<DictionaryValue>(),
+        right_ref.downcast::<DictionaryValue>(),
+    ) {
+        let mut entries = a.entries.clone();
+        entries.extend(b.entries.iter().map(|(k, v)| (k.clone(), v.clone())));
+        return Some(Ok(prelude.dictionary_value(entries)));
+    }
+    None
+}
+
+/// Есть ли `item` в коллекции: элемент массива, ключ словаря или подстрока.
+fn contains(collection: &ObjectRef, item: &ObjectRef) -> Option<bool> {
+    let collection = collection.borrow();
+    if let Some(array) = collection.downcast::<ArrayValue>() {
+        return Some(
+            array
+                .elements
+                .iter()
+                .any(|element| soft_equals(element, item)),
+        );
+    }
+    let item = item.borrow();
+    if let Some(dictionary) = collection.downcast::<DictionaryValue>() {
+        return Some(dictionary.entries.contains_key(as_text(&item)?));
+    }
+    Some(as_text(&collection)?.contains(as_text(&item)?))
+}
+
+/// Арифметика над числами. Целые остаются целыми, а вышедший за `i64` результат
+/// становится `BigInteger`: `9223372036854775807 + 1 = 9223372036854775808`.
+/// Система счисления берётся у левого операнда: `0x1E + 1 = 0x1F`.
+fn arithmetic(
+    prelude: &Prelude,
+    op: BinaryOp,
+    a: Number,
+    b: Number,
+    radix: Radix,
+) -> Result<ObjectRef, OpError> {
+    if let (Number::Integer(a), Number::Integer(b)) = (&a, &b) {
+        let (a, b) = (*a, *b);
+        let result = match op {
+            BinaryOp::Add => a.checked_add(b),
+            BinaryOp::Sub => a.checked_sub(b),
+            BinaryOp::Mul => a.checked_mul(b),
+            BinaryOp::Monus => Some(a.saturating_sub(b).max(0)),
+            BinaryOp::Div | BinaryOp::IDiv | BinaryOp::Mod if b == 0 => {
+                return Err(OpError::DivisionByZero)
+            }
+            BinaryOp::IDiv => floor_div(a, b),
+            BinaryOp::Mod => floor_div(a, b).and_then(|q| a.checked_sub(q.checked_mul(b)?)),
+            BinaryOp::Pow if b >= 0 => u32::try_from(b).ok().and_then(|b| a.checked_pow(b)),
+            // Деление и отрицательная степень дают дробь
+            _ => return float_arithmetic(prelude, op, a as f64, b as f64),
+        };
+        if let Some(value) = result {
+            return Ok(prelude.radix_integer_value(value, radix));
+        }
+    }
+    if matches!(a, Number::Fraction(_)) || matches!(b, Number::Fraction(_)) {
+        if let (Some(a), Some(b)) = (a.as_rational(), b.as_rational()) {
+            return rational_arithmetic(prelude, op, a, b);
+        }
+    }
+    match (a.as_big(), b.as_big()) {
+        (Some(a), Some(b)) => big_arithmetic(prelude, op, a, b, radix),
+        _ => float_arithmetic(prelude, op, a.as_f64(), b.as_f64()),
+    }
+}
+
+/// Наибольшая длина результата `**` в битах; дальше степень считается переполнением.
+const MAX_POWER_BITS: u64 = 1 << 22;
+
+fn big_arithmetic(
+    prelude: &Prelude,
+    op: BinaryOp,
+    a: BigInt,
+    b: BigInt,
+    radix: Radix,
+) -> Result<ObjectRef, OpError> {
+    let overflow = OpError::Overflow { op: op.as_str() };
+    let result = match op {
+        BinaryOp::Add => &a + &b,
+        BinaryOp::Sub => &a - &b,
+        BinaryOp::Mul => &a * &b,
+        BinaryOp::Monus => (&a - &b).max(BigInt::zero()),
+        BinaryOp::Pow if !b.is_negative() => {
+            let exponent = b.to_i64().and_then(|b| u32::try_from(b).ok());
+            match exponent {
+                Some(exponent) if a.bit_length() <= 1 => a.pow(exponent),
+                Some(exponent) if a.bit_length() * exponent as u64 <= MAX_POWER_BITS => {
+                    a.pow(exponent)
+                }
+                _ => return Err(overflow),
+            }
+        }
+        BinaryOp::Div if b.is_zero() => return Err(OpError::DivisionByZero),
+        BinaryOp::IDiv | BinaryOp::Mod => {
+            let (quotient, remainder) = a.div_mod_floor(&b).ok_or(OpError::DivisionByZero)?;
+            match op {
+                BinaryOp::IDiv => quotient,
+                _ => remainder,
+            }
+        }
+        _ => return float_arithmetic(prelude, op, a.to_f64(), b.to_f64()),
+    };
+    Ok(prelude.big_integer_value(result, radix))
+}
+
+/// Арифметика с точной дробью: `1fr / 7 = 1/7fr`, `(2/3fr) ** 2 = 4/9fr`.
+/// `//` даёт целое, дробная степень — `Float`.
+fn rational_arithmetic(
+    prelude: &Prelude,
+    op: BinaryOp,
+    a: Rational,
+    b: Rational,
+) -> Result<ObjectRef, OpError> {
+    if matches!(op, BinaryOp::Div | BinaryOp::IDiv | BinaryOp::Mod) && b.is_zero() {
+        return Err(OpError::DivisionByZero);
+    }
+    let quotient = || a.checked_div(&b).expect("делитель не ноль");
+    let result = match op {
+        BinaryOp::Add => &a + &b,
+        BinaryOp::Sub => &a - &b,
+        BinaryOp::Mul => &a * &b,
+        BinaryOp::Monus => (&a - &b).max(Rational::integer(BigInt::zero())),
+        BinaryOp::Div => quotient(),
+        BinaryOp::IDiv => return Ok(prelude.big_integer_value(quotient().floor(), Radix::Decimal)),
+        BinaryOp::Mod => &a - &(&b * &Rational::integer(quotient().floor())),
+        BinaryOp::Pow if b.is_integer() => {
+            let overflow = OpError::Overflow { op: op.as_str() };
+            let exponent = b.numerator().to_i64().and_then(|b| i32::try_from(b).ok());
+            let bits = a.numerator().bit_length() + a.denominator().bit_length();
+            match exponent {
+                Some(exponent) if bits * exponent.unsigned_abs() as u64 <= MAX_POWER_BITS => {
+                    a.pow(exponent).ok_or(OpError::DivisionByZero)?
+                }
+                _ => return Err(overflow),
+            }
+        }
+        _ => return float_arithmetic(prelude, op, a.to_f64(), b.to_f64()),
+    };
+    Ok(prelude.fraction_value(result))
+}
+
+/// Арифметика `Float` по IEEE 754: `1.0 / 0.0 = Infinity`, `0.0 / 0.0 = NaN`.
+fn float_arithmetic(prelude: &Prelude, op: BinaryOp, a: f64, b: f64) -> Result<ObjectRef, OpError> {
+    let result = match op {
+        BinaryOp::Add => a + b,
+        BinaryOp::Sub => a - b,
+        BinaryOp::Mul => a * b,
+        BinaryOp::Div => a / b,
+        BinaryOp::IDiv => (a / b).floor(),
+        BinaryOp::Mod => a - b * (a / b).floor(),
+        BinaryOp::Pow => a.powf(b),
+        BinaryOp::Monus => (a - b).max(0.0),
+        _ => unreachable!("не арифметический оператор {:?}", op),
+    };
+    Ok(prelude.float_value(result))
+}
+
+/// `NaN` — сам по себе или как значение величины: `NaN`, `NaNkg`.
+fn is_nan(object: &ObjectRef) -> bool {
+    let object = object.borrow();
+    match (as_number(&object), as_quantity(&object)) {
+        (Some(Number::Float(value)), _) => value.is_nan(),
+        (_, Some(quantity)) => quantity.value.is_nan(),
+        _ => false,
+    }
+}
+
+/// Целочисленное деление с округлением вниз: `-7 // 2 = -4`.
+fn floor_div(a: i64, b: i64) -> Option<i64> {
+    let quotient = a.checked_div(b)?;
+    if a % b != 0 && ((a < 0) != (b < 0)) {
+        quotient.checked_sub(1)
+    } else {
+        Some(quotient)
+    }
+}
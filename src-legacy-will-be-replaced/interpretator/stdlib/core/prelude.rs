@@ -0,0 +1,303 @@
+use crate::interpretator::prototypes::environment::time::{Clock, SystemClock};
+use crate::interpretator::prototypes::types::formal::formal::{Quantity, Unit};
+use crate::interpretator::prototypes::types::formal::fraction::{Fraction, Rational};
+use crate::interpretator::prototypes::types::formal::length::UnitContext;
+use crate::interpretator::prototypes::types::formal::temperature::{Temperature, TemperatureValue};
+use crate::interpretator::prototypes::types::object::array::Array;
+use crate::interpretator::prototypes::types::object::date_time::{
+    DateTime, DateTimeValue, Duration, DurationValue,
+};
+use crate::interpretator::prototypes::types::object::dictionary::Dictionary;
+use crate::interpretator::prototypes::types::object::object::ObjectRef;
+use crate::interpretator::prototypes::types::object::routine::routine::{Routine, RoutineContent};
+use crate::interpretator::prototypes::types::primitive::color::{Color, ColorValue};
+use crate::interpretator::prototypes::types::primitive::number::big_integer::{BigInt, BigInteger};
+use crate::interpretator::prototypes::types::primitive::number::float::Float;
+use crate::interpretator::prototypes::types::primitive::number::hexadecimal::{
+    Radix, RadixInteger,
+};
+use crate::interpretator::prototypes::types::primitive::number::integer::Integer;
+use crate::interpretator::prototypes::types::primitive::operator::{Operator, OperatorType};
+use crate::interpretator::prototypes::types::primitive::{
+    auto, boolean, nil, none, string, undefined,
+};
+use crate::interpretator::prototypes::types::prototype::{Prototype, PrototypeRef};
+use indexmap::IndexMap;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+/// Встроенные прототипы, общие для всех документов, и реестр типов по имени.
+///
+/// Иерархия повторяет список типов из `concept.lacon`:
+/// `Prototype → Primitive → (Undefined, Auto, None, Nil, Boolean, String, Operator, Number → (Int → BigInteger), Float, Binary, Octal, Hex, Base32, Fraction, Quantity, Temperature → TemperatureDelta, Color)`
+/// и `Prototype → Object → (Array, Dictionary, MultilineString, DateTime, Duration, Routine → Function, Procedure)`.
+pub struct Prelude {
+    pub prototype: PrototypeRef,
+    pub primitive: PrototypeRef,
+    pub undefined: PrototypeRef,
+    pub auto: PrototypeRef,
+    pub none: PrototypeRef,
+    pub nil: PrototypeRef,
+    pub boolean: PrototypeRef,
+    pub string: PrototypeRef,
+    pub operator: PrototypeRef,
+    pub number: PrototypeRef,
+    pub integer: PrototypeRef,
+    pub float: PrototypeRef,
+    /// Целое, не поместившееся в `i64`; появляется при переполнении
+    pub big_integer: PrototypeRef,
+    /// Целые, записанные в другой системе счисления: `0b`, `0o`, `0x`, `0t`
+    pub binary: PrototypeRef,
+    pub octal: PrototypeRef,
+    pub hex: PrototypeRef,
+    pub base32: PrototypeRef,
+    /// Точная дробь `1/7fr`
+    pub fraction: PrototypeRef,
+    /// Величина с единицей измерения. Её вид (`Length`, `Energy`) определяется размерностью,
+    /// а не прототипом
+    pub quantity: PrototypeRef,
+    /// Абсолютная температура; разность температур — её наследник
+    pub temperature: PrototypeRef,
+    pub temperature_delta: PrototypeRef,
+    pub color: PrototypeRef,
+    pub object: PrototypeRef,
+    pub array: PrototypeRef,
+    pub dictionary: PrototypeRef,
+    pub multiline_string: PrototypeRef,
+    pub date_time: PrototypeRef,
+    pub duration: PrototypeRef,
+    pub routine: PrototypeRef,
+    pub function: PrototypeRef,
+    pub procedure: PrototypeRef,
+    /// Размеры шрифта хоста для относительных единиц `em` и `rem`
+    pub units: UnitContext,
+    /// Часы для `now()` и `today()`
+    pub clock: Rc<dyn Clock>,
+    /// Имя типа в нижнем регистре → прототип. Аннотации `<Int>` и `<int>` равнозначны.
+    types: HashMap<String, PrototypeRef>,
+}
+
+impl Prelude {
+    pub fn new() -> Self {
+        let prototype = Prototype::new("Prototype", None);
+        let primitive = Prototype::new("Primitive", Some(prototype.clone()));
+        let number = Prototype::new("Number", Some(primitive.clone()));
+        let object = Prototype::new("Object", Some(prototype.clone()));
+        let routine = Prototype::new("Routine", Some(object.clone()));
+        let integer = Prototype::new("Int", Some(number.clone()));
+        let temperature = Prototype::new("Temperature", Some(number.clone()));
+
+        let mut prelude = Prelude {
+            undefined: Prototype::new("Undefined", Some(primitive.clone())),
+            auto: Prototype::new("Auto", Some(primitive.clone())),
+            none: Prototype::new("None", Some(primitive.clone())),
+            nil: Prototype::new("Nil", Some(primitive.clone())),
+            boolean: Prototype::new("Boolean", Some(primitive.clone())),
+            string: Prototype::new("String", Some(primitive.clone())),
+            operator: Prototype::new("Operator", Some(primitive.clone())),
+            big_integer: Prototype::new("BigInteger", Some(integer.clone())),
+            integer,
+            float: Prototype::new("Float", Some(number.clone())),
+            binary: Prototype::new("Binary", Some(number.clone())),
+            octal: Prototype::new("Octal", Some(number.clone())),
+            hex: Prototype::new("Hex", Some(number.clone())),
+            base32: Prototype::new("Base32", Some(number.clone())),
+            fraction: Prototype::new("Fraction", Some(number.clone())),
+            quantity: Prototype::new("Quantity", Some(number.clone())),
+            temperature_delta: Prototype::new("TemperatureDelta", Some(temperature.clone())),
+            temperature,
+            color: Prototype::new("Color", Some(primitive.clone())),
+            array: Prototype::new("Array", Some(object.clone())),
+            dictionary: Prototype::new("Dictionary", Some(object.clone())),
+            multiline_string: Prototype::new("MultilineString", Some(object.clone())),
+            date_time: Prototype::new("DateTime", Some(object.clone())),
+            duration: Prototype::new("Duration", Some(object.clone())),
+            function: Prototype::new("Function", Some(routine.clone())),
+            procedure: Prototype::new("Procedure", Some(routine.clone())),
+            prototype,
+            primitive,
+            number,
+            object,
+            routine,
+            units: UnitContext::default(),
+            clock: Rc::new(SystemClock),
+            types: HashMap::new(),
+        };
+
+        let builtins = [
+            prelude.prototype.clone(),
+            prelude.primitive.clone(),
+            prelude.undefined.clone(),
+            prelude.auto.clone(),
+            prelude.none.clone(),
+            prelude.nil.clone(),
+            prelude.boolean.clone(),
+            prelude.string.clone(),
+            prelude.operator.clone(),
+            prelude.number.clone(),
+            prelude.integer.clone(),
+            prelude.big_integer.clone(),
+            prelude.float.clone(),
+            prelude.binary.clone(),
+            prelude.octal.clone(),
+            prelude.hex.clone(),
+            prelude.base32.clone(),
+            prelude.fraction.clone(),
+            prelude.quantity.clone(),
+            prelude.temperature.clone(),
+            prelude.temperature_delta.clone(),
+            prelude.color.clone(),
+            prelude.object.clone(),
+            prelude.array.clone(),
+            prelude.dictionary.clone(),
+            prelude.multiline_string.clone(),
+            prelude.date_time.clone(),
+            prelude.duration.clone(),
+            prelude.routine.clone(),
+            prelude.function.clone(),
+            prelude.procedure.clone(),
+        ];
+        for proto in builtins {
+            prelude.register(proto);
+        }
+        prelude.register_alias("Integer", &prelude.integer.clone());
+        prelude.register_alias("Hexadecimal", &prelude.hex.clone());
+        prelude.register_alias("Bool", &prelude.boolean.clone());
+        prelude.register_alias("Unit", &prelude.quantity.clone());
+        prelude
+    }
+
+    /// Делает прототип доступным в аннотациях типов под его именем.
+    pub fn register(&mut self, proto: PrototypeRef) {
+        let name = proto.borrow().name.to_lowercase();
+        self.types.insert(name, proto);
+    }
+
+    pub fn register_alias(&mut self, alias: &str, proto: &PrototypeRef) {
+        self.types.insert(alias.to_lowercase(), Rc::clone(proto));
+    }
+
+    /// Прототип по имени типа без учёта регистра.
+    pub fn lookup_type(&self, name: &str) -> Option<PrototypeRef> {
+        self.types.get(&name.to_lowercase()).cloned()
+    }
+
+    // --- Конструкторы значений ---
+
+    pub fn undefined_value(&self) -> ObjectRef {
+        undefined::Undefined::new_instance(self.undefined.clone())
+    }
+
+    pub fn auto_value(&self) -> ObjectRef {
+        auto::Auto::new_instance(self.auto.clone())
+    }
+
+    pub fn none_value(&self) -> ObjectRef {
+        none::None::new_instance(self.none.clone())
+    }
+
+    pub fn nil_value(&self) -> ObjectRef {
+        nil::Nil::new_instance(self.nil.clone())
+    }
+
+    pub fn boolean_value(&self, value: bool) -> ObjectRef {
+        boolean::Boolean::new_instance(self.boolean.clone(), value)
+    }
+
+    pub fn string_value(&self, value: impl Into<String>) -> ObjectRef {
+        string::String::new_instance(self.string.clone(), value.into())
+    }
+
+    pub fn operator_value(&self, op_type: OperatorType) -> ObjectRef {
+        Operator::new_instance(self.operator.clone(), op_type)
+    }
+
+    pub fn integer_value(&self, value: i64) -> ObjectRef {
+        Integer::new_instance(self.integer.clone(), value)
+    }
+
+    /// Целое в системе счисления литерала. Десятичные и `0c` остаются `Int`,
+    /// у остальных систем свой тип: `0x1E` — это `Hex`.
+    pub fn radix_integer_value(&self, value: i64, radix: Radix) -> ObjectRef {
+        let proto = match radix {
+            Radix::Decimal => return self.integer_value(value),
+            Radix::Binary => self.binary.clone(),
+            Radix::Octal => self.octal.clone(),
+            Radix::Hex => self.hex.clone(),
+            Radix::Base32 | Radix::Crockford => self.base32.clone(),
+        };
+        RadixInteger::new_instance(proto, value, radix)
+    }
+
+    /// Целое произвольной длины; если оно помещается в `i64`, получается обычное целое.
+    pub fn big_integer_value(&self, value: BigInt, radix: Radix) -> ObjectRef {
+        match value.to_i64() {
+            Some(value) => self.radix_integer_value(value, radix),
+            None => BigInteger::new_instance(self.big_integer.clone(), value, radix),
+        }
+    }
+
+    pub fn float_value(&self, value: f64) -> ObjectRef {
+        Float::new_instance(self.float.clone(), value)
+    }
+
+    pub fn fraction_value(&self, value: Rational) -> ObjectRef {
+        Fraction::new_instance(self.fraction.clone(), value)
+    }
+
+    pub fn quantity_value(&self, value: f64, unit: Unit) -> ObjectRef {
+        Quantity::new_instance(self.quantity.clone(), value, unit)
+    }
+
+    pub fn temperature_value(&self, value: TemperatureValue) -> ObjectRef {
+        let proto = match value.delta {
+            true => self.temperature_delta.clone(),
+            false => self.temperature.clone(),
+        };
+        Temperature::new_instance(proto, value)
+    }
+
+    pub fn color_value(&self, value: ColorValue) -> ObjectRef {
+        Color::new_instance(self.color.clone(), value)
+    }
+
+    pub fn date_time_value(&self, value: DateTimeValue) -> ObjectRef {
+        DateTime::new_instance(self.date_time.clone(), value)
+    }
+
+    pub fn duration_value(&self, value: DurationValue) -> ObjectRef {
+        Duration::new_instance(self.duration.clone(), value)
+    }
+
+    /// Встроенная функция или метод с привязанным получателем.
+    pub fn builtin_value(
+        &self,
+        name: &str,
+        parameters: &[&str],
+        receiver: Option<ObjectRef>,
+    ) -> ObjectRef {
+        Routine::new_instance(
+            self.function.clone(),
+            name.to_string(),
+            parameters
+                .iter()
+                .map(|parameter| parameter.to_string())
+                .collect(),
+            RoutineContent::Builtin { receiver },
+        )
+    }
+
+    pub fn array_value(&self, elements: Vec<ObjectRef>) -> ObjectRef {
+        Array::new_instance(self.array.clone(), elements)
+    }
+
+    pub fn dictionary_value(&self, entries: IndexMap<String, ObjectRef>) -> ObjectRef {
+        Dictionary::new_instance(self.dictionary.clone(), entries)
+    }
+}
+
+impl Default for Prelude {
+    fn default() -> Self {
+        Prelude::new()
+    }
+}
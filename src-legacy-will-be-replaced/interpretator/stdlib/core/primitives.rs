@@ -0,0 +1,327 @@
+use crate::interpretator::prototypes::types::formal::formal::{Dimension, QuantityValue};
+use crate::interpretator::prototypes::types::formal::fraction::{FractionValue, Rational};
+use crate::interpretator::prototypes::types::formal::temperature::TemperatureValue;
+use crate::interpretator::prototypes::types::object::array::ArrayValue;
+use crate::interpretator::prototypes::types::object::date_time::{DateTimeValue, DurationValue};
+use crate::interpretator::prototypes::types::object::dictionary::DictionaryValue;
+use crate::interpretator::prototypes::types::object::multiline_string::MultilineStringValue;
+use crate::interpretator::prototypes::types::object::object::{IntegerValue, Object, ObjectRef};
+use crate::interpretator::prototypes::types::primitive::auto::AutoValue;
+use crate::interpretator::prototypes::types::primitive::boolean::BooleanValue;
+use crate::interpretator::prototypes::types::primitive::color::ColorValue;
+use crate::interpretator::prototypes::types::primitive::nil::NilValue;
+use crate::interpretator::prototypes::types::primitive::none::NoneValue;
+use crate::interpretator::prototypes::types::primitive::number::big_integer::{
+    BigInt, BigIntegerValue,
+};
+use crate::interpretator::prototypes::types::primitive::number::float::FloatValue;
+use crate::interpretator::prototypes::types::primitive::number::hexadecimal::{Radix, RadixValue};
+use crate::interpretator::prototypes::types::primitive::operator::OperatorValue;
+use crate::interpretator::prototypes::types::primitive::string::StringValue;
+use crate::interpretator::prototypes::types::primitive::undefined::UndefinedValue;
+use crate::interpretator::prototypes::types::prototype::PrototypeRef;
+use std::cmp::Ordering;
+use std::rc::Rc;
+
+/// Число, извлечённое из объекта, для арифметики и сравнений.
+/// Система счисления литерала здесь не важна: `0x1E` — это `Integer(30)`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Number {
+    Integer(i64),
+    Big(BigInt),
+    Fraction(Rational),
+    Float(f64),
+}
+
+impl Number {
+    pub fn as_f64(self) -> f64 {
+        match self {
+            Number::Integer(value) => value as f64,
+            Number::Big(value) => value.to_f64(),
+            Number::Fraction(value) => value.to_f64(),
+            Number::Float(value) => value,
+        }
+    }
+
+    /// Целое значение без потери точности; у дробного его нет.
+    pub fn as_big(&self) -> Option<BigInt> {
+        match self {
+            Number::Integer(value) => Some(BigInt::from(*value)),
+            Number::Big(value) => Some(value.clone()),
+            Number::Fraction(_) | Number::Float(_) => None,
+        }
+    }
+
+    /// Точное значение дробью; у `Float` его нет, даже если оно целое.
+    pub fn as_rational(&self) -> Option<Rational> {
+        match self {
+            Number::Fraction(value) => Some(value.clone()),
+            _ => self.as_big().map(Rational::integer),
+        }
+    }
+
+    /// Сравнение по величине: целые и дроби сравниваются точно, с `Float` — как `f64`.
+    /// С `NaN` результата нет.
+    pub fn compare(&self, other: &Number) -> Option<Ordering> {
+        if let (Number::Integer(a), Number::Integer(b)) = (self, other) {
+            return Some(a.cmp(b));
+        }
+        match (self.as_rational(), other.as_rational()) {
+            (Some(a), Some(b)) => Some(a.cmp(&b)),
+            _ => self.clone().as_f64().partial_cmp(&other.clone().as_f64()),
+        }
+    }
+}
+
+pub fn as_number(object: &Object) -> Option<Number> {
+    if let Some(IntegerValue(value)) = object.downcast::<IntegerValue>() {
+        return Some(Number::Integer(*value));
+    }
+    if let Some(RadixValue { value, .. }) = object.downcast::<RadixValue>() {
+        return Some(Number::Integer(*value));
+    }
+    if let Some(BigIntegerValue { value, .. }) = object.downcast::<BigIntegerValue>() {
+        return Some(Number::Big(value.clone()));
+    }
+    if let Some(FractionValue(value)) = object.downcast::<FractionValue>() {
+        return Some(Number::Fraction(value.clone()));
+    }
+    object
+        .downcast::<FloatValue>()
+        .map(|FloatValue(value)| Number::Float(*value))
+}
+
+/// Система счисления, в которой записано целое; у остальных значений — десятичная.
+pub fn radix_of(object: &Object) -> Radix {
+    if let Some(value) = object.downcast::<RadixValue>() {
+        return value.radix;
+    }
+    object
+        .downcast::<BigIntegerValue>()
+        .map_or(Radix::Decimal, |value| value.radix)
+}
+
+pub fn as_quantity(object: &Object) -> Option<&QuantityValue> {
+    object.downcast::<QuantityValue>()
+}
+
+/// Промежуток времени: `Duration` или величина размерности времени (`10s`, `25μs`).
+pub fn as_duration(object: &Object) -> Option<DurationValue> {
+    if let Some(duration) = object.downcast::<DurationValue>() {
+        return Some(*duration);
+    }
+    let quantity =
+        as_quantity(object).filter(|quantity| quantity.dimension() == Dimension::TIME)?;
+    DurationValue::from_seconds(quantity.si_value())
+}
+
+pub fn as_temperature(object: &Object) -> Option<&TemperatureValue> {
+    object.downcast::<TemperatureValue>()
+}
+
+/// Текст строки или многострочной строки.
+pub fn as_text(object: &Object) -> Option<&str> {
+    if let Some(StringValue(text)) = object.downcast::<StringValue>() {
+        return Some(text);
+    }
+    object
+        .downcast::<MultilineStringValue>()
+        .map(|value| value.content.as_str())
+}
+
+/// `nil`, `none`, `undefined` и `auto` — «пустые» значения, которые допускает тип с `?`.
+pub fn is_empty_value(object: &Object) -> bool {
+    object.downcast::<NilValue>().is_some()
+        || object.downcast::<NoneValue>().is_some()
+        || object.downcast::<UndefinedValue>().is_some()
+        || object.downcast::<AutoValue>().is_some()
+}
+
+/// Истинность значения в условиях: ложны `false`, пустые значения, ноль, `NaN`,
+/// пустая строка и пустые коллекции.
+pub fn is_truthy(object: &Object) -> bool {
+    if let Some(BooleanValue(value)) = object.downcast::<BooleanValue>() {
+        return *value;
+    }
+    if is_empty_value(object) {
+        return false;
+    }
+    if let Some(number) = as_number(object) {
+        let value = number.as_f64();
+        return value != 0.0 && !value.is_nan();
+    }
+    if let Some(quantity) = as_quantity(object) {
+        return quantity.value != 0.0;
+    }
+    if let Some(temperature) = as_temperature(object) {
+        return temperature.value != 0.0;
+    }
+    if let Some(text) = as_text(object) {
+        return !text.is_empty();
+    }
+    if let Some(array) = object.downcast::<ArrayValue>() {
+        return !array.elements.is_empty();
+    }
+    if let Some(dictionary) = object.downcast::<DictionaryValue>() {
+        return !dictionary.entries.is_empty();
+    }
+    true
+}
+
+/// Имя типа значения — имя его прототипа, а для величины — её вид: `Energy`, `Velocity`.
+pub fn type_name(object: &Object) -> String {
+    if let Some(quantity) = as_quantity(object) {
+        return quantity.dimension().describe();
+    }
+    object.prototype.borrow().name.clone()
+}
+
+/// Наследует ли значение указанный прототип (напрямую или через цепочку).
+pub fn is_instance_of(object: &Object, proto: &PrototypeRef) -> bool {
+    let mut current = Some(Rc::clone(&object.prototype));
+    while let Some(candidate) = current {
+        if Rc::ptr_eq(&candidate, proto) {
+            return true;
+        }
+        current = candidate.borrow().parent.clone();
+    }
+    false
+}
+
+/// Мягкое сравнение `==`: числа сравниваются по величине, величины — в единицах СИ
+/// (`25m3 == 25000L`), температуры — с учётом шкалы (`25°C == 77°F`), строки — без учёта регистра, `true`/`false` равны `1`/`0`.
+pub fn soft_equals(left: &ObjectRef, right: &ObjectRef) -> bool {
+    if Rc::ptr_eq(left, right) {
+        return true;
+    }
+    let (left, right) = (left.borrow(), right.borrow());
+    let as_soft_number = |object: &Object| {
+        as_number(object).or_else(|| {
+            object
+                .downcast::<BooleanValue>()
+                .map(|BooleanValue(value)| Number::Integer(*value as i64))
+        })
+    };
+    if let (Some(a), Some(b)) = (as_soft_number(&left), as_soft_number(&right)) {
+        return a.compare(&b) == Some(Ordering::Equal);
+    }
+    if let (Some(a), Some(b)) = (as_quantity(&left), as_quantity(&right)) {
+        return same_quantity(a, b);
+    }
+    if let (Some(a), Some(b)) = (as_temperature(&left), as_temperature(&right)) {
+        return a.delta == b.delta && a.value_in(b.scale) == b.value;
+    }
+    if let (Some(a), Some(b)) = (as_duration(&left), as_duration(&right)) {
+        return a == b;
+    }
+    if let (Some(a), Some(b)) = (as_text(&left), as_text(&right)) {
+        return a.to_lowercase() == b.to_lowercase();
+    }
+    if let (Some(a), Some(b)) = (
+        left.downcast::<ArrayValue>(),
+        right.downcast::<ArrayValue>(),
+    ) {
+        return a.elements.len() == b.elements.len()
+            && a.elements
+                .iter()
+                .zip(&b.elements)
+                .all(|(a, b)| soft_equals(a, b));
+    }
+    if let (Some(a), Some(b)) = (
+        left.downcast::<DictionaryValue>(),
+        right.downcast::<DictionaryValue>(),
+    ) {
+        return a.entries.len() == b.entries.len()
+            && a.entries.iter().all(|(key, value)| {
+                b.entries
+                    .get(key)
+                    .is_some_and(|other| soft_equals(value, other))
+            });
+    }
+    same_scalar(&left, &right)
+}
+
+/// Строгое сравнение `===`: совпадают и тип, и значение.
+pub fn strict_equals(left: &ObjectRef, right: &ObjectRef) -> bool {
+    if Rc::ptr_eq(left, right) {
+        return true;
+    }
+    let (left, right) = (left.borrow(), right.borrow());
+    if !Rc::ptr_eq(&left.prototype, &right.prototype) {
+        return false;
+    }
+    if let (Some(a), Some(b)) = (as_number(&left), as_number(&right)) {
+        return a == b;
+    }
+    if let (Some(a), Some(b)) = (as_quantity(&left), as_quantity(&right)) {
+        return same_quantity(a, b);
+    }
+    if let (Some(a), Some(b)) = (as_temperature(&left), as_temperature(&right)) {
+        return a.delta == b.delta && a.value_in(b.scale) == b.value;
+    }
+    if let (Some(a), Some(b)) = (as_text(&left), as_text(&right)) {
+        return a == b;
+    }
+    if let (Some(a), Some(b)) = (
+        left.downcast::<ArrayValue>(),
+        right.downcast::<ArrayValue>(),
+    ) {
+        return a.elements.len() == b.elements.len()
+            && a.elements
+                .iter()
+                .zip(&b.elements)
+                .all(|(a, b)| strict_equals(a, b));
+    }
+    if let (Some(a), Some(b)) = (
+        left.downcast::<DictionaryValue>(),
+        right.downcast::<DictionaryValue>(),
+    ) {
+        return a.entries.len() == b.entries.len()
+            && a.entries.iter().all(|(key, value)| {
+                b.entries
+                    .get(key)
+                    .is_some_and(|other| strict_equals(value, other))
+            });
+    }
+    same_scalar(&left, &right)
+}
+
+/// Величины равны, если у них одна размерность и одинаковое значение в СИ.
+fn same_quantity(left: &QuantityValue, right: &QuantityValue) -> bool {
+    left.dimension() == right.dimension() && left.si_value() == right.si_value()
+}
+
+/// Логические значения, пустые значения и операторы равны, если совпадают вид и содержимое.
+fn same_scalar(left: &Object, right: &Object) -> bool {
+    if let (Some(a), Some(b)) = (
+        left.downcast::<BooleanValue>(),
+        right.downcast::<BooleanValue>(),
+    ) {
+        return a.0 == b.0;
+    }
+    if let (Some(a), Some(b)) = (
+        left.downcast::<OperatorValue>(),
+        right.downcast::<OperatorValue>(),
+    ) {
+        return a.op_type == b.op_type;
+    }
+    if let (Some(a), Some(b)) = (
+        left.downcast::<ColorValue>(),
+        right.downcast::<ColorValue>(),
+    ) {
+        return a.same_color(b);
+    }
+    if let (Some(a), Some(b)) = (
+        left.downcast::<DateTimeValue>(),
+        right.downcast::<DateTimeValue>(),
+    ) {
+        return a.timestamp_nanos() == b.timestamp_nanos();
+    }
+    if let (Some(a), Some(b)) = (
+        left.downcast::<DurationValue>(),
+        right.downcast::<DurationValue>(),
+    ) {
+        return a == b;
+    }
+    is_empty_value(left) && is_empty_value(right) && Rc::ptr_eq(&left.prototype, &right.prototype)
+}
@@ -0,0 +1,242 @@
+use crate::interpretator::frontend::ast::expression::{BinaryOp, UnaryOp};
+use crate::interpretator::prototypes::types::formal::formal::{QuantityValue, Unit};
+use crate::interpretator::prototypes::types::object::object::ObjectRef;
+use crate::interpretator::stdlib::core::ops::OpError;
+use crate::interpretator::stdlib::core::prelude::Prelude;
+use crate::interpretator::stdlib::core::primitives::{
+    as_duration, as_number, as_quantity, type_name,
+};
+use std::cmp::Ordering;
+
+/// Операнд арифметики над величинами: величина или простое число.
+/// `Duration` здесь — величина в секундах, так что её можно перевести: `-> nmin`.
+enum Operand {
+    Quantity(QuantityValue),
+    Number(f64),
+}
+
+fn operand(object: &ObjectRef) -> Option<Operand> {
+    let object = object.borrow();
+    if let Some(quantity) = as_quantity(&object) {
+        return Some(Operand::Quantity(quantity.clone()));
+    }
+    if let Some(duration) = as_duration(&object) {
+        let second = Unit::parse("s").expect("секунда — известная единица");
+        return Some(Operand::Quantity(QuantityValue::new(
+            duration.seconds(),
+            second,
+        )));
+    }
+    as_number(&object).map(|number| Operand::Number(number.as_f64()))
+}
+
+/// Бинарная операция, в которой участвует хотя бы одна величина.
+/// `None` — операнды не величины или оператор не арифметический,
+/// и он обрабатывается как обычно.
+///
+/// Тип результата берётся у левого операнда:
+/// - сложение, вычитание и остаток требуют одной размерности, правый операнд переводится
+///   в единицу левого: `25m3 + 3000L = 28m3`;
+/// - число без единицы принимает единицу левого операнда (`10em + 5 = 15em`),
+///   а число слева остаётся числом;
+/// - доля справа отсчитывается от левого операнда: `5 - 50% = 2.5`, `10m * 50% = 5m`;
+/// - относительную единицу нельзя вычислить из абсолютной: `16pt + 10em` можно,
+///   а `10em + 16pt` — ошибка.
+///
+/// Умножение и деление перемножают единицы: `10kW * 2s = 20kW⋅s`,
+/// а величина, у которой единицы сократились, становится числом.
+pub fn binary(
+    prelude: &Prelude,
+    op: BinaryOp,
+    left: &ObjectRef,
+    right: &ObjectRef,
+) -> Option<Result<ObjectRef, OpError>> {
+    let arithmetic = matches!(
+        op,
+        BinaryOp::Add
+            | BinaryOp::Sub
+            | BinaryOp::Mul
+            | BinaryOp::Div
+            | BinaryOp::Mod
+            | BinaryOp::Pow
+            | BinaryOp::Monus
+    );
+    if !arithmetic {
+        return None;
+    }
+    let (left, right) = (operand(left)?, operand(right)?);
+    if let (Operand::Number(_), Operand::Number(_)) = (&left, &right) {
+        return None;
+    }
+    let mismatch = |left: &Operand, right: &Operand| OpError::DimensionMismatch {
+        op: op.as_str(),
+        left: describe(left),
+        right: describe(right),
+    };
+    let additive_op = matches!(
+        op,
+        BinaryOp::Add | BinaryOp::Sub | BinaryOp::Mod | BinaryOp::Monus
+    );
+
+    let result = match (op, &left, &right) {
+        (BinaryOp::Add | BinaryOp::Sub | BinaryOp::Monus, _, Operand::Quantity(b))
+            if b.unit.is_fraction() && !is_fraction(&left) =>
+        {
+            let fraction = b.si_value();
+            let scale = match op {
+                BinaryOp::Add => 1.0 + fraction,
+                BinaryOp::Sub => 1.0 - fraction,
+                _ => (1.0 - fraction).max(0.0),
+            };
+            Ok(scaled(prelude, &left, scale))
+        }
+        (_, Operand::Quantity(a), Operand::Quantity(b))
+            if additive_op && a.unit.is_relative() && !b.unit.is_relative() =>
+        {
+            Err(OpError::RelativeUnit {
+                relative: a.unit.to_string(),
+                absolute: b.unit.to_string(),
+            })
+        }
+        (_, Operand::Quantity(a), Operand::Quantity(b)) if additive_op => {
+            let b = b.value_in(&a.unit).ok_or_else(|| mismatch(&left, &right));
+            b.and_then(|b| additive(op, a.value, b))
+                .map(|value| prelude.quantity_value(value, a.unit.clone()))
+        }
+        (_, Operand::Quantity(a), Operand::Number(b)) if additive_op => {
+            additive(op, a.value, *b).map(|value| prelude.quantity_value(value, a.unit.clone()))
+        }
+        (_, Operand::Number(a), Operand::Quantity(b)) if additive_op => {
+            additive(op, *a, b.value).map(|value| prelude.float_value(value))
+        }
+        // Умножение на долю масштабирует другой операнд и сохраняет его тип
+        (BinaryOp::Mul, _, Operand::Quantity(b)) if b.unit.is_fraction() => {
+            Ok(scaled(prelude, &left, b.si_value()))
+        }
+        (BinaryOp::Mul, Operand::Quantity(a), Operand::Quantity(_)) if a.unit.is_fraction() => {
+            Ok(scaled(prelude, &right, a.si_value()))
+        }
+        (BinaryOp::Div, _, Operand::Quantity(b)) if b.unit.is_fraction() => {
+            Ok(scaled(prelude, &left, 1.0 / b.si_value()))
+        }
+        (BinaryOp::Mul, Operand::Quantity(a), Operand::Quantity(b)) => {
+            Ok(product(prelude, a.value * b.value, a.unit.mul(&b.unit)))
+        }
+        (BinaryOp::Mul, Operand::Quantity(a), Operand::Number(b))
+        | (BinaryOp::Mul, Operand::Number(b), Operand::Quantity(a)) => {
+            Ok(prelude.quantity_value(a.value * b, a.unit.clone()))
+        }
+        (BinaryOp::Div, Operand::Quantity(a), Operand::Quantity(b)) => {
+            Ok(product(prelude, a.value / b.value, a.unit.div(&b.unit)))
+        }
+        (BinaryOp::Div, Operand::Quantity(a), Operand::Number(b)) => {
+            Ok(prelude.quantity_value(a.value / b, a.unit.clone()))
+        }
+        (BinaryOp::Div, Operand::Number(a), Operand::Quantity(b)) => {
+            Ok(prelude.quantity_value(a / b.value, b.unit.powi(-1)))
+        }
+        // Степень единицы должна остаться целой: `(2m)^3 = 8m3`
+        (BinaryOp::Pow, Operand::Quantity(a), Operand::Number(power))
+            if power.fract() == 0.0 && power.abs() <= i32::MAX as f64 =>
+        {
+            let power = *power as i32;
+            Ok(product(prelude, a.value.powi(power), a.unit.powi(power)))
+        }
+        _ => Err(OpError::InvalidOperands {
+            op: op.as_str(),
+            left: describe(&left),
+            right: describe(&right),
+        }),
+    };
+    Some(result)
+}
+
+/// `value -> unit`: величина переводится в единицу той же размерности,
+/// а число без единицы просто получает её: `25m3 -> nL = 25000L`, `5 -> nkm = 5km`.
+/// Перевести абсолютную длину в `em` нельзя, обратно — можно.
+pub fn convert(prelude: &Prelude, value: &ObjectRef, unit: &Unit) -> Result<ObjectRef, OpError> {
+    match operand(value) {
+        Some(Operand::Quantity(quantity)) if unit.is_relative() && !quantity.unit.is_relative() => {
+            Err(OpError::RelativeUnit {
+                relative: unit.to_string(),
+                absolute: quantity.unit.to_string(),
+            })
+        }
+        Some(Operand::Quantity(quantity)) => match quantity.value_in(unit) {
+            Some(converted) => Ok(prelude.quantity_value(converted, unit.clone())),
+            None => Err(OpError::IncompatibleUnits {
+                from: quantity.dimension().describe(),
+                to: unit.dimension().describe(),
+                unit: unit.to_string(),
+            }),
+        },
+        Some(Operand::Number(number)) => Ok(prelude.quantity_value(number, unit.clone())),
+        None => Err(OpError::InvalidOperand {
+            op: BinaryOp::Convert.as_str(),
+            operand: type_name(&value.borrow()),
+        }),
+    }
+}
+
+/// Сравнение величин одной размерности в единицах СИ.
+/// `None`, если хотя бы один операнд не величина или размерности различаются.
+pub fn compare(left: &ObjectRef, right: &ObjectRef) -> Option<Ordering> {
+    let (left, right) = (left.borrow(), right.borrow());
+    let (a, b) = (as_quantity(&left)?, as_quantity(&right)?);
+    if a.dimension() != b.dimension() {
+        return None;
+    }
+    a.si_value().partial_cmp(&b.si_value())
+}
+
+/// Унарные `-` и `+` сохраняют единицу.
+pub fn unary(prelude: &Prelude, op: UnaryOp, operand: &ObjectRef) -> Option<ObjectRef> {
+    let quantity = as_quantity(&operand.borrow())?.clone();
+    match op {
+        UnaryOp::Negate => Some(prelude.quantity_value(-quantity.value, quantity.unit)),
+        UnaryOp::Plus => Some(operand.clone()),
+        _ => None,
+    }
+}
+
+fn additive(op: BinaryOp, a: f64, b: f64) -> Result<f64, OpError> {
+    Ok(match op {
+        BinaryOp::Add => a + b,
+        BinaryOp::Sub => a - b,
+        BinaryOp::Monus => (a - b).max(0.0),
+        _ => a - b * (a / b).floor(),
+    })
+}
+
+/// Результат умножения или деления. Символы одной размерности в числителе и знаменателе
+/// сокращаются: `25MiByte/s * 2min = 3000MiByte`. Если сократилось всё (`10m / 2m`, `1km / 1m`),
+/// получается число. Доли вроде `%` сохраняются: `50% * 2 = 100%`.
+fn product(prelude: &Prelude, value: f64, unit: Unit) -> ObjectRef {
+    let (factor, unit) = unit.simplify();
+    let value = factor.apply(value);
+    if unit.dimension().is_dimensionless() && !unit.has_dimensionless_terms() {
+        return prelude.float_value(unit.factor().apply(value));
+    }
+    prelude.quantity_value(value, unit)
+}
+
+fn is_fraction(operand: &Operand) -> bool {
+    matches!(operand, Operand::Quantity(quantity) if quantity.unit.is_fraction())
+}
+
+/// Операнд, умноженный на коэффициент, того же типа.
+fn scaled(prelude: &Prelude, operand: &Operand, scale: f64) -> ObjectRef {
+    match operand {
+        Operand::Quantity(quantity) => {
+            prelude.quantity_value(quantity.value * scale, quantity.unit.clone())
+        }
+        Operand::Number(number) => prelude.float_value(number * scale),
+    }
+}
+
+fn describe(operand: &Operand) -> String {
+    match operand {
+        Operand::Quantity(quantity) => quantity.dimension().describe(),
+        Operand::Number(_) => "Number".to_string(),
+    }
+}
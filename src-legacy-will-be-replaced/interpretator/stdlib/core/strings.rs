@@ -0,0 +1,51 @@
+use crate::interpretator::prototypes::types::object::object::{Object, ObjectRef};
+use crate::interpretator::prototypes::types::primitive::string::{format_template, FormatArg};
+use crate::interpretator::stdlib::core::ops::OpError;
+use crate::interpretator::stdlib::core::prelude::Prelude;
+use crate::interpretator::stdlib::core::primitives::{as_number, as_text, Number};
+
+/// Встроенные функции строк и их параметры; у `format` аргументов сколько угодно.
+const FUNCTIONS: [(&str, &[&str]); 1] = [("format", &["template", "...values"])];
+
+pub fn function_parameters(name: &str) -> Option<&'static [&'static str]> {
+    FUNCTIONS
+        .iter()
+        .find(|(function, _)| *function == name)
+        .map(|(_, parameters)| *parameters)
+}
+
+/// `format("{:>6.2}", 3.14159)`, `format("U+{:04X(0x1F031)}", index)`.
+pub fn call(prelude: &Prelude, name: &str, arguments: &[ObjectRef]) -> Result<ObjectRef, OpError> {
+    let invalid = || arguments_error(name, function_parameters(name).unwrap_or_default());
+    match (name, arguments) {
+        ("format", [template, values @ ..]) => {
+            let template = template.borrow();
+            let template = as_text(&template).ok_or_else(invalid)?;
+            let values: Vec<_> = values
+                .iter()
+                .map(|value| format_argument(&value.borrow()))
+                .collect();
+            format_template(template, &values)
+                .map(|text| prelude.string_value(text))
+                .map_err(OpError::Format)
+        }
+        _ => Err(invalid()),
+    }
+}
+
+/// Целые (в том числе `0x1F031`) и дробные числа форматируются как числа, остальное — текстом.
+fn format_argument(value: &Object) -> FormatArg {
+    match as_number(value) {
+        Some(Number::Integer(value)) => FormatArg::Integer(value),
+        Some(Number::Float(value)) => FormatArg::Float(value),
+        Some(number @ Number::Fraction(_)) => FormatArg::Float(number.as_f64()),
+        _ => FormatArg::Text(value.to_string()),
+    }
+}
+
+fn arguments_error(function: &str, parameters: &[&str]) -> OpError {
+    OpError::Arguments {
+        function: function.to_string(),
+        parameters: parameters.join(", "),
+    }
+}
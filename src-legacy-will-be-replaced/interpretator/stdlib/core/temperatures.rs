@@ -0,0 +1,180 @@
+use crate::interpretator::frontend::ast::expression::{BinaryOp, UnaryOp};
+use crate::interpretator::prototypes::types::formal::temperature::{
+    TemperatureScale, TemperatureValue,
+};
+use crate::interpretator::prototypes::types::object::object::ObjectRef;
+use crate::interpretator::stdlib::core::ops::OpError;
+use crate::interpretator::stdlib::core::prelude::Prelude;
+use crate::interpretator::stdlib::core::primitives::{as_number, as_temperature, type_name};
+use std::cmp::Ordering;
+
+/// Операнд арифметики над температурами.
+enum Operand {
+    Temperature(TemperatureValue),
+    Number(f64),
+    Other(String),
+}
+
+fn operand(object: &ObjectRef) -> Operand {
+    let object = object.borrow();
+    if let Some(temperature) = as_temperature(&object) {
+        return Operand::Temperature(temperature.clone());
+    }
+    match as_number(&object) {
+        Some(number) => Operand::Number(number.as_f64()),
+        None => Operand::Other(type_name(&object)),
+    }
+}
+
+/// Бинарная операция, в которой участвует хотя бы одна температура.
+/// `None` — температур среди операндов нет или оператор не арифметический.
+///
+/// Правила аффинной шкалы:
+/// - абсолютная ± разность = абсолютная, разность + абсолютная = абсолютная;
+/// - абсолютная − абсолютная = разность;
+/// - разности складываются, вычитаются, умножаются и делятся на число;
+/// - сложить две абсолютные температуры или умножить абсолютную нельзя.
+///
+/// Результат получает шкалу левого операнда: `17.1°C + Δ14°F ≈ 24.88°C`.
+pub fn binary(
+    prelude: &Prelude,
+    op: BinaryOp,
+    left: &ObjectRef,
+    right: &ObjectRef,
+) -> Option<Result<ObjectRef, OpError>> {
+    if !matches!(
+        op,
+        BinaryOp::Add | BinaryOp::Sub | BinaryOp::Mul | BinaryOp::Div
+    ) {
+        return None;
+    }
+    let (left, right) = (operand(left), operand(right));
+    if !matches!(left, Operand::Temperature(_)) && !matches!(right, Operand::Temperature(_)) {
+        return None;
+    }
+    let affine = || OpError::AffineTemperature {
+        op: op.as_str(),
+        left: describe(&left),
+        right: describe(&right),
+    };
+    let value = |value: f64, scale: TemperatureScale, delta: bool| {
+        checked(
+            prelude,
+            TemperatureValue {
+                value,
+                scale,
+                delta,
+            },
+        )
+    };
+
+    let result = match (op, &left, &right) {
+        (BinaryOp::Add | BinaryOp::Sub, Operand::Temperature(a), Operand::Temperature(b)) => {
+            let b_value = b.value_in(a.scale);
+            match (op, a.delta, b.delta) {
+                // Сдвиг абсолютной температуры или сумма разностей
+                (BinaryOp::Add, _, true) => value(a.value + b_value, a.scale, a.delta),
+                (BinaryOp::Sub, _, true) => value(a.value - b_value, a.scale, a.delta),
+                (BinaryOp::Add, true, false) => value(a.value + b_value, a.scale, false),
+                (BinaryOp::Sub, false, false) => value(a.value - b_value, a.scale, true),
+                _ => Err(affine()),
+            }
+        }
+        (BinaryOp::Mul, Operand::Temperature(a), Operand::Number(b))
+        | (BinaryOp::Mul, Operand::Number(b), Operand::Temperature(a))
+            if a.delta =>
+        {
+            value(a.value * b, a.scale, true)
+        }
+        (BinaryOp::Div, Operand::Temperature(a), Operand::Number(b)) if a.delta => {
+            value(a.value / b, a.scale, true)
+        }
+        (BinaryOp::Div, Operand::Temperature(a), Operand::Temperature(b)) if a.delta && b.delta => {
+            Ok(prelude.float_value(a.value / b.value_in(a.scale)))
+        }
+        (_, Operand::Temperature(a), _) | (_, _, Operand::Temperature(a)) if !a.delta => {
+            Err(affine())
+        }
+        _ => Err(OpError::InvalidOperands {
+            op: op.as_str(),
+            left: describe(&left),
+            right: describe(&right),
+        }),
+    };
+    Some(result)
+}
+
+/// `Δ` превращает температуру в разность, `-` и `+` сохраняют вид.
+pub fn unary(
+    prelude: &Prelude,
+    op: UnaryOp,
+    operand: &ObjectRef,
+) -> Option<Result<ObjectRef, OpError>> {
+    let temperature = as_temperature(&operand.borrow())?.clone();
+    match op {
+        UnaryOp::Negate => Some(checked(
+            prelude,
+            TemperatureValue {
+                value: -temperature.value,
+                ..temperature
+            },
+        )),
+        UnaryOp::Delta => Some(Ok(prelude.temperature_value(TemperatureValue {
+            delta: true,
+            ..temperature
+        }))),
+        UnaryOp::Plus => Some(Ok(operand.clone())),
+        UnaryOp::Not => None,
+    }
+}
+
+/// Температура как значение. Абсолютная не бывает ниже абсолютного нуля:
+/// `0K - Δ10K` и `-300°C` — ошибки, а разность может быть любой.
+pub fn checked(prelude: &Prelude, temperature: TemperatureValue) -> Result<ObjectRef, OpError> {
+    let below_zero = !temperature.delta && temperature.value_in(TemperatureScale::Kelvin) < 0.0;
+    let value = prelude.temperature_value(temperature);
+    match below_zero {
+        true => Err(OpError::BelowAbsoluteZero {
+            value: value.borrow().to_string(),
+        }),
+        false => Ok(value),
+    }
+}
+
+/// Сравнение температур одного вида с учётом шкалы.
+pub fn compare(left: &ObjectRef, right: &ObjectRef) -> Option<Ordering> {
+    let (left, right) = (left.borrow(), right.borrow());
+    let (a, b) = (as_temperature(&left)?, as_temperature(&right)?);
+    if a.delta != b.delta {
+        return None;
+    }
+    a.value.partial_cmp(&b.value_in(a.scale))
+}
+
+/// `value -> n°F`: температура пересчитывается в другую шкалу,
+/// а число без единицы становится абсолютной температурой в ней.
+pub fn convert(
+    prelude: &Prelude,
+    value: &ObjectRef,
+    scale: TemperatureScale,
+) -> Result<ObjectRef, OpError> {
+    match operand(value) {
+        Operand::Temperature(temperature) => {
+            Ok(prelude.temperature_value(temperature.convert(scale)))
+        }
+        Operand::Number(number) => checked(prelude, TemperatureValue::absolute(number, scale)),
+        Operand::Other(type_name) => Err(OpError::IncompatibleUnits {
+            from: type_name,
+            to: "Temperature".to_string(),
+            unit: scale.symbol().to_string(),
+        }),
+    }
+}
+
+fn describe(operand: &Operand) -> String {
+    match operand {
+        Operand::Temperature(temperature) => temperature.kind().to_string(),
+        Operand::Number(_) => "Number".to_string(),
+        Operand::Other(type_name) => type_name.clone(),
+    }
+}
@@ -1,5 +1,6 @@
 // src/lib.rs
 
+pub mod extensions;
 pub mod interpretator;
 
 // Импортируем необходимые трейты и модули из стандартной библиотеки